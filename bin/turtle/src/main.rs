@@ -1,8 +1,7 @@
 use turtle_net::server::build_server;
 
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // build our application with a single route
-    build_server().await;
-
-}
\ No newline at end of file
+    build_server().await
+}
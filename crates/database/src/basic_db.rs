@@ -1,5 +1,4 @@
-use std::borrow::Cow;
-use libmdbx::{Database, DatabaseOptions, WriteMap, WriteFlags, TableFlags};
+use libmdbx::{Database, DatabaseOptions, Transaction, WriteMap, WriteFlags, TableFlags, RW};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::path::Path;
@@ -9,6 +8,47 @@ pub struct InnerDatabase {
     db: Arc<Mutex<Database<WriteMap>>>,
 }
 
+// 하나의 쓰기 트랜잭션 안에서 읽고 쓰는 핸들
+pub struct DatabaseTransaction<'txn, 'db> {
+    transaction: &'txn Transaction<'db, RW, WriteMap>,
+}
+
+impl DatabaseTransaction<'_, '_> {
+    pub fn read(&self, key: &str, table: &str) -> Result<Option<Vec<u8>>, libmdbx::Error> {
+        match self.transaction.open_table(Some(table)) {
+            Ok(table) => self.transaction.get(&table, key.as_bytes()),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn read_all(&self, table: &str) -> Result<HashMap<Vec<u8>, Vec<u8>>, libmdbx::Error> {
+        let mut map = HashMap::new();
+
+        if let Ok(table) = self.transaction.open_table(Some(table)) {
+            let cursor = self.transaction.cursor(&table)?;
+
+            for item in cursor {
+                let (key, value) = item?;
+                map.insert(key.to_vec(), value.to_vec());
+            }
+        }
+
+        Ok(map)
+    }
+
+    pub fn write(&self, key: &str, value: &str, table: &str) -> Result<(), libmdbx::Error> {
+        let table = self.transaction.create_table(Some(table), TableFlags::default())?;
+        self.transaction.put(&table, key, value, WriteFlags::default())
+    }
+
+    pub fn delete(&self, key: &str, table: &str) -> Result<bool, libmdbx::Error> {
+        match self.transaction.open_table(Some(table)) {
+            Ok(table) => self.transaction.del(&table, key.as_bytes(), None),
+            Err(_) => Ok(false),
+        }
+    }
+}
+
 pub trait SafeDatabase {
 
     fn new<P: AsRef<Path>>(path: P) -> Result<Self, libmdbx::Error> where Self: Sized;
@@ -24,18 +64,29 @@ pub trait SafeDatabase {
 
     fn read_all(&self, table: &str) -> Result<HashMap<Vec<u8>, Vec<u8>>, libmdbx::Error>;
 
+    // 키가 존재해서 삭제했으면 true
+    fn delete(&self, key: &str, table: &str) -> Result<bool, libmdbx::Error>;
+
     fn batch_write<K, V>(&self, items: &[(K, V)], table: &str) -> Result<(), libmdbx::Error>
     where
         K: AsRef<[u8]>,
         V: AsRef<[u8]>;
+
+    // read-modify-write를 원자적으로 수행. f가 Err를 반환하면 아무것도 기록하지 않음
+    // (f 안에서 SafeDatabase 메서드를 다시 호출하면 교착 상태가 되므로 전달된 핸들만 사용)
+    fn transaction<F, R, E>(&self, f: F) -> Result<Result<R, E>, libmdbx::Error>
+    where
+        F: FnOnce(&DatabaseTransaction<'_, '_>) -> Result<R, E>;
 }
 
 
 impl SafeDatabase for InnerDatabase{
 
     fn new<P: AsRef<Path>>(path: P) -> Result<Self, libmdbx::Error> {
-        let mut options = DatabaseOptions::default();
-        options.max_tables = Some(100);
+        let options = DatabaseOptions {
+            max_tables: Some(100),
+            ..Default::default()
+        };
         let db = Database::<WriteMap>::open_with_options(path, options)?;
 
         Ok(Self {
@@ -92,6 +143,19 @@ impl SafeDatabase for InnerDatabase{
         Ok(map)
     }
 
+    fn delete(&self, key: &str, table: &str) -> Result<bool, libmdbx::Error> {
+        let db = self.db.lock().expect("Failed to lock database mutex");
+        let transaction = db.begin_rw_txn()?;
+
+        let deleted = match transaction.open_table(Some(table)) {
+            Ok(table) => transaction.del(&table, key.as_bytes(), None)?,
+            Err(_) => false,
+        };
+
+        transaction.commit()?;
+        Ok(deleted)
+    }
+
 
     fn batch_write<K, V>(&self, items: &[(K, V)], table: &str) -> Result<(), libmdbx::Error>
     where
@@ -109,6 +173,25 @@ impl SafeDatabase for InnerDatabase{
         transaction.commit()?;
         Ok(())
     }
+
+    fn transaction<F, R, E>(&self, f: F) -> Result<Result<R, E>, libmdbx::Error>
+    where
+        F: FnOnce(&DatabaseTransaction<'_, '_>) -> Result<R, E>,
+    {
+        let db = self.db.lock().expect("Failed to lock database mutex");
+        let transaction = db.begin_rw_txn()?;
+
+        let result = f(&DatabaseTransaction {
+            transaction: &transaction,
+        });
+
+        // 실패하면 트랜잭션을 커밋하지 않고 버림
+        if result.is_ok() {
+            transaction.commit()?;
+        }
+
+        Ok(result)
+    }
 }
 
 
@@ -0,0 +1,47 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::error::Error as StdError;
+use std::fmt;
+
+// 테이블을 읽고 쓰는 모듈(작업, 예치, 라운드, 정산 등)이 공통으로 쓰는 에러
+#[derive(Debug, PartialEq, Eq)]
+pub enum StorageError {
+    Database(String),
+    Serialization(String),
+    NotFound(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Database(msg) => write!(f, "Database error: {}", msg),
+            StorageError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            StorageError::NotFound(msg) => write!(f, "Not found: {}", msg),
+        }
+    }
+}
+
+impl StdError for StorageError {}
+
+impl From<libmdbx::Error> for StorageError {
+    fn from(e: libmdbx::Error) -> Self {
+        StorageError::Database(e.to_string())
+    }
+}
+
+pub fn database_error(e: impl fmt::Display) -> StorageError {
+    StorageError::Database(e.to_string())
+}
+
+// 저장된 JSON 값을 역직렬화
+pub fn decode<V: DeserializeOwned>(data: &[u8]) -> Result<V, StorageError> {
+    serde_json::from_slice(data).map_err(|e| StorageError::Serialization(format!("Invalid JSON: {}", e)))
+}
+
+pub fn encode<V: Serialize>(value: &V) -> Result<String, StorageError> {
+    serde_json::to_string(value).map_err(|e| StorageError::Serialization(e.to_string()))
+}
+
+pub fn not_found(msg: impl Into<String>) -> StorageError {
+    StorageError::NotFound(msg.into())
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use crate::basic_db::{DatabaseTransaction, SafeDatabase};
+use crate::error::{decode, encode, StorageError};
+
+// 스케줄러 작업 정의와 실행 상태를 저장하는 테이블 (key: 작업 이름)
+pub const JOBS_TABLE: &str = "jobs";
+
+// 재시도 대기 시간 상한(초)
+pub const MAX_BACKOFF_SECS: u64 = 3600;
+
+// 작업 정의
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobDefinition {
+    pub name: String,
+    pub interval_secs: u64,         // 성공 후 다음 실행까지 간격
+    pub max_retries: u32,           // 연속 실패 허용 횟수. 넘으면 다음 주기까지 대기
+    pub backoff_secs: u64,          // 첫 재시도 대기 시간. 실패할 때마다 두 배
+    pub lease_secs: u64,            // 실행 중 잠금 유지 시간. 만료되면 다른 인스턴스가 가져감
+}
+
+// 작업 실행 상태
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobState {
+    pub next_run_at: u64,
+    pub last_started_at: Option<u64>,
+    pub last_success_at: Option<u64>,
+    pub last_error: Option<String>,
+    pub last_result: Option<String>,
+    pub failures: u32,              // 연속 실패 횟수
+    pub lease_owner: Option<String>,
+    pub lease_expires_at: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub definition: JobDefinition,
+    pub state: JobState,
+}
+
+pub type JobError = StorageError;
+
+fn read_record(txn: &DatabaseTransaction<'_, '_>, name: &str) -> Result<JobRecord, JobError> {
+    let data = txn.read(name, JOBS_TABLE)?
+        .ok_or_else(|| JobError::NotFound(format!("Job {} not found", name)))?;
+
+    decode(&data)
+}
+
+fn write_record(txn: &DatabaseTransaction<'_, '_>, record: &JobRecord) -> Result<(), JobError> {
+    txn.write(&record.definition.name, &encode(record)?, JOBS_TABLE)?;
+    Ok(())
+}
+
+// 작업 정의 등록. 이미 있으면 정의만 갱신하고 실행 상태는 유지
+pub fn register_job<T: SafeDatabase>(database: &T, definition: JobDefinition, now: u64) -> Result<(), JobError> {
+    database.transaction(|txn| {
+        let state = match read_record(txn, &definition.name) {
+            Ok(existing) => existing.state,
+            Err(JobError::NotFound(_)) => JobState {
+                next_run_at: now,
+                ..Default::default()
+            },
+            Err(e) => return Err(e),
+        };
+
+        write_record(txn, &JobRecord { definition, state })
+    })?
+}
+
+// 이름순으로 정렬된 모든 작업
+pub fn list_jobs<T: SafeDatabase>(database: &T) -> Result<Vec<JobRecord>, JobError> {
+    let mut records = database.read_all(JOBS_TABLE)?
+        .into_values()
+        .map(|data| decode::<JobRecord>(&data))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    records.sort_by(|a, b| a.definition.name.cmp(&b.definition.name));
+    Ok(records)
+}
+
+// 실행할 때가 된 작업의 잠금 획득. 다른 인스턴스가 유효한 잠금을 가지고 있으면 false
+pub fn acquire_job<T: SafeDatabase>(database: &T, name: &str, owner: &str, now: u64) -> Result<bool, JobError> {
+    database.transaction(|txn| {
+        let mut record = read_record(txn, name)?;
+
+        let leased_by_other = record.state.lease_owner.as_deref().is_some_and(|current| current != owner)
+            && record.state.lease_expires_at > now;
+        if leased_by_other || record.state.next_run_at > now {
+            return Ok(false);
+        }
+
+        record.state.lease_owner = Some(owner.to_string());
+        record.state.lease_expires_at = now.saturating_add(record.definition.lease_secs);
+        record.state.last_started_at = Some(now);
+        write_record(txn, &record)?;
+
+        Ok(true)
+    })?
+}
+
+// 실패 횟수에 따른 재시도 대기 시간 (backoff_secs * 2^(failures-1), 상한 MAX_BACKOFF_SECS)
+pub fn backoff_delay(definition: &JobDefinition, failures: u32) -> u64 {
+    let exponent = failures.saturating_sub(1).min(32);
+    definition.backoff_secs
+        .saturating_mul(1u64 << exponent)
+        .min(MAX_BACKOFF_SECS)
+}
+
+// 실행 결과 기록 후 잠금 해제. 실패하면 백오프 후 재시도하고, 재시도를 모두 쓰면 다음 주기로 넘김
+pub fn complete_job<T: SafeDatabase>(
+    database: &T,
+    name: &str,
+    owner: &str,
+    now: u64,
+    result: Result<String, String>,
+) -> Result<(), JobError> {
+    database.transaction(|txn| {
+        let mut record = read_record(txn, name)?;
+
+        // 잠금이 만료되어 다른 인스턴스가 가져간 경우 결과를 덮어쓰지 않음
+        if record.state.lease_owner.as_deref() != Some(owner) {
+            return Ok(());
+        }
+
+        match result {
+            Ok(summary) => {
+                record.state.failures = 0;
+                record.state.last_success_at = Some(now);
+                record.state.last_result = Some(summary);
+                record.state.last_error = None;
+                record.state.next_run_at = now.saturating_add(record.definition.interval_secs);
+            },
+            Err(error) => {
+                record.state.failures += 1;
+                record.state.last_error = Some(error);
+                record.state.next_run_at = if record.state.failures > record.definition.max_retries {
+                    record.state.failures = 0;
+                    now.saturating_add(record.definition.interval_secs)
+                } else {
+                    now.saturating_add(backoff_delay(&record.definition, record.state.failures))
+                };
+            },
+        }
+
+        record.state.lease_owner = None;
+        record.state.lease_expires_at = 0;
+        write_record(txn, &record)
+    })?
+}
+
+// 다음 스케줄러 주기에 바로 실행되도록 예약
+pub fn trigger_job<T: SafeDatabase>(database: &T, name: &str, now: u64) -> Result<JobRecord, JobError> {
+    database.transaction(|txn| {
+        let mut record = read_record(txn, name)?;
+        record.state.next_run_at = now;
+        record.state.failures = 0;
+        write_record(txn, &record)?;
+        Ok(record)
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::basic_db::InnerDatabase;
+    use tempfile::tempdir;
+
+    fn definition() -> JobDefinition {
+        JobDefinition {
+            name: "close_proposals".to_string(),
+            interval_secs: 60,
+            max_retries: 2,
+            backoff_secs: 5,
+            lease_secs: 30,
+        }
+    }
+
+    #[test]
+    fn test_only_one_owner_holds_the_lease() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        register_job(&db, definition(), 100)?;
+
+        assert!(acquire_job(&db, "close_proposals", "a", 100)?);
+        assert!(!acquire_job(&db, "close_proposals", "b", 110)?);
+        // 잠금이 만료되면 다른 인스턴스가 가져갈 수 있음
+        assert!(acquire_job(&db, "close_proposals", "b", 131)?);
+
+        // 만료된 소유자의 결과는 무시
+        complete_job(&db, "close_proposals", "a", 132, Ok("late".to_string()))?;
+        assert_eq!(list_jobs(&db)?[0].state.lease_owner.as_deref(), Some("b"));
+
+        complete_job(&db, "close_proposals", "b", 140, Ok("done".to_string()))?;
+        let state = &list_jobs(&db)?[0].state;
+        assert_eq!(state.next_run_at, 200);
+        assert_eq!(state.last_result.as_deref(), Some("done"));
+        assert!(!acquire_job(&db, "close_proposals", "a", 150)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_failures_back_off_then_wait_for_next_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        register_job(&db, definition(), 0)?;
+
+        let mut now = 0;
+        let mut delays = Vec::new();
+        for _ in 0..3 {
+            assert!(acquire_job(&db, "close_proposals", "a", now)?);
+            complete_job(&db, "close_proposals", "a", now, Err("boom".to_string()))?;
+            let next = list_jobs(&db)?[0].state.next_run_at;
+            delays.push(next - now);
+            now = next;
+        }
+        // 5초, 10초 후 재시도, 재시도를 모두 쓰면 다음 주기(60초)
+        assert_eq!(delays, vec![5, 10, 60]);
+
+        // 수동 실행 요청은 바로 실행 가능하게 만들고 정의는 재등록해도 상태 유지
+        trigger_job(&db, "close_proposals", 1)?;
+        register_job(&db, definition(), 999)?;
+        assert!(acquire_job(&db, "close_proposals", "a", 1)?);
+
+        Ok(())
+    }
+}
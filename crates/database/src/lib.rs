@@ -1 +1,3 @@
 pub mod basic_db;
+pub mod error;
+pub mod jobs;
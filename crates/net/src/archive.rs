@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_service::sequence::next_id;
+use crate::clock::unix_now;
+
+// 소프트 삭제된 레코드를 감사용으로 보관하는 테이블
+pub const DELETED_RECORDS_TABLE: &str = "deleted_records";
+
+// DELETE 요청 공통 쿼리
+#[derive(Deserialize)]
+pub struct DeleteQuery {
+    #[serde(default)]
+    pub hard: bool,                     // true면 감사 기록 없이 영구 삭제
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DeletedRecord {
+    pub table: String,
+    pub key: String,
+    pub data: String,                   // 삭제 시점의 원본 JSON
+    pub deleted_at: u64,
+    pub deleted_by: Option<String>,     // 삭제를 요청한 지갑
+}
+
+// 트랜잭션 안에서 레코드 삭제. hard가 아니면 원본을 deleted_records 테이블(table:key:deleted_at:n)에 먼저 남김
+pub fn archive_record(
+    txn: &DatabaseTransaction<'_, '_>,
+    table: &str,
+    key: &str,
+    data: &str,
+    deleted_by: Option<&str>,
+    hard: bool,
+) -> Result<bool, String> {
+    if !hard {
+        let deleted_at = unix_now();
+
+        let record = DeletedRecord {
+            table: table.to_string(),
+            key: key.to_string(),
+            data: data.to_string(),
+            deleted_at,
+            deleted_by: deleted_by.map(|pubkey| pubkey.to_string()),
+        };
+
+        let record_json = serde_json::to_string(&record)
+            .map_err(|e| e.to_string())?;
+
+        // 같은 초에 같은 키가 다시 삭제되어도 이전 기록을 덮어쓰지 않도록 키별 순번을 붙임
+        let sequence = next_id(txn, DELETED_RECORDS_TABLE, &format!("{}:{}", table, key))
+            .map_err(|e| e.to_string())?;
+        txn.write(&format!("{}:{}:{}:{}", table, key, deleted_at, sequence), &record_json, DELETED_RECORDS_TABLE)
+            .map_err(|e| e.to_string())?;
+    }
+
+    txn.delete(key, table)
+        .map_err(|e| e.to_string())
+}
+
+pub fn remove_record<T: SafeDatabase>(
+    database: &T,
+    table: &str,
+    key: &str,
+    data: &str,
+    deleted_by: Option<&str>,
+    hard: bool,
+) -> Result<bool, String> {
+    database.transaction(|txn| archive_record(txn, table, key, data, deleted_by, hard))
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use turtle_database::basic_db::InnerDatabase;
+
+    #[test]
+    fn test_deletes_in_the_same_second_keep_every_record() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        // 같은 키를 다시 만들고 곧바로 삭제해도 감사 기록이 모두 남음
+        for data in ["{\"v\":1}", "{\"v\":2}"] {
+            db.write("pda1_1", data, "content")?;
+            remove_record(&db, "content", "pda1_1", data, Some("admin"), false)?;
+        }
+        assert_eq!(db.read_all(DELETED_RECORDS_TABLE)?.len(), 2);
+
+        Ok(())
+    }
+}
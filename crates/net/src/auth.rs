@@ -0,0 +1,204 @@
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use std::error::Error as StdError;
+use std::fmt;
+use crate::error::ErrorBody;
+use crate::clock::unix_now;
+use sol::pubkey::parse_pubkey;
+
+// 지갑 서명 인증 헤더
+pub const WALLET_HEADER: &str = "x-turtle-wallet";
+pub const SIGNATURE_HEADER: &str = "x-turtle-signature";
+pub const TIMESTAMP_HEADER: &str = "x-turtle-timestamp";
+
+// 서명 타임스탬프 허용 오차(초)
+pub const MAX_CLOCK_SKEW_SECS: u64 = 300;
+
+// 서명 검증을 통과한 호출자 지갑
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalletIdentity {
+    pub pubkey: String,
+}
+
+#[derive(Debug)]
+pub enum AuthError {
+    MissingHeader(&'static str),
+    InvalidPubkey(String),
+    InvalidSignature(String),
+    StaleTimestamp(u64),
+    ReplayedSignature,
+}
+
+impl AuthError {
+    fn reason(&self) -> &'static str {
+        match self {
+            AuthError::MissingHeader(_) => "missing_header",
+            AuthError::InvalidPubkey(_) => "invalid_pubkey",
+            AuthError::InvalidSignature(_) => "invalid_signature",
+            AuthError::StaleTimestamp(_) => "stale_timestamp",
+            AuthError::ReplayedSignature => "replayed_signature",
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingHeader(name) => write!(f, "Missing header: {}", name),
+            AuthError::InvalidPubkey(msg) => write!(f, "Invalid wallet pubkey: {}", msg),
+            AuthError::InvalidSignature(msg) => write!(f, "Invalid signature: {}", msg),
+            AuthError::StaleTimestamp(ts) => write!(f, "Signature timestamp {} is outside the allowed window", ts),
+            AuthError::ReplayedSignature => write!(f, "Signature has already been used"),
+        }
+    }
+}
+
+impl StdError for AuthError {}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        ErrorBody::new("unauthorized", self.to_string())
+            .with_reason(self.reason())
+            .respond(StatusCode::UNAUTHORIZED)
+    }
+}
+
+// 클라이언트가 서명해야 하는 메시지 (메서드, 경로+쿼리, 타임스탬프)
+pub fn signing_message(method: &Method, path_and_query: &str, timestamp: u64) -> String {
+    format!("turtle:{}:{}:{}", method, path_and_query, timestamp)
+}
+
+// base58 공개키와 서명으로 ed25519 서명 검증
+pub fn verify_wallet_signature(pubkey: &str, message: &[u8], signature: &str) -> Result<(), AuthError> {
+    let key_bytes = parse_pubkey(pubkey)
+        .map_err(|e| AuthError::InvalidPubkey(e.to_string()))?
+        .to_bytes();
+
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| AuthError::InvalidPubkey(e.to_string()))?;
+
+    let signature_bytes: [u8; 64] = bs58::decode(signature)
+        .into_vec()
+        .map_err(|e| AuthError::InvalidSignature(e.to_string()))?
+        .try_into()
+        .map_err(|_| AuthError::InvalidSignature("signature must be 64 bytes".to_string()))?;
+
+    verifying_key
+        .verify(message, &Signature::from_bytes(&signature_bytes))
+        .map_err(|e| AuthError::InvalidSignature(e.to_string()))
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, AuthError> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .ok_or(AuthError::MissingHeader(name))
+}
+
+// 요청 헤더에서 지갑 서명을 검증하고 호출자 지갑 반환
+pub fn authenticate(parts: &Parts) -> Result<WalletIdentity, AuthError> {
+    let pubkey = header_value(&parts.headers, WALLET_HEADER)?;
+    let signature = header_value(&parts.headers, SIGNATURE_HEADER)?;
+    let timestamp: u64 = header_value(&parts.headers, TIMESTAMP_HEADER)?
+        .parse()
+        .map_err(|_| AuthError::MissingHeader(TIMESTAMP_HEADER))?;
+
+    let now = unix_now();
+
+    if now.abs_diff(timestamp) > MAX_CLOCK_SKEW_SECS {
+        return Err(AuthError::StaleTimestamp(timestamp));
+    }
+
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+    let message = signing_message(&parts.method, path_and_query, timestamp);
+
+    verify_wallet_signature(pubkey, message.as_bytes(), signature)?;
+
+    Ok(WalletIdentity {
+        pubkey: pubkey.to_string(),
+    })
+}
+
+impl<S> FromRequestParts<S> for WalletIdentity
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        // 정책 미들웨어에서 이미 인증했다면 재검증하지 않음
+        if let Some(identity) = parts.extensions.get::<WalletIdentity>() {
+            return Ok(identity.clone());
+        }
+
+        authenticate(parts)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use axum::http::{HeaderValue, Request};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    // 테스트용 지갑 키와 서명 헤더 생성
+    pub(crate) fn test_wallet(seed: u8) -> (SigningKey, String) {
+        let signing_key = SigningKey::from_bytes(&[seed; 32]);
+        let pubkey = bs58::encode(signing_key.verifying_key().to_bytes()).into_string();
+        (signing_key, pubkey)
+    }
+
+    pub(crate) fn sign_headers(signing_key: &SigningKey, method: &Method, path_and_query: &str) -> Vec<(&'static str, String)> {
+        let timestamp = unix_now();
+        let message = signing_message(method, path_and_query, timestamp);
+        let signature = signing_key.sign(message.as_bytes());
+
+        vec![
+            (WALLET_HEADER, bs58::encode(signing_key.verifying_key().to_bytes()).into_string()),
+            (SIGNATURE_HEADER, bs58::encode(signature.to_bytes()).into_string()),
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+        ]
+    }
+
+    fn parts_with_headers(uri: &str, headers: Vec<(&'static str, String)>) -> Parts {
+        let mut request = Request::builder().method(Method::POST).uri(uri).body(()).unwrap();
+        for (name, value) in headers {
+            request.headers_mut().insert(name, HeaderValue::from_str(&value).unwrap());
+        }
+        request.into_parts().0
+    }
+
+    #[test]
+    fn test_authenticate_valid_signature() {
+        let (signing_key, pubkey) = test_wallet(1);
+        let headers = sign_headers(&signing_key, &Method::POST, "/api/dao/community?pda=abc");
+        let parts = parts_with_headers("/api/dao/community?pda=abc", headers);
+
+        let identity = authenticate(&parts).unwrap();
+        assert_eq!(identity.pubkey, pubkey);
+    }
+
+    #[test]
+    fn test_authenticate_rejects_signature_for_other_path() {
+        let (signing_key, _) = test_wallet(1);
+        let headers = sign_headers(&signing_key, &Method::POST, "/api/dao/community?pda=abc");
+        let parts = parts_with_headers("/api/dao/community?pda=other", headers);
+
+        assert!(matches!(authenticate(&parts), Err(AuthError::InvalidSignature(_))));
+    }
+
+    #[test]
+    fn test_authenticate_missing_headers() {
+        let parts = parts_with_headers("/api/dao/community?pda=abc", vec![]);
+
+        assert!(matches!(authenticate(&parts), Err(AuthError::MissingHeader(WALLET_HEADER))));
+    }
+}
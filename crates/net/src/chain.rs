@@ -0,0 +1,295 @@
+use sol::account::{AccountDecoder, AccountError};
+use sol::pda::{community_pda, depositor_pda, PdaError};
+use sol::pubkey::{parse_wallet, validate_pubkey, Curve, Pubkey, PubkeyError};
+use sol::rpc::{AccountSource, RpcAccountSource};
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::Arc;
+use turtle_service::parser::community::{Community, Content, Depositor, Proposal};
+use crate::error::ApiError;
+
+// 온체인 프로그램 ID 환경 변수 (설정하면 커뮤니티 PDA를 관리자 지갑에서 유도해 대조)
+pub const PROGRAM_ID_ENV: &str = "TURTLE_PROGRAM_ID";
+
+// 온체인 계정 디코딩에 쓰는 Anchor IDL 파일 경로 환경 변수
+pub const IDL_PATH_ENV: &str = "TURTLE_IDL_PATH";
+
+// 예치 계정을 확인할 Solana JSON-RPC 주소 환경 변수
+pub const RPC_URL_ENV: &str = "TURTLE_RPC_URL";
+
+// 시작 시 설정을 읽지 못한 이유. 잘못된 설정이면 서버를 띄우지 않음
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChainConfigError {
+    InvalidProgramId(PubkeyError),
+    InvalidIdl { path: String, error: AccountError },
+}
+
+impl fmt::Display for ChainConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainConfigError::InvalidProgramId(e) => write!(f, "{} is invalid: {}", PROGRAM_ID_ENV, e),
+            ChainConfigError::InvalidIdl { path, error } => write!(f, "{}={} could not be loaded: {}", IDL_PATH_ENV, path, error),
+        }
+    }
+}
+
+impl StdError for ChainConfigError {}
+
+// Turtle 프로그램 계정을 서비스 구조체로 변환한 결과
+pub enum TurtleAccount {
+    Community(Community),
+    Content(Content),
+    Depositor(Depositor),
+    Proposal(Proposal),
+}
+
+// IDL 디코더로 읽은 계정을 이름에 맞는 서비스 구조체로 변환
+pub fn decode_turtle(decoder: &AccountDecoder, data: &[u8]) -> Result<TurtleAccount, AccountError> {
+    let (name, value) = decoder.decode_value(data)?;
+    let mismatch = |e: serde_json::Error| AccountError::Mismatch(e.to_string());
+
+    match name {
+        "Community" => serde_json::from_value(value).map(TurtleAccount::Community).map_err(mismatch),
+        "Content" => serde_json::from_value(value).map(TurtleAccount::Content).map_err(mismatch),
+        "Depositor" => serde_json::from_value(value).map(TurtleAccount::Depositor).map_err(mismatch),
+        "Proposal" => serde_json::from_value(value).map(TurtleAccount::Proposal).map_err(mismatch),
+        other => Err(AccountError::UnknownAccount(other.to_string())),
+    }
+}
+
+// 서버 시작 시 한 번 읽는 온체인 설정. 핸들러에는 Extension으로 전달
+#[derive(Clone, Debug, Default)]
+pub struct ChainConfig {
+    program_id: Option<Pubkey>,
+    decoder: Option<Arc<AccountDecoder>>,
+    accounts: Option<Arc<dyn AccountSource>>,
+}
+
+impl ChainConfig {
+    pub fn new(program_id: Option<Pubkey>) -> Self {
+        Self {
+            program_id,
+            decoder: None,
+            accounts: None,
+        }
+    }
+
+    pub fn with_decoder(mut self, decoder: AccountDecoder) -> Self {
+        self.decoder = Some(Arc::new(decoder));
+        self
+    }
+
+    pub fn with_accounts(mut self, accounts: impl AccountSource + 'static) -> Self {
+        self.accounts = Some(Arc::new(accounts));
+        self
+    }
+
+    pub fn from_env() -> Result<Self, ChainConfigError> {
+        let mut config = Self::parse(std::env::var(PROGRAM_ID_ENV).ok().as_deref())?;
+
+        if let Some(url) = std::env::var(RPC_URL_ENV).ok().filter(|url| !url.trim().is_empty()) {
+            config = config.with_accounts(RpcAccountSource::new(url.trim()));
+        }
+
+        match std::env::var(IDL_PATH_ENV).ok().filter(|path| !path.trim().is_empty()) {
+            Some(path) => config.load_idl(&path),
+            None => Ok(config),
+        }
+    }
+
+    // IDL 파일을 읽어 디코더로 등록. 실패하면 환경 변수와 경로를 담아 반환
+    pub fn load_idl(self, path: &str) -> Result<Self, ChainConfigError> {
+        let decoder = AccountDecoder::load(path)
+            .map_err(|error| ChainConfigError::InvalidIdl { path: path.to_string(), error })?;
+        Ok(self.with_decoder(decoder))
+    }
+
+    // 비어 있으면 프로그램 ID 없이 동작
+    pub fn parse(program_id: Option<&str>) -> Result<Self, ChainConfigError> {
+        let program_id = match program_id.map(str::trim).filter(|value| !value.is_empty()) {
+            Some(value) => Some(validate_pubkey(value, Curve::Any).map_err(ChainConfigError::InvalidProgramId)?),
+            None => None,
+        };
+        Ok(Self::new(program_id))
+    }
+
+    pub fn program_id(&self) -> Option<&Pubkey> {
+        self.program_id.as_ref()
+    }
+
+    pub fn decoder(&self) -> Option<&AccountDecoder> {
+        self.decoder.as_deref()
+    }
+
+    // 지갑의 온체인 예치 계정을 읽어 디코딩. 계정은 커뮤니티와 지갑에서 유도한 PDA이고
+    // 프로그램이 소유해야 하며, 기록된 지갑이 요청한 지갑과 같아야 함. 블로킹 RPC 호출
+    pub fn verified_depositor(&self, community: &str, wallet: &str) -> Result<Depositor, ApiError> {
+        let (Some(program_id), Some(decoder), Some(accounts)) = (&self.program_id, self.decoder(), &self.accounts) else {
+            return Err(ApiError::UnavailableError(format!(
+                "Deposits require {}, {} and {} to verify on-chain accounts", PROGRAM_ID_ENV, IDL_PATH_ENV, RPC_URL_ENV,
+            )));
+        };
+
+        let community = validate_pubkey(community, Curve::OffCurve).map_err(|e| ApiError::invalid_field("pda", e))?;
+        let wallet = parse_wallet(wallet).map_err(|e| ApiError::invalid_field("pubkey", e))?;
+        let (address, _) = depositor_pda(&community, &wallet, program_id).map_err(|e| ApiError::invalid_field("pubkey", e))?;
+
+        let account = accounts.account(&address)
+            .map_err(|e| ApiError::UnavailableError(e.to_string()))?
+            .ok_or_else(|| ApiError::ValidationError(format!("No on-chain deposit account {} for wallet {}", address, wallet)))?;
+        if account.owner != *program_id {
+            return Err(ApiError::ValidationError(format!("Deposit account {} is not owned by the program", address)));
+        }
+
+        let depositor: Depositor = decoder.decode("Depositor", &account.data)
+            .map_err(|e| ApiError::ValidationError(format!("Deposit account {} could not be decoded: {}", address, e)))?;
+        if depositor.pubkey != wallet.to_string() {
+            return Err(ApiError::ValidationError(format!("Deposit account {} belongs to {}", address, depositor.pubkey)));
+        }
+        Ok(depositor)
+    }
+
+    // 프로그램 ID가 설정된 경우 관리자 지갑에서 유도한 커뮤니티 PDA와 대조
+    pub fn check_community_derivation(&self, field: &'static str, address: &str, admin: &str) -> Result<(), ApiError> {
+        let Some(program_id) = &self.program_id else {
+            return Ok(());
+        };
+        let admin = parse_wallet(admin).map_err(|e| ApiError::invalid_field("admin", e))?;
+        let (expected, _) = community_pda(&admin, program_id).map_err(|e| ApiError::invalid_field(field, e))?;
+        if expected.to_string() != address {
+            return Err(ApiError::invalid_field(field, PdaError::Mismatch { expected: expected.to_string() }));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use sol::account::account_discriminator;
+    use sol::rpc::{ChainAccount, RpcError};
+    use std::collections::HashMap;
+    use turtle_service::parser::community::ProposalKind;
+
+    const TURTLE_IDL: &str = include_str!("../../sol/fixtures/turtle_idl.json");
+    const COMMUNITY_ACCOUNT: &[u8] = include_bytes!("../../sol/fixtures/community.bin");
+    const DEPOSITOR_ACCOUNT: &[u8] = include_bytes!("../../sol/fixtures/depositor.bin");
+    const PROPOSAL_ACCOUNT: &[u8] = include_bytes!("../../sol/fixtures/proposal.bin");
+
+    // 메모리에 둔 온체인 계정
+    #[derive(Debug, Default)]
+    pub(crate) struct FakeAccounts(pub HashMap<Pubkey, ChainAccount>);
+
+    impl AccountSource for FakeAccounts {
+        fn account(&self, address: &Pubkey) -> Result<Option<ChainAccount>, RpcError> {
+            Ok(self.0.get(address).cloned())
+        }
+    }
+
+    // IDL 순서(pubkey, amount, locked_until, voting_power)로 Borsh 인코딩한 Depositor 계정
+    pub(crate) fn depositor_account(wallet: &Pubkey, amount: u64, locked_until: u64) -> Vec<u8> {
+        let mut data = account_discriminator("Depositor").to_vec();
+        data.extend_from_slice(wallet.as_ref());
+        for value in [amount, locked_until, amount] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data
+    }
+
+    // 프로그램 ID, IDL 디코더, 지갑별 예치 계정(잔액, 잠금 시간)을 갖춘 설정
+    pub(crate) fn chain_with_deposits(program_id: Pubkey, community: &str, deposits: &[(&str, u64, u64)]) -> ChainConfig {
+        let community = validate_pubkey(community, Curve::OffCurve).unwrap();
+        let mut accounts = FakeAccounts::default();
+        for (wallet, amount, locked_until) in deposits {
+            let wallet = parse_wallet(wallet).unwrap();
+            let (address, _) = depositor_pda(&community, &wallet, &program_id).unwrap();
+            accounts.0.insert(address, ChainAccount { owner: program_id, data: depositor_account(&wallet, *amount, *locked_until) });
+        }
+        ChainConfig::new(Some(program_id))
+            .with_decoder(AccountDecoder::from_idl(TURTLE_IDL).unwrap())
+            .with_accounts(accounts)
+    }
+
+    #[test]
+    fn test_parse_program_id() {
+        let program_id = Pubkey::new_from_array([7; 32]);
+
+        assert_eq!(ChainConfig::parse(None).unwrap().program_id(), None);
+        assert_eq!(ChainConfig::parse(Some("  ")).unwrap().program_id(), None);
+        assert_eq!(ChainConfig::parse(Some(&program_id.to_string())).unwrap().program_id(), Some(&program_id));
+        assert!(matches!(ChainConfig::parse(Some("not-a-key")), Err(ChainConfigError::InvalidProgramId(_))));
+    }
+
+    #[test]
+    fn test_decode_turtle_accounts_into_service_structs() -> Result<(), Box<dyn std::error::Error>> {
+        let decoder = AccountDecoder::from_idl(TURTLE_IDL)?;
+
+        let TurtleAccount::Community(community) = decode_turtle(&decoder, COMMUNITY_ACCOUNT)? else {
+            return Err("expected Community".into());
+        };
+        assert_eq!((community.time_limit, community.base_fee, community.deposit_share), (3600, 100, 50));
+        assert_eq!((community.active_proposal_count, community.content_count, community.depositor_count), (1, 2, 3));
+
+        let TurtleAccount::Depositor(depositor) = decode_turtle(&decoder, DEPOSITOR_ACCOUNT)? else {
+            return Err("expected Depositor".into());
+        };
+        assert_eq!((depositor.amount, depositor.locked_until, depositor.unlocked_at), (5000, 1_800_000_000, None));
+
+        // 온체인 proposal_type/new_value는 kind로 변환
+        let TurtleAccount::Proposal(proposal) = decode_turtle(&decoder, PROPOSAL_ACCOUNT)? else {
+            return Err("expected Proposal".into());
+        };
+        assert_eq!(proposal.kind, ProposalKind::BaseFee(250));
+        assert_eq!((proposal.yes_votes, proposal.no_votes, proposal.is_executed), (30, 10, false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verified_depositor_checks_derivation_owner_and_wallet() -> Result<(), Box<dyn std::error::Error>> {
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let (_, admin) = crate::auth::tests::test_wallet(1);
+        let (_, alice) = crate::auth::tests::test_wallet(2);
+        let (_, bob) = crate::auth::tests::test_wallet(3);
+        let (community, _) = community_pda(&parse_wallet(&admin)?, &program_id)?;
+        let community = community.to_string();
+
+        // 온체인 설정이 없으면 예치를 확인할 수 없음
+        let unconfigured = ChainConfig::new(Some(program_id)).verified_depositor(&community, &alice);
+        assert!(matches!(unconfigured, Err(ApiError::UnavailableError(_))));
+
+        let chain = chain_with_deposits(program_id, &community, &[(&alice, 500, 1_800_000_000)]);
+        let depositor = chain.verified_depositor(&community, &alice)?;
+        assert_eq!((depositor.pubkey.as_str(), depositor.amount, depositor.locked_until), (alice.as_str(), 500, 1_800_000_000));
+        assert!(matches!(chain.verified_depositor(&community, &bob), Err(ApiError::ValidationError(_))));
+
+        // 다른 프로그램이 소유한 계정이나 다른 지갑이 기록된 계정은 거부
+        let alice_key = parse_wallet(&alice)?;
+        let (address, _) = depositor_pda(&validate_pubkey(&community, Curve::OffCurve)?, &alice_key, &program_id)?;
+        let forged = |owner: Pubkey, wallet: &Pubkey| {
+            let mut accounts = FakeAccounts::default();
+            accounts.0.insert(address, ChainAccount { owner, data: depositor_account(wallet, 500, 0) });
+            ChainConfig::new(Some(program_id)).with_decoder(AccountDecoder::from_idl(TURTLE_IDL).unwrap()).with_accounts(accounts)
+        };
+        let result = forged(Pubkey::new_from_array([9; 32]), &alice_key).verified_depositor(&community, &alice);
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+        let result = forged(program_id, &parse_wallet(&bob)?).verified_depositor(&community, &alice);
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_idl() -> Result<(), Box<dyn std::error::Error>> {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../sol/fixtures/turtle_idl.json");
+        let config = ChainConfig::default().load_idl(path)?;
+        assert!(config.decoder().is_some_and(|decoder| decoder.account_names().count() == 4));
+
+        // 읽지 못한 이유에 환경 변수 이름이 포함됨
+        let error = ChainConfig::default().load_idl("/nonexistent/turtle_idl.json").unwrap_err();
+        assert!(matches!(error, ChainConfigError::InvalidIdl { error: AccountError::Io(_), .. }));
+        assert!(error.to_string().starts_with(IDL_PATH_ENV));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use axum::extract::State;
+use crate::extract::{Json, Query};
+use serde::{Deserialize, Serialize};
+use turtle_database::basic_db::SafeDatabase;
+use turtle_service::claim::{claims_for_wallet, ClaimError, ClaimLeaf};
+use crate::community::database_error;
+use crate::error::ApiError;
+
+#[derive(Deserialize)]
+pub struct ClaimQuery {
+    wallet: String,
+    pda: Option<String>,
+    round_id: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct ClaimsResponse {
+    wallet: String,
+    total_amount: u64,
+    claims: Vec<ClaimLeaf>,         // 라운드별 금액과 머클 증명
+}
+
+// 지갑이 온체인에서 청구할 수 있는 라운드별 금액과 증명
+pub async fn get_claims<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<ClaimQuery>,
+) -> Result<Json<ClaimsResponse>, ApiError> {
+    if query.wallet.is_empty() {
+        return Err(ApiError::missing_field("wallet"));
+    }
+
+    let claims: Vec<ClaimLeaf> = claims_for_wallet(&database, &query.wallet)
+        .map_err(|e| match e {
+            ClaimError::Storage(e) => e.into(),
+            e => database_error(e),
+        })?
+        .into_iter()
+        .filter(|claim| query.pda.as_ref().is_none_or(|pda| &claim.pda == pda))
+        .filter(|claim| query.round_id.is_none_or(|round_id| claim.round_id == round_id))
+        .collect();
+
+    Ok(Json(ClaimsResponse {
+        wallet: query.wallet,
+        total_amount: claims.iter().map(|claim| claim.amount).fold(0, u64::saturating_add),
+        claims,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use turtle_database::basic_db::InnerDatabase;
+    use turtle_service::claim::{decode_hash, leaf_hash, record_claims, verify_proof};
+    use turtle_service::settlement::{Payout, PayoutTable};
+    use turtle_service::test_util::wallet;
+
+    #[tokio::test]
+    async fn test_get_claims_returns_verifiable_proofs() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let (wallet, other) = (wallet(7), wallet(8));
+
+        for (round_id, lamports) in [(1, 10), (2, 25)] {
+            let table = PayoutTable {
+                total_deposit: lamports + 5,
+                reward_pool: 0,
+                author_payouts: Vec::new(),
+                depositor_payouts: vec![
+                    Payout { pubkey: wallet.clone(), lamports },
+                    Payout { pubkey: other.clone(), lamports: 5 },
+                ],
+                dust: 0,
+                skipped: Vec::new(),
+            };
+            db.transaction(|txn| record_claims(txn, "pda1", round_id, &table))??;
+        }
+
+        let query = ClaimQuery { wallet: wallet.clone(), pda: None, round_id: None };
+        let Json(response) = get_claims(State(Clone::clone(&db)), Query(query)).await?;
+        assert_eq!(response.total_amount, 35);
+
+        for claim in &response.claims {
+            let root = decode_hash(&claim.root).unwrap();
+            let proof: Vec<_> = claim.proof.iter().map(|hash| decode_hash(hash).unwrap()).collect();
+            assert!(verify_proof(leaf_hash(&wallet, claim.amount, claim.round_id)?, &proof, &root));
+        }
+
+        let query = ClaimQuery { wallet, pda: Some("pda1".to_string()), round_id: Some(2) };
+        let Json(response) = get_claims(State(db), Query(query)).await?;
+        assert_eq!(response.claims.len(), 1);
+        assert_eq!(response.claims[0].amount, 25);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+// 현재 유닉스 시간(밀리초). 요청 제한처럼 초보다 세밀한 간격이 필요한 곳에서 사용
+pub fn unix_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+// 현재 유닉스 시간(초). 핸들러와 스케줄러가 같은 시계를 사용
+pub fn unix_now() -> u64 {
+    unix_now_ms() / 1000
+}
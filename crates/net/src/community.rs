@@ -1,13 +1,37 @@
-use axum::extract::{Multipart, Query, State};
+use axum::extract::State;
+use axum::Extension;
 use axum::http::StatusCode;
-use axum::response::{IntoResponse, Response};
-use std::error::Error as StdError;
 use std::fmt;
-use axum::Json;
+use crate::extract::{Json, Query};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use turtle_database::basic_db::{SafeDatabase};
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error as storage;
 use turtle_service::parser::community::{Community, Content, Depositor, Proposal, Daopda};
-use std::collections::HashMap;
+use turtle_service::round::{round_status, RoundStatus};
+use turtle_service::deposit::{deposit_backed, deposit_history, withdraw, DepositError, DepositEvent, DepositPosition};
+use turtle_service::snapshot::{delete_snapshot, record_snapshot};
+use turtle_service::sequence::next_id;
+use turtle_service::delegation::effective_power_in;
+use turtle_service::lifecycle::{check_new_proposal, ProposalRejection};
+use turtle_service::preview::{preview_proposal as build_preview, ProposalPreview};
+use turtle_service::validation::Validate;
+use std::collections::{BTreeSet, HashMap};
+use crate::archive::{archive_record, remove_record, DeleteQuery};
+use crate::auth::WalletIdentity;
+use crate::error::{ApiError, FieldError};
+use crate::delegation::delegation_error;
+use crate::listing::{key_id, paginate, ListQuery};
+use crate::chain::ChainConfig;
+use crate::clock::unix_now;
+use sol::pubkey::{parse_wallet, validate_pubkey, Curve};
+
+// PDA 주소 형식 검사 (base58, 32바이트, 곡선 밖)
+fn check_pda_address(field: &'static str, address: &str) -> Result<(), ApiError> {
+    validate_pubkey(address, Curve::OffCurve)
+        .map(|_| ())
+        .map_err(|e| ApiError::invalid_field(field, e))
+}
 
 // 다양한 쿼리 파라미터를 위한 구조체들
 #[derive(Deserialize)]
@@ -30,96 +54,465 @@ pub struct ProposalCreateQuery {
     pda: String,
 }
 
+#[derive(Deserialize)]
+pub struct WithdrawQuery {
+    pda: String,
+    amount: u64,
+}
+
+#[derive(Deserialize)]
+pub struct DepositorHistoryQuery {
+    pda: String,
+    pubkey: String,
+}
+
+// 단일 하위 리소스 지정 (키는 pda_id)
+#[derive(Deserialize)]
+pub struct ChildQuery {
+    pda: String,
+    id: u64,
+}
+
 // 응답 구조체들
 #[derive(Serialize)]
 pub struct PdasResponse {
     pdas: Vec<String>,
 }
 
+// 목록/단건 응답의 하위 리소스. 저장 키(pda_n)의 n을 함께 반환
+#[derive(Serialize)]
+pub struct Keyed<R> {
+    key_id: u64,
+    #[serde(flatten)]
+    item: R,
+}
+
+impl<R> Keyed<R> {
+    fn new(key: &str, item: R) -> Self {
+        Self {
+            key_id: key_id(key),
+            item,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct CommunityEntry {
+    pda: String,
+    #[serde(flatten)]
+    community: Community,
+}
+
 #[derive(Serialize)]
 pub struct CommunitiesResponse {
-    communities: Vec<Community>,
+    communities: Vec<CommunityEntry>,
+    next_cursor: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct ContentsResponse {
-    contents: Vec<Content>,
+    contents: Vec<Keyed<Content>>,
+    next_cursor: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct DepositorsResponse {
-    depositors: Vec<Depositor>,
+    depositors: Vec<Keyed<Depositor>>,
+    next_cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct DepositHistoryResponse {
+    key: String,                // 포지션 키 (pda_n)
+    history: Vec<DepositEvent>,
 }
 
 #[derive(Serialize)]
 pub struct ProposalsResponse {
-    proposals: Vec<Proposal>,
+    proposals: Vec<Keyed<Proposal>>,
+    next_cursor: Option<String>,
 }
 
-// 에러 타입
-#[derive(Debug)]
-pub enum DaoError {
-    MultipartError(String),
-    DatabaseError(String),
-    SerializationError(String),
-    ValidationError(String),
+pub(crate) fn load_community<T: SafeDatabase>(database: &T, pda: &str) -> Result<Community, ApiError> {
+    let community_data = database.read(pda, "community")
+        .map_err(database_error)?
+        .ok_or_else(|| ApiError::NotFoundError(format!("Community with PDA {} not found", pda)))?;
+
+    decode(community_data)
 }
 
-impl fmt::Display for DaoError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            DaoError::MultipartError(msg) => write!(f, "Multipart error: {}", msg),
-            DaoError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
-            DaoError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
-            DaoError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
+// PDA 하위 리소스(content, depositor, proposal)의 수정/삭제 공통 동작
+pub trait ChildResource: Validate + Serialize + DeserializeOwned + Send + 'static {
+    const TABLE: &'static str;
+
+    // 이 리소스를 세는 커뮤니티 카운터
+    fn counter(community: &mut Community) -> &mut u64;
+
+    // 카운터에 포함되는 상태인지
+    fn is_counted(&self) -> bool {
+        true
+    }
+
+    // 서버가 관리하는 읽기 전용 필드 (PATCH 요청에 포함되면 422)
+    const MANAGED_FIELDS: &'static [&'static str] = &[];
+
+    // 수정 요청으로 바뀌면 안 되는 서버 관리 필드를 기존 값으로 유지
+    fn keep_managed_fields(&mut self, _existing: &Self) {}
+
+    // 지금 삭제할 수 있는 상태인지
+    fn check_removable(&self, _key: &str) -> Result<(), ApiError> {
+        Ok(())
+    }
+
+    // 삭제 시 카운터 외에 함께 조정할 커뮤니티 집계
+    fn release(&self, _community: &mut Community) {}
+
+    // 삭제 시 함께 지울 부속 행
+    fn remove_related(_txn: &DatabaseTransaction<'_, '_>, _key: &str) -> Result<(), ApiError> {
+        Ok(())
+    }
+}
+
+impl ChildResource for Content {
+    const TABLE: &'static str = "content";
+
+    fn counter(community: &mut Community) -> &mut u64 {
+        &mut community.content_count
+    }
+
+    const MANAGED_FIELDS: &'static [&'static str] = &["votes"];
+
+    // 득표 수는 투표 API로만 변경
+    fn keep_managed_fields(&mut self, existing: &Self) {
+        self.votes = existing.votes;
+    }
+}
+
+impl ChildResource for Depositor {
+    const TABLE: &'static str = "depositor";
+
+    fn counter(community: &mut Community) -> &mut u64 {
+        &mut community.depositor_count
+    }
+
+    const MANAGED_FIELDS: &'static [&'static str] = &["pubkey", "amount", "locked_until", "voting_power", "unlocked_at"];
+
+    // 예치 금액, 잠금 기간, 투표 파워는 예치/인출로만, 잠금 해제 시점은 스케줄러로만 변경
+    fn keep_managed_fields(&mut self, existing: &Self) {
+        self.pubkey = existing.pubkey.clone();
+        self.amount = existing.amount;
+        self.locked_until = existing.locked_until;
+        self.voting_power = existing.voting_power;
+        self.unlocked_at = existing.unlocked_at;
+    }
+
+    fn release(&self, community: &mut Community) {
+        community.total_deposit = community.total_deposit.saturating_sub(self.amount);
+    }
+}
+
+impl ChildResource for Proposal {
+    const TABLE: &'static str = "proposal";
+
+    fn counter(community: &mut Community) -> &mut u64 {
+        &mut community.active_proposal_count
+    }
+
+    // 실행되었거나 마감된 제안은 활성 제안 수에서 제외
+    fn is_counted(&self) -> bool {
+        !self.is_executed && self.outcome.is_none()
+    }
+
+    // 예전 형식의 proposal_type/new_value도 유형을 바꾸므로 포함
+    const MANAGED_FIELDS: &'static [&'static str] = &[
+        "id", "kind", "proposal_type", "new_value", "voting_end_time", "yes_votes", "no_votes", "is_executed", "outcome", "rules",
+    ];
+
+    // ID는 키와 같게, 찬반 집계, 마감 결과, 실행 여부는 서명 투표와 lifecycle 엔진으로만 변경.
+    // 투표가 서명한 내용과 기간이 바뀌지 않도록 유형과 투표 종료 시간도 고정
+    fn keep_managed_fields(&mut self, existing: &Self) {
+        self.id = existing.id;
+        self.kind = existing.kind.clone();
+        self.voting_end_time = existing.voting_end_time;
+        self.yes_votes = existing.yes_votes;
+        self.no_votes = existing.no_votes;
+        self.is_executed = existing.is_executed;
+        self.outcome = existing.outcome;
+        self.rules = existing.rules;
+    }
+
+    // 투표 중인 제안(관리자 교체, 지출 등)을 관리자가 지우지 못하도록 마감된 제안만 삭제
+    fn check_removable(&self, key: &str) -> Result<(), ApiError> {
+        if self.outcome.is_none() && !self.is_executed {
+            return Err(ApiError::ConflictError(format!("Proposal {} is still open and can only be deleted after it closes", key)));
         }
+        Ok(())
+    }
+
+    // 투표 파워 스냅샷은 제안과 함께 삭제 (원본 제안은 삭제 기록에 남음)
+    fn remove_related(txn: &DatabaseTransaction<'_, '_>, key: &str) -> Result<(), ApiError> {
+        delete_snapshot(txn, key)?;
+        Ok(())
     }
 }
 
-impl StdError for DaoError {}
+// 저장된 하위 리소스의 원본 JSON
+fn read_child<T: SafeDatabase, R: ChildResource>(database: &T, key: &str) -> Result<String, ApiError> {
+    let data = database.read(key, R::TABLE)
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFoundError(format!("{} {} not found", R::TABLE, key)))?;
 
-impl IntoResponse for DaoError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            DaoError::MultipartError(msg) => (StatusCode::BAD_REQUEST, msg),
-            DaoError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            DaoError::SerializationError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            DaoError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
-        };
+    String::from_utf8(data)
+        .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8: {}", e)))
+}
 
-        (status, error_message).into_response()
+pub(crate) fn database_error(e: impl fmt::Display) -> ApiError {
+    ApiError::DatabaseError(e.to_string())
+}
+
+// 저장된 JSON 값을 역직렬화
+pub(crate) fn decode<V: DeserializeOwned>(data: Vec<u8>) -> Result<V, ApiError> {
+    Ok(storage::decode(&data)?)
+}
+
+pub(crate) fn encode<V: Serialize>(value: &V) -> Result<String, ApiError> {
+    Ok(storage::encode(value)?)
+}
+
+// 기존 하위 리소스를 교체하고 카운터에 포함되는 상태가 바뀌면 커뮤니티 카운터 조정
+fn replace_child<T: SafeDatabase, R: ChildResource>(database: &T, query: &ChildQuery, mut updated: R) -> Result<(), ApiError> {
+    let key = format!("{}_{}", query.pda, query.id);
+    let now = unix_now();
+
+    database.transaction(|txn| {
+        let mut community: Community = decode(txn.read(&query.pda, "community").map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("Community with PDA {} not found", query.pda)))?)?;
+
+        let existing: R = decode(txn.read(&key, R::TABLE).map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("{} {} not found", R::TABLE, key)))?)?;
+        updated.keep_managed_fields(&existing);
+        updated.validate_fields(now)?;
+
+        txn.write(&key, &encode(&updated)?, R::TABLE).map_err(database_error)?;
+
+        match (existing.is_counted(), updated.is_counted()) {
+            (true, false) => {
+                let counter = R::counter(&mut community);
+                *counter = counter.saturating_sub(1);
+            },
+            (false, true) => *R::counter(&mut community) += 1,
+            _ => return Ok(()),
+        }
+
+        txn.write(&query.pda, &encode(&community)?, "community").map_err(database_error)
+    }).map_err(database_error)?
+}
+
+// GET - 하위 리소스 단건 조회
+pub async fn get_child<T: SafeDatabase, R: ChildResource>(
+    State(database): State<T>,
+    Query(query): Query<ChildQuery>,
+) -> Result<Json<Keyed<R>>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let key = format!("{}_{}", query.pda, query.id);
+    let item: R = serde_json::from_str(&read_child::<T, R>(&database, &key)?)
+        .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
+
+    Ok(Json(Keyed::new(&key, item)))
+}
+
+// PUT - 하위 리소스 전체 교체
+pub async fn update_child<T: SafeDatabase, R: ChildResource>(
+    State(database): State<T>,
+    Query(query): Query<ChildQuery>,
+    Json(updated): Json<R>,
+) -> Result<StatusCode, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    replace_child(&database, &query, updated)?;
+
+    Ok(StatusCode::OK)
+}
+
+// PATCH - 요청에 포함된 필드만 수정
+pub async fn patch_child<T: SafeDatabase, R: ChildResource>(
+    State(database): State<T>,
+    Query(query): Query<ChildQuery>,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<StatusCode, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let serde_json::Value::Object(fields) = patch else {
+        return Err(ApiError::MalformedRequest("Patch body must be a JSON object".to_string()));
+    };
+
+    // 서버 관리 필드는 조용히 무시하지 않고 어떤 필드가 읽기 전용인지 알려줌
+    let read_only: Vec<FieldError> = R::MANAGED_FIELDS.iter()
+        .filter(|field| fields.contains_key(**field))
+        .map(|field| FieldError::new(*field, "is read-only"))
+        .collect();
+    if !read_only.is_empty() {
+        return Err(ApiError::InvalidFields(read_only));
+    }
+
+    let key = format!("{}_{}", query.pda, query.id);
+    let mut merged: serde_json::Value = serde_json::from_str(&read_child::<T, R>(&database, &key)?)
+        .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
+
+    if let serde_json::Value::Object(existing) = &mut merged {
+        existing.extend(fields);
+    }
+
+    let updated: R = serde_json::from_value(merged)
+        .map_err(|e| ApiError::ValidationError(format!("Invalid patch: {}", e)))?;
+
+    replace_child(&database, &query, updated)?;
+
+    Ok(StatusCode::OK)
+}
+
+// DELETE - 기본은 소프트 삭제(deleted_records에 보관), hard=true면 영구 삭제
+pub async fn delete_child<T: SafeDatabase, R: ChildResource>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<ChildQuery>,
+    Query(options): Query<DeleteQuery>,
+) -> Result<StatusCode, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
     }
+
+    let key = format!("{}_{}", query.pda, query.id);
+
+    database.transaction(|txn| {
+        let mut community: Community = decode(txn.read(&query.pda, "community").map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("Community with PDA {} not found", query.pda)))?)?;
+
+        let existing_data = txn.read(&key, R::TABLE).map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("{} {} not found", R::TABLE, key)))?;
+        let existing_json = String::from_utf8(existing_data)
+            .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+        let existing: R = serde_json::from_str(&existing_json)
+            .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
+        existing.check_removable(&key)?;
+
+        archive_record(txn, R::TABLE, &key, &existing_json, Some(&caller.pubkey), options.hard)
+            .map_err(database_error)?;
+
+        if existing.is_counted() {
+            let counter = R::counter(&mut community);
+            *counter = counter.saturating_sub(1);
+        }
+        existing.release(&mut community);
+        R::remove_related(txn, &key)?;
+        txn.write(&query.pda, &encode(&community)?, "community").map_err(database_error)?;
+
+        Ok(())
+    }).map_err(database_error)??;
+
+    Ok(StatusCode::NO_CONTENT)
 }
 
 // DAOPDA 테이블 관련 함수들
 pub async fn save_pda<T: SafeDatabase>(
     State(database): State<T>,
-    Json(daopda): Json<Daopda>,
-) -> Result<StatusCode, DaoError> {
+    Extension(chain): Extension<ChainConfig>,
+    caller: WalletIdentity,
+    Json(mut daopda): Json<Daopda>,
+) -> Result<StatusCode, ApiError> {
     // PDA 유효성 검사
     if daopda.address.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
+    }
+    check_pda_address("address", &daopda.address)?;
+
+    // 관리자는 서명한 지갑으로 고정 (생략하면 서명한 지갑)
+    let admin = daopda.admin.take()
+        .filter(|admin| !admin.is_empty())
+        .unwrap_or_else(|| caller.pubkey.clone());
+    if admin != caller.pubkey {
+        return Err(ApiError::ForbiddenError(format!("PDA admin must be the signing wallet {}", caller.pubkey)));
     }
+    parse_wallet(&admin).map_err(|e| ApiError::invalid_field("admin", e))?;
+    chain.check_community_derivation("address", &daopda.address, &admin)?;
+    daopda.admin = Some(admin);
+
+    // 데이터베이스에 저장 - key는 PDA, value는 PDA와 유도에 쓰인 관리자. 다른 지갑의 등록은 덮어쓰지 않음
+    database.transaction(|txn| {
+        let registered = txn.read(&daopda.address, "daopda").map_err(database_error)?;
+        let community = txn.read(&daopda.address, "community").map_err(database_error)?
+            .map(decode::<Community>)
+            .transpose()?;
+        let owner = match registered.and_then(pda_admin) {
+            Some(owner) => Some(owner),
+            // 관리자 없이 등록된 예전 PDA는 커뮤니티 관리자만 다시 등록
+            None => community.map(|community| community.admin),
+        };
+        if owner.is_some_and(|owner| owner != caller.pubkey) {
+            return Err(ApiError::ConflictError(format!("PDA {} is registered to another wallet", daopda.address)));
+        }
 
-    // 데이터베이스에 저장 - key와 value 모두 PDA
-    database.write(&daopda.address, &daopda.address, "daopda")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        txn.write(&daopda.address, &encode(&daopda)?, "daopda").map_err(database_error)?;
+        Ok(())
+    }).map_err(database_error)??;
 
     Ok(StatusCode::OK)
 }
 
+// 등록된 PDA의 관리자. 주소만 저장된 예전 행이면 None
+pub(crate) fn pda_admin(data: Vec<u8>) -> Option<String> {
+    decode::<Daopda>(data).ok().and_then(|daopda| daopda.admin)
+}
+
+// 커뮤니티가 남아 있는 PDA는 삭제할 수 없음
+pub async fn delete_pda<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<PdaQuery>,
+    Query(options): Query<DeleteQuery>,
+) -> Result<StatusCode, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let community_data = database.read(&query.pda, "community")
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+    if community_data.is_some() {
+        return Err(ApiError::ConflictError(format!("Community for PDA {} still exists", query.pda)));
+    }
+
+    let pda_data = database.read(&query.pda, "daopda")
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| ApiError::NotFoundError(format!("PDA {} not found", query.pda)))?;
+
+    let pda_str = String::from_utf8(pda_data)
+        .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+
+    remove_record(&database, "daopda", &query.pda, &pda_str, Some(&caller.pubkey), options.hard)
+        .map_err(ApiError::DatabaseError)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 pub async fn get_all_pdas<T: SafeDatabase>(
     State(database): State<T>,
-) -> Result<Json<PdasResponse>, DaoError> {
+) -> Result<Json<PdasResponse>, ApiError> {
     // 데이터베이스에서 모든 PDA 읽기
     let pda_entries: HashMap<Vec<u8>, Vec<u8>> = database.read_all("daopda")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     let mut pdas = Vec::new();
     for (key_bytes, _) in pda_entries {
         // key_bytes를 문자열로 변환
         let key_str = String::from_utf8(key_bytes)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid UTF-8 in key: {}", e)))?;
+            .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8 in key: {}", e)))?;
         pdas.push(key_str);
     }
 
@@ -129,140 +522,192 @@ pub async fn get_all_pdas<T: SafeDatabase>(
 // COMMUNITY 테이블 관련 함수들
 pub async fn save_community<T: SafeDatabase>(
     State(database): State<T>,
+    Extension(chain): Extension<ChainConfig>,
     Query(query): Query<PdaQuery>,
-    Json(community): Json<Community>,
-) -> Result<StatusCode, DaoError> {
+    Json(mut community): Json<Community>,
+) -> Result<StatusCode, ApiError> {
     // PDA 유효성 검사
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
-    // JSON 직렬화
-    let community_json = serde_json::to_string(&community)
-        .map_err(|e| DaoError::SerializationError(e.to_string()))?;
+    // 필드 규칙 검사 (관리자 공개키, 값 범위, 거버넌스 규칙)
+    let now = unix_now();
+    community.validate_fields(now)?;
+    parse_wallet(&community.admin).map_err(|e| ApiError::invalid_field("admin", e))?;
+
+    // 기존 행 조회와 저장을 한 트랜잭션으로 처리해 그 사이의 예치/콘텐츠/제안 집계를 덮어쓰지 않음
+    database.transaction(|txn| {
+        let existing = txn.read(&query.pda, "community").map_err(database_error)?
+            .map(decode::<Community>)
+            .transpose()?;
+
+        match existing {
+            Some(existing) => {
+                keep_managed_totals(&mut community, &existing);
+                // 거버넌스 규칙은 생성 시에만 지정하고 이후에는 거버넌스 제안으로만 변경
+                community.governance = existing.governance;
+            },
+            None => {
+                // 새 커뮤니티는 PDA 형식과 관리자 지갑에서 유도된 주소인지 확인 (기존 키는 그대로 수정 허용)
+                check_pda_address("pda", &query.pda)?;
+                chain.check_community_derivation("pda", &query.pda, &community.admin)?;
+                keep_managed_totals(&mut community, &Community { last_activity_timestamp: now, ..Community::default() });
+            },
+        }
 
-    // 데이터베이스에 저장 - key는 PDA, value는 Community
-    database.write(&query.pda, &community_json, "community")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        // 데이터베이스에 저장 - key는 PDA, value는 Community
+        txn.write(&query.pda, &encode(&community)?, "community").map_err(database_error)
+    }).map_err(database_error)??;
 
     Ok(StatusCode::OK)
 }
 
+// 예치/콘텐츠/제안 핸들러가 관리하는 집계는 요청 값 대신 저장된 값(새 커뮤니티는 0)을 유지
+fn keep_managed_totals(community: &mut Community, existing: &Community) {
+    community.total_deposit = existing.total_deposit;
+    community.depositor_count = existing.depositor_count;
+    community.content_count = existing.content_count;
+    community.active_proposal_count = existing.active_proposal_count;
+    community.last_activity_timestamp = existing.last_activity_timestamp;
+}
+
 pub async fn get_all_communities<T: SafeDatabase>(
     State(database): State<T>,
-) -> Result<Json<CommunitiesResponse>, DaoError> {
+    Query(list): Query<ListQuery>,
+) -> Result<Json<CommunitiesResponse>, ApiError> {
     // 데이터베이스에서 모든 커뮤니티 읽기
     let community_entries: HashMap<Vec<u8>, Vec<u8>> = database.read_all("community")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     let mut communities = Vec::new();
-    for (_, value_bytes) in community_entries {
+    for (key_bytes, value_bytes) in community_entries {
+        let key_str = String::from_utf8(key_bytes)
+            .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8 in key: {}", e)))?;
+
         let community_data = String::from_utf8(value_bytes)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+            .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
 
         let community: Community = serde_json::from_str(&community_data)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+            .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
 
-        communities.push(community);
+        communities.push((key_str, community));
     }
 
-    Ok(Json(CommunitiesResponse { communities }))
+    // 필터, 정렬 및 pagination 적용
+    let page = paginate(communities, &list)?;
+
+    Ok(Json(CommunitiesResponse {
+        communities: page.items.into_iter().map(|(pda, community)| CommunityEntry { pda, community }).collect(),
+        next_cursor: page.next_cursor,
+    }))
 }
 
 pub async fn get_community_by_pda<T: SafeDatabase>(
     State(database): State<T>,
     Query(query): Query<PdaQuery>,
-) -> Result<Json<Community>, DaoError> {
+) -> Result<Json<Community>, ApiError> {
     // PDA 유효성 검사
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
     // 데이터베이스에서 커뮤니티 읽기
     let community_data = database.read(&query.pda, "community")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     if let Some(data) = community_data {
         let community_str = String::from_utf8(data)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+            .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
 
         let community: Community = serde_json::from_str(&community_str)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+            .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
 
         Ok(Json(community))
     } else {
-        Err(DaoError::ValidationError(format!("Community with PDA {} not found", query.pda)))
+        Err(ApiError::NotFoundError(format!("Community with PDA {} not found", query.pda)))
     }
 }
 
-// CONTENT 테이블 관련 함수들
-pub async fn save_content<T: SafeDatabase>(
+pub(crate) fn deposit_error(e: DepositError) -> ApiError {
+    match e {
+        DepositError::Storage(e) => e.into(),
+        DepositError::Locked(_) => ApiError::ConflictError(e.to_string()),
+        DepositError::Validation(_) | DepositError::InsufficientBalance { .. } | DepositError::Unbacked { .. } => ApiError::ValidationError(e.to_string()),
+    }
+}
+
+// 진행 중인 라운드의 남은 시간과 현재 1위 콘텐츠
+pub async fn get_community_status<T: SafeDatabase>(
     State(database): State<T>,
-    Query(query): Query<ContentCreateQuery>,
-    Json(content): Json<Content>,
-) -> Result<StatusCode, DaoError> {
-    // PDA 유효성 검사
+    Query(query): Query<PdaQuery>,
+) -> Result<Json<RoundStatus>, ApiError> {
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
-    // 커뮤니티 조회하여 content_count 및 last_activity_timestamp 업데이트
-    let community_data = database.read(&query.pda, "community")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+    let now = unix_now();
 
-    if let Some(data) = community_data {
-        let community_str = String::from_utf8(data.clone())
-            .map_err(|e| DaoError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+    let status = round_status(&database, &query.pda, now)?;
 
-        let mut community: Community = serde_json::from_str(&community_str)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+    Ok(Json(status))
+}
 
-        // content_count 증가
-        community.content_count += 1;
+// CONTENT 테이블 관련 함수들
+pub async fn save_content<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<ContentCreateQuery>,
+    Json(mut content): Json<Content>,
+) -> Result<StatusCode, ApiError> {
+    // 득표 수는 투표 API로만 증가
+    content.votes = 0;
 
-        // last_activity_timestamp 업데이트
-        community.last_activity_timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+    // PDA 유효성 검사
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
 
-        // 콘텐츠 키 생성 (pda_n 형식)
-        let content_key = format!("{}_{}", query.pda, community.content_count);
+    // 필드 규칙 검사 (작성자 공개키, URI 스킴, 길이, 타임스탬프)
+    let now = unix_now();
+    content.validate_fields(now)?;
 
-        // 콘텐츠 JSON 직렬화
-        let content_json = serde_json::to_string(&content)
-            .map_err(|e| DaoError::SerializationError(e.to_string()))?;
+    // 작성자는 서명한 지갑 본인만
+    if content.author != caller.pubkey {
+        return Err(ApiError::ForbiddenError(format!("Content author must be the signing wallet {}", caller.pubkey)));
+    }
 
-        // 업데이트된 커뮤니티 JSON 직렬화
-        let updated_community_json = serde_json::to_string(&community)
-            .map_err(|e| DaoError::SerializationError(e.to_string()))?;
+    // 커뮤니티 조회, 콘텐츠 키 발급(pda_n 형식, 삭제된 ID는 재사용하지 않음)과 저장을 한 트랜잭션으로 처리해
+    // 그 사이의 예치/인출/정산 집계를 덮어쓰지 않음
+    database.transaction(|txn| {
+        let mut community: Community = decode(txn.read(&query.pda, "community").map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("Community with PDA {} not found", query.pda)))?)?;
 
-        // 데이터베이스에 콘텐츠 저장
-        database.write(&content_key, &content_json, "content")
-            .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        community.content_count += 1;
+        community.last_activity_timestamp = now;
 
-        // 데이터베이스에 업데이트된 커뮤니티 정보 저장
-        database.write(&query.pda, &updated_community_json, "community")
-            .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        let content_key = format!("{}_{}", query.pda, next_id(txn, "content", &query.pda)?);
+        txn.write(&content_key, &encode(&content)?, "content").map_err(database_error)?;
+        txn.write(&query.pda, &encode(&community)?, "community").map_err(database_error)?;
+        Ok::<_, ApiError>(())
+    }).map_err(database_error)??;
 
-        Ok(StatusCode::OK)
-    } else {
-        Err(DaoError::ValidationError(format!("Community with PDA {} not found", query.pda)))
-    }
+    Ok(StatusCode::OK)
 }
 
 pub async fn get_contents_by_pda<T: SafeDatabase>(
     State(database): State<T>,
     Query(query): Query<PdaQuery>,
-) -> Result<Json<ContentsResponse>, DaoError> {
+    Query(list): Query<ListQuery>,
+) -> Result<Json<ContentsResponse>, ApiError> {
     // PDA 유효성 검사
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
     // 데이터베이스에서 모든 콘텐츠 읽기
     let content_entries: HashMap<Vec<u8>, Vec<u8>> = database.read_all("content")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     // PDA에 해당하는 콘텐츠만 필터링
     let prefix = format!("{}_", query.pda);
@@ -278,87 +723,104 @@ pub async fn get_contents_by_pda<T: SafeDatabase>(
         if key_str.starts_with(&prefix) {
             let content_str = match String::from_utf8(value_bytes) {
                 Ok(s) => s,
-                Err(e) => return Err(DaoError::SerializationError(format!("Invalid UTF-8: {}", e))),
+                Err(e) => return Err(ApiError::SerializationError(format!("Invalid UTF-8: {}", e))),
             };
 
             let content: Content = serde_json::from_str(&content_str)
-                .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+                .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
 
-            contents.push(content);
+            contents.push((key_str, content));
         }
     }
 
-    Ok(Json(ContentsResponse { contents }))
+    // 필터, 정렬 및 pagination 적용
+    let page = paginate(contents, &list)?;
+
+    Ok(Json(ContentsResponse {
+        contents: page.items.into_iter().map(|(key, content)| Keyed::new(&key, content)).collect(),
+        next_cursor: page.next_cursor,
+    }))
 }
 
 // DEPOSIT 테이블 관련 함수들
+// 같은 지갑의 예치는 하나의 포지션(pda, pubkey)으로 합산. 온체인 예치 계정 잔액까지만 인정
 pub async fn save_depositor<T: SafeDatabase>(
     State(database): State<T>,
+    Extension(chain): Extension<ChainConfig>,
+    caller: WalletIdentity,
     Query(query): Query<DepositorCreateQuery>,
     Json(depositor): Json<Depositor>,
-) -> Result<StatusCode, DaoError> {
+) -> Result<StatusCode, ApiError> {
     // PDA 유효성 검사
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
-    // 커뮤니티 조회하여 depositor_count 및 last_activity_timestamp 업데이트
-    let community_data = database.read(&query.pda, "community")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+    let now = unix_now();
+    depositor.validate_fields(now)?;
 
-    if let Some(data) = community_data {
-        let community_str = String::from_utf8(data.clone())
-            .map_err(|e| DaoError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+    // 예치자는 서명한 지갑 본인만
+    if depositor.pubkey != caller.pubkey {
+        return Err(ApiError::ForbiddenError(format!("Depositor must be the signing wallet {}", caller.pubkey)));
+    }
 
-        let mut community: Community = serde_json::from_str(&community_str)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+    // RPC 조회는 블로킹이므로 별도 스레드에서 실행
+    let pda = query.pda.clone();
+    let on_chain = tokio::task::spawn_blocking(move || chain.verified_depositor(&pda, &caller.pubkey))
+        .await
+        .map_err(|e| ApiError::UnavailableError(e.to_string()))??;
 
-        // depositor_count 증가
-        community.depositor_count += 1;
+    let locked_until = depositor.locked_until.max(on_chain.locked_until);
+    deposit_backed(&database, &query.pda, &depositor.pubkey, depositor.amount, locked_until, on_chain.amount, now)
+        .map_err(deposit_error)?;
 
-        // last_activity_timestamp 업데이트
-        community.last_activity_timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+    Ok(StatusCode::OK)
+}
 
-        // depositor 키 생성 (pda_n 형식)
-        let depositor_key = format!("{}_{}", query.pda, community.depositor_count);
+// 잠금 기간이 지난 본인 포지션에서 인출
+pub async fn withdraw_depositor<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<WithdrawQuery>,
+) -> Result<Json<DepositPosition>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
 
-        // depositor JSON 직렬화
-        let depositor_json = serde_json::to_string(&depositor)
-            .map_err(|e| DaoError::SerializationError(e.to_string()))?;
+    let now = unix_now();
 
-        // 업데이트된 커뮤니티 JSON 직렬화
-        let updated_community_json = serde_json::to_string(&community)
-            .map_err(|e| DaoError::SerializationError(e.to_string()))?;
+    let position = withdraw(&database, &query.pda, &caller.pubkey, query.amount, now)
+        .map_err(deposit_error)?;
 
-        // 데이터베이스에 depositor 저장
-        database.write(&depositor_key, &depositor_json, "depositor")
-            .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+    Ok(Json(position))
+}
 
-        // 데이터베이스에 업데이트된 커뮤니티 정보 저장
-        database.write(&query.pda, &updated_community_json, "community")
-            .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+// 포지션의 예치/인출 기록
+pub async fn get_depositor_history<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<DepositorHistoryQuery>,
+) -> Result<Json<DepositHistoryResponse>, ApiError> {
+    ApiError::require(&[("pda", query.pda.as_str()), ("pubkey", query.pubkey.as_str())])?;
 
-        Ok(StatusCode::OK)
-    } else {
-        Err(DaoError::ValidationError(format!("Community with PDA {} not found", query.pda)))
-    }
+    let (key, history) = deposit_history(&database, &query.pda, &query.pubkey)
+        .map_err(deposit_error)?;
+
+    Ok(Json(DepositHistoryResponse { key, history }))
 }
 
 pub async fn get_depositors_by_pda<T: SafeDatabase>(
     State(database): State<T>,
     Query(query): Query<PdaQuery>,
-) -> Result<Json<DepositorsResponse>, DaoError> {
+    Query(list): Query<ListQuery>,
+) -> Result<Json<DepositorsResponse>, ApiError> {
     // PDA 유효성 검사
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
     // 데이터베이스에서 모든 depositor 읽기
     let depositor_entries: HashMap<Vec<u8>, Vec<u8>> = database.read_all("depositor")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     // PDA에 해당하는 depositor만 필터링
     let prefix = format!("{}_", query.pda);
@@ -374,87 +836,117 @@ pub async fn get_depositors_by_pda<T: SafeDatabase>(
         if key_str.starts_with(&prefix) {
             let depositor_str = match String::from_utf8(value_bytes) {
                 Ok(s) => s,
-                Err(e) => return Err(DaoError::SerializationError(format!("Invalid UTF-8: {}", e))),
+                Err(e) => return Err(ApiError::SerializationError(format!("Invalid UTF-8: {}", e))),
             };
 
             let depositor: Depositor = serde_json::from_str(&depositor_str)
-                .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+                .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
 
-            depositors.push(depositor);
+            depositors.push((key_str, depositor));
         }
     }
 
-    Ok(Json(DepositorsResponse { depositors }))
+    // 필터, 정렬 및 pagination 적용
+    let page = paginate(depositors, &list)?;
+
+    Ok(Json(DepositorsResponse {
+        depositors: page.items.into_iter().map(|(key, depositor)| Keyed::new(&key, depositor)).collect(),
+        next_cursor: page.next_cursor,
+    }))
 }
 
 // PROPOSAL 테이블 관련 함수들
 pub async fn save_proposal<T: SafeDatabase>(
     State(database): State<T>,
+    caller: WalletIdentity,
     Query(query): Query<ProposalCreateQuery>,
-    Json(proposal): Json<Proposal>,
-) -> Result<StatusCode, DaoError> {
+    Json(mut proposal): Json<Proposal>,
+) -> Result<StatusCode, ApiError> {
+    // 찬반 집계는 서명 투표로만 증가
+    proposal.yes_votes = 0;
+    proposal.no_votes = 0;
+    proposal.outcome = None;
+
     // PDA 유효성 검사
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
-    // 커뮤니티 조회하여 active_proposal_count 및 last_activity_timestamp 업데이트
-    let community_data = database.read(&query.pda, "community")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+    // 필드 규칙 검사 (제안 유형별 값 범위, 투표 종료 시간)
+    let now = unix_now();
+    proposal.validate_fields(now)?;
+
+    // 커뮤니티 조회, 거버넌스 규칙 확인, 키 발급(pda_n 형식, 삭제된 ID는 재사용하지 않음), 커뮤니티 집계와
+    // 생성 시점의 투표 파워 스냅샷 저장을 한 트랜잭션으로 처리해 동시 요청이 제한을 넘거나 집계를 덮어쓰지 않음
+    database.transaction(|txn| {
+        let mut community: Community = decode(txn.read(&query.pda, "community").map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("Community with PDA {} not found", query.pda)))?)?;
+
+        // 제안은 이 커뮤니티에서 투표 파워(본인 예치 또는 위임)가 있는 지갑만 올릴 수 있음
+        let power = effective_power_in(txn, &query.pda, &caller.pubkey, None, now, &BTreeSet::new())
+            .map_err(delegation_error)?;
+        if power.total() == 0 {
+            return Err(ApiError::ForbiddenError(format!(
+                "Wallet {} has no deposit or delegated voting power in community {}", caller.pubkey, query.pda
+            )));
+        }
 
-    if let Some(data) = community_data {
-        let community_str = String::from_utf8(data.clone())
-            .map_err(|e| DaoError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+        // 커뮤니티 거버넌스 규칙(최소 투표 기간, 동시 활성 제안 수) 확인
+        check_new_proposal(&community, &proposal, now).map_err(|e| match e {
+            ProposalRejection::VotingPeriodTooShort { .. } => ApiError::invalid_field("voting_end_time", e),
+            ProposalRejection::TooManyActiveProposals { .. } => ApiError::ConflictError(e.to_string()),
+        })?;
 
-        let mut community: Community = serde_json::from_str(&community_str)
-            .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+        // 마감 시 적용할 규칙은 생성 시점의 커뮤니티 규칙으로 고정
+        proposal.rules = Some(community.governance);
 
-        // active_proposal_count 증가
         community.active_proposal_count += 1;
+        community.last_activity_timestamp = now;
 
-        // last_activity_timestamp 업데이트
-        community.last_activity_timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        // 요청의 id 대신 발급된 n을 기록
+        proposal.id = next_id(txn, "proposal", &query.pda)?;
+        let proposal_key = format!("{}_{}", query.pda, proposal.id);
+        txn.write(&proposal_key, &encode(&proposal)?, "proposal").map_err(database_error)?;
+        txn.write(&query.pda, &encode(&community)?, "community").map_err(database_error)?;
+        record_snapshot(txn, &query.pda, &proposal_key, now)?;
+        Ok::<_, ApiError>(())
+    }).map_err(database_error)??;
 
-        // proposal 키 생성 (pda_n 형식)
-        let proposal_key = format!("{}_{}", query.pda, community.active_proposal_count);
+    Ok(StatusCode::OK)
+}
 
-        // proposal JSON 직렬화
-        let proposal_json = serde_json::to_string(&proposal)
-            .map_err(|e| DaoError::SerializationError(e.to_string()))?;
+// 제안이 가결되면 커뮤니티가 어떻게 바뀌는지 미리보기 (저장하지 않음)
+pub async fn preview_proposal<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<ProposalCreateQuery>,
+    Json(proposal): Json<Proposal>,
+) -> Result<Json<ProposalPreview>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
 
-        // 업데이트된 커뮤니티 JSON 직렬화
-        let updated_community_json = serde_json::to_string(&community)
-            .map_err(|e| DaoError::SerializationError(e.to_string()))?;
+    proposal.kind.validate().map_err(|e| ApiError::invalid_field("kind", e))?;
 
-        // 데이터베이스에 proposal 저장
-        database.write(&proposal_key, &proposal_json, "proposal")
-            .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+    let now = unix_now();
 
-        // 데이터베이스에 업데이트된 커뮤니티 정보 저장
-        database.write(&query.pda, &updated_community_json, "community")
-            .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+    let preview = build_preview(&database, &query.pda, &proposal, now)?;
 
-        Ok(StatusCode::OK)
-    } else {
-        Err(DaoError::ValidationError(format!("Community with PDA {} not found", query.pda)))
-    }
+    Ok(Json(preview))
 }
 
 pub async fn get_proposals_by_pda<T: SafeDatabase>(
     State(database): State<T>,
     Query(query): Query<PdaQuery>,
-) -> Result<Json<ProposalsResponse>, DaoError> {
+    Query(list): Query<ListQuery>,
+) -> Result<Json<ProposalsResponse>, ApiError> {
     // PDA 유효성 검사
     if query.pda.is_empty() {
-        return Err(DaoError::ValidationError("PDA cannot be empty".to_string()));
+        return Err(ApiError::missing_field("pda"));
     }
 
     // 데이터베이스에서 모든 proposal 읽기
     let proposal_entries: HashMap<Vec<u8>, Vec<u8>> = database.read_all("proposal")
-        .map_err(|e| DaoError::DatabaseError(e.to_string()))?;
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     // PDA에 해당하는 proposal만 필터링
     let prefix = format!("{}_", query.pda);
@@ -470,16 +962,516 @@ pub async fn get_proposals_by_pda<T: SafeDatabase>(
         if key_str.starts_with(&prefix) {
             let proposal_str = match String::from_utf8(value_bytes) {
                 Ok(s) => s,
-                Err(e) => return Err(DaoError::SerializationError(format!("Invalid UTF-8: {}", e))),
+                Err(e) => return Err(ApiError::SerializationError(format!("Invalid UTF-8: {}", e))),
             };
 
             let proposal: Proposal = serde_json::from_str(&proposal_str)
-                .map_err(|e| DaoError::SerializationError(format!("Invalid JSON: {}", e)))?;
+                .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
 
-            proposals.push(proposal);
+            proposals.push((key_str, proposal));
         }
     }
 
-    Ok(Json(ProposalsResponse { proposals }))
+    // 필터, 정렬 및 pagination 적용
+    let page = paginate(proposals, &list)?;
+
+    Ok(Json(ProposalsResponse {
+        proposals: page.items.into_iter().map(|(key, proposal)| Keyed::new(&key, proposal)).collect(),
+        next_cursor: page.next_cursor,
+    }))
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::DELETED_RECORDS_TABLE;
+    use crate::chain::tests::chain_with_deposits;
+    use turtle_service::snapshot::VOTING_POWER_SNAPSHOT_TABLE;
+    use turtle_service::deposit::deposit;
+    use turtle_service::lifecycle::close_proposal;
+    use sol::pubkey::Pubkey;
+    use turtle_service::parser::community::{GovernanceRules, ProposalKind, ProposalOutcome};
+    use tempfile::tempdir;
+    use turtle_database::basic_db::InnerDatabase;
+    use turtle_service::test_util::{community, content, proposal, wallet};
+
+    // 하루 뒤 마감하는 시간 제한 변경 제안
+    fn open_proposal() -> Proposal {
+        Proposal {
+            voting_end_time: unix_now() + 24 * 60 * 60,
+            ..proposal(ProposalKind::TimeLimit(7200))
+        }
+    }
+
+    fn caller() -> WalletIdentity {
+        signer("admin")
+    }
+
+    fn signer(pubkey: &str) -> WalletIdentity {
+        WalletIdentity {
+            pubkey: pubkey.to_string(),
+        }
+    }
+
+    // caller()가 제안을 올릴 수 있도록 예치 포지션 생성
+    fn fund_proposer(db: &InnerDatabase, pda: &str) -> Result<(), Box<dyn std::error::Error>> {
+        deposit(db, pda, &caller().pubkey, 100, 0, unix_now())?;
+        Ok(())
+    }
+
+    fn child(pda: &str, id: u64) -> Query<ChildQuery> {
+        Query(ChildQuery {
+            pda: pda.to_string(),
+            id,
+        })
+    }
+
+    fn pda(pda: &str) -> Query<PdaQuery> {
+        Query(PdaQuery {
+            pda: pda.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_content_updates_counter_and_keeps_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", &encode(&community())?, "community")?;
+
+        for author in [wallet(1), wallet(2)] {
+            save_content(State(Clone::clone(&db)), signer(&author), Query(ContentCreateQuery { pda: "pda1".to_string() }), Json(content(&author))).await?;
+        }
+
+        let status = delete_child::<_, Content>(State(Clone::clone(&db)), caller(), child("pda1", 1), Query(DeleteQuery { hard: false })).await?;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(db.read("pda1_1", "content")?.is_none());
+        assert_eq!(load_community(&db, "pda1")?.content_count, 1);
+
+        // 감사 기록에 원본과 삭제자가 남음
+        let archived = db.read_all(DELETED_RECORDS_TABLE)?;
+        assert_eq!(archived.len(), 1);
+        let record: crate::archive::DeletedRecord = serde_json::from_slice(archived.values().next().unwrap())?;
+        assert_eq!((record.table.as_str(), record.key.as_str()), ("content", "pda1_1"));
+        assert_eq!(record.deleted_by.as_deref(), Some("admin"));
+
+        // 카운터가 줄어도 새 콘텐츠는 기존 키를 덮어쓰지 않음
+        save_content(State(Clone::clone(&db)), signer(&wallet(3)), Query(ContentCreateQuery { pda: "pda1".to_string() }), Json(content(&wallet(3)))).await?;
+        assert!(db.read("pda1_2", "content")?.is_some());
+        assert!(db.read("pda1_3", "content")?.is_some());
+        assert_eq!(load_community(&db, "pda1")?.content_count, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_proposal_patch_and_hard_delete_track_active_count() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", &encode(&community())?, "community")?;
+        fund_proposer(&db, "pda1")?;
+        save_proposal(State(Clone::clone(&db)), caller(), Query(ProposalCreateQuery { pda: "pda1".to_string() }), Json(open_proposal())).await?;
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 1);
+
+        // 실행 여부와 집계는 lifecycle 엔진과 투표로만 바뀌므로 수정 요청은 읽기 전용 필드를 알려주며 거부
+        let read_only = patch_child::<_, Proposal>(State(Clone::clone(&db)), child("pda1", 1), Json(serde_json::json!({ "is_executed": true, "yes_votes": "many" }))).await;
+        let Err(ApiError::InvalidFields(errors)) = read_only else {
+            return Err("expected InvalidFields".into());
+        };
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["yes_votes", "is_executed"]);
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 1);
+
+        // 투표 중인 제안은 삭제할 수 없음
+        let open = delete_child::<_, Proposal>(State(Clone::clone(&db)), caller(), child("pda1", 1), Query(DeleteQuery { hard: true })).await;
+        assert!(matches!(open, Err(ApiError::ConflictError(_))));
+        assert!(db.read("pda1_1", "proposal")?.is_some());
+
+        // 마감된 제안은 삭제 가능. 마감 때 이미 활성 제안 수에서 빠졌으므로 다시 줄이지 않음
+        let outcome = close_proposal(&db, "pda1_1", open_proposal().voting_end_time + 1)?;
+        assert_eq!(outcome, Some(ProposalOutcome::QuorumNotMet));
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 0);
+        save_proposal(State(Clone::clone(&db)), caller(), Query(ProposalCreateQuery { pda: "pda1".to_string() }), Json(open_proposal())).await?;
+
+        assert!(db.read("pda1_1", VOTING_POWER_SNAPSHOT_TABLE)?.is_some());
+        delete_child::<_, Proposal>(State(Clone::clone(&db)), caller(), child("pda1", 1), Query(DeleteQuery { hard: true })).await?;
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 1);
+        assert!(db.read("pda1_1", VOTING_POWER_SNAPSHOT_TABLE)?.is_none());
+        assert!(db.read_all(DELETED_RECORDS_TABLE)?.is_empty());
+
+        let missing = delete_child::<_, Proposal>(State(Clone::clone(&db)), caller(), child("pda1", 1), Query(DeleteQuery { hard: false })).await;
+        assert!(matches!(missing, Err(ApiError::NotFoundError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_proposal_kind_is_validated() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", &encode(&community())?, "community")?;
+        fund_proposer(&db, "pda1")?;
+
+        let create = |kind: ProposalKind| save_proposal(
+            State(Clone::clone(&db)),
+            caller(),
+            Query(ProposalCreateQuery { pda: "pda1".to_string() }),
+            Json(Proposal { kind, ..open_proposal() }),
+        );
+        assert!(matches!(create(ProposalKind::DepositShare(150)).await, Err(ApiError::InvalidFields(errors)) if errors[0].field == "kind"));
+        assert!(matches!(create(ProposalKind::Unknown { proposal_type: 7, new_value: 0 }).await, Err(ApiError::InvalidFields(_))));
+        create(ProposalKind::DepositShare(80)).await?;
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 1);
+
+        // 요청의 id와 관계없이 저장된 id는 발급된 키 번호
+        save_proposal(State(Clone::clone(&db)), caller(), Query(ProposalCreateQuery { pda: "pda1".to_string() }), Json(Proposal { id: 1, ..open_proposal() })).await?;
+        let second: Proposal = decode(db.read("pda1_2", "proposal")?.unwrap())?;
+        assert_eq!(second.id, 2);
+        let result = patch_child::<_, Proposal>(State(Clone::clone(&db)), child("pda1", 2), Json(serde_json::json!({ "id": 7 }))).await;
+        assert!(matches!(result, Err(ApiError::InvalidFields(errors)) if errors[0].field == "id"));
+        let second: Proposal = decode(db.read("pda1_2", "proposal")?.unwrap())?;
+        assert_eq!(second.id, 2);
+
+        // 투표가 서명한 내용이 바뀌지 않도록 수정 요청(예전 형식 포함)으로는 유형, 기간, 실행 여부를 바꿀 수 없음
+        for patch in [serde_json::json!({ "kind": { "type": "time_limit", "value": 1 } }), serde_json::json!({ "proposal_type": 1, "new_value": 1 })] {
+            let result = patch_child::<_, Proposal>(State(Clone::clone(&db)), child("pda1", 1), Json(patch)).await;
+            assert!(matches!(result, Err(ApiError::InvalidFields(_))));
+        }
+        let legacy: Proposal = serde_json::from_value(serde_json::json!({
+            "id": 1, "proposal_type": 1, "new_value": 250, "voting_end_time": 0, "yes_votes": 0, "no_votes": 0, "is_executed": true,
+        }))?;
+        update_child::<_, Proposal>(State(Clone::clone(&db)), child("pda1", 1), Json(legacy)).await?;
+        let stored: Proposal = decode(db.read("pda1_1", "proposal")?.unwrap())?;
+        assert_eq!((stored.kind, stored.voting_end_time, stored.is_executed), (ProposalKind::DepositShare(80), open_proposal().voting_end_time, false));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_proposal_creation_follows_governance_rules() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let community = Community {
+            governance: GovernanceRules { max_active_proposals: 1, ..GovernanceRules::default() },
+            ..community()
+        };
+        db.write("pda1", &encode(&community)?, "community")?;
+        fund_proposer(&db, "pda1")?;
+
+        let create = |voting_end_time: u64| save_proposal(
+            State(Clone::clone(&db)),
+            caller(),
+            Query(ProposalCreateQuery { pda: "pda1".to_string() }),
+            Json(Proposal { voting_end_time, ..open_proposal() }),
+        );
+
+        // 최소 투표 기간보다 짧은 제안은 거부 사유와 함께 422
+        let rejected = create(1).await;
+        assert!(matches!(rejected, Err(ApiError::InvalidFields(errors)) if errors[0].message.contains("shorter than the community minimum")));
+
+        create(unix_now() + 24 * 60 * 60).await?;
+        assert!(matches!(create(unix_now() + 24 * 60 * 60).await, Err(ApiError::ConflictError(_))));
+
+        // 마감 때 적용할 규칙은 생성 시점 값으로 저장되고 수정 요청으로 바꿀 수 없음
+        let result = patch_child::<_, Proposal>(State(Clone::clone(&db)), child("pda1", 1), Json(serde_json::json!({ "rules": null }))).await;
+        assert!(matches!(result, Err(ApiError::InvalidFields(errors)) if errors[0].field == "rules"));
+        let stored: Proposal = decode(db.read("pda1_1", "proposal")?.unwrap())?;
+        assert_eq!(stored.rules, Some(community.governance));
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_proposal_requires_voting_power() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", &encode(&community())?, "community")?;
+        let create = |caller: WalletIdentity| save_proposal(
+            State(Clone::clone(&db)),
+            caller,
+            Query(ProposalCreateQuery { pda: "pda1".to_string() }),
+            Json(open_proposal()),
+        );
+
+        // 예치 포지션이 없는 지갑은 제안을 올려 활성 제안 한도를 채울 수 없음
+        assert!(matches!(create(caller()).await, Err(ApiError::ForbiddenError(_))));
+        assert!(db.read_all("proposal")?.is_empty());
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 0);
+
+        // 예치한 지갑, 그리고 그 지갑의 투표권을 위임받은 지갑은 허용
+        fund_proposer(&db, "pda1")?;
+        create(caller()).await?;
+        turtle_service::delegation::delegate(&db, "pda1", &caller().pubkey, "delegate", unix_now())?;
+        create(signer("delegate")).await?;
+        assert_eq!(load_community(&db, "pda1")?.active_proposal_count, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_preview_proposal_does_not_write() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", &encode(&community())?, "community")?;
+
+        let target = || Query(ProposalCreateQuery { pda: "pda1".to_string() });
+        let preview = preview_proposal(State(Clone::clone(&db)), target(), Json(Proposal { kind: ProposalKind::DepositShare(80), ..open_proposal() })).await?;
+        assert_eq!((preview.before.deposit_share, preview.after.deposit_share), (50, 80));
+        assert_eq!(load_community(&db, "pda1")?.deposit_share, 50);
+        assert!(db.read_all("proposal")?.is_empty());
+
+        let invalid = preview_proposal(State(Clone::clone(&db)), target(), Json(Proposal { kind: ProposalKind::DepositShare(101), ..open_proposal() })).await;
+        assert!(matches!(invalid, Err(ApiError::InvalidFields(_))));
+        let missing = preview_proposal(State(Clone::clone(&db)), Query(ProposalCreateQuery { pda: "pda2".to_string() }), Json(open_proposal())).await;
+        assert!(matches!(missing, Err(ApiError::NotFoundError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_single_lookup_and_list_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", &encode(&community())?, "community")?;
+        for author in [wallet(1), wallet(2)] {
+            save_content(State(Clone::clone(&db)), signer(&author), Query(ContentCreateQuery { pda: "pda1".to_string() }), Json(content(&author))).await?;
+        }
+
+        let found = get_child::<_, Content>(State(Clone::clone(&db)), child("pda1", 2)).await?;
+        assert_eq!((found.key_id, found.item.author.as_str()), (2, wallet(2).as_str()));
+        let missing = get_child::<_, Proposal>(State(Clone::clone(&db)), child("pda1", 1)).await;
+        assert!(matches!(missing, Err(ApiError::NotFoundError(_))));
+
+        // 목록 항목에도 단건 조회에 쓰는 ID가 포함됨
+        let listed = get_contents_by_pda(State(Clone::clone(&db)), pda("pda1"), Query(ListQuery::default())).await?;
+        let body = serde_json::to_value(&listed.0)?;
+        assert_eq!(body["contents"][1]["key_id"], 2);
+        assert_eq!(body["contents"][1]["author"], wallet(2));
+
+        let communities = get_all_communities(State(Clone::clone(&db)), Query(ListQuery::default())).await?;
+        assert_eq!(serde_json::to_value(&communities.0)?["communities"][0]["pda"], "pda1");
+
+        // 형식이 틀린 값은 422, 득표 수는 읽기 전용
+        let invalid = patch_child::<_, Content>(State(Clone::clone(&db)), child("pda1", 1), Json(serde_json::json!({ "timestamp": "soon" }))).await;
+        assert!(matches!(invalid, Err(ApiError::ValidationError(_))));
+        let read_only = patch_child::<_, Content>(State(Clone::clone(&db)), child("pda1", 1), Json(serde_json::json!({ "votes": 100 }))).await;
+        assert!(matches!(read_only, Err(ApiError::InvalidFields(errors)) if errors[0].field == "votes"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_depositor_positions_keep_community_totals_exact() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let (_, admin) = crate::auth::tests::test_wallet(1);
+        let (_, alice) = crate::auth::tests::test_wallet(2);
+        let (_, bob) = crate::auth::tests::test_wallet(3);
+        let (address, _) = sol::pda::community_pda(&parse_wallet(&admin)?, &program_id)?;
+        let address = address.to_string();
+        db.write(&address, &encode(&community())?, "community")?;
+
+        // alice는 온체인에 150을 예치, bob은 예치 계정이 없음
+        let chain = || Extension(chain_with_deposits(program_id, &address, &[(&alice, 150, 0)]));
+        let query = || Query(DepositorCreateQuery { pda: address.clone() });
+        let depositor = |pubkey: &str, amount| Json(Depositor {
+            pubkey: pubkey.to_string(),
+            amount,
+            locked_until: 0,
+            voting_power: 999,
+            unlocked_at: Some(1),
+        });
+
+        for amount in [100, 50] {
+            save_depositor(State(Clone::clone(&db)), chain(), signer(&alice), query(), depositor(&alice, amount)).await?;
+        }
+        let community = load_community(&db, &address)?;
+        assert_eq!((community.total_deposit, community.depositor_count), (150, 1));
+
+        // 온체인 잔액을 넘는 예치, 다른 지갑 명의의 예치, 예치 계정이 없는 지갑은 거부
+        let result = save_depositor(State(Clone::clone(&db)), chain(), signer(&alice), query(), depositor(&alice, 1)).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+        let result = save_depositor(State(Clone::clone(&db)), chain(), signer(&bob), query(), depositor(&alice, 1)).await;
+        assert!(matches!(result, Err(ApiError::ForbiddenError(_))));
+        let result = save_depositor(State(Clone::clone(&db)), chain(), signer(&bob), query(), depositor(&bob, 1)).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+        let unconfigured = Extension(ChainConfig::new(Some(program_id)));
+        let result = save_depositor(State(Clone::clone(&db)), unconfigured, signer(&alice), query(), depositor(&alice, 1)).await;
+        assert!(matches!(result, Err(ApiError::UnavailableError(_))));
+        assert_eq!(load_community(&db, &address)?.total_deposit, 150);
+
+        // 금액과 잠금 기간은 수정 요청으로 바뀌지 않음 (PATCH는 422, PUT은 기존 값 유지)
+        let result = patch_child::<_, Depositor>(State(Clone::clone(&db)), child(&address, 1), Json(serde_json::json!({ "amount": 1, "locked_until": u64::MAX }))).await;
+        let Err(ApiError::InvalidFields(errors)) = result else {
+            return Err("expected InvalidFields".into());
+        };
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["amount", "locked_until"]);
+        update_child::<_, Depositor>(State(Clone::clone(&db)), child(&address, 1), depositor(&alice, 1)).await?;
+        let stored: Depositor = decode(db.read(&format!("{}_1", address), "depositor")?.unwrap())?;
+        assert_eq!((stored.amount, stored.voting_power, stored.locked_until), (150, 150, 0));
+        let locked = Depositor { locked_until: u64::MAX, ..stored };
+        update_child::<_, Depositor>(State(Clone::clone(&db)), child(&address, 1), Json(locked)).await?;
+        let stored: Depositor = decode(db.read(&format!("{}_1", address), "depositor")?.unwrap())?;
+        assert_eq!(stored.locked_until, 0);
+
+        let withdrawn = withdraw_depositor(
+            State(Clone::clone(&db)),
+            signer(&alice),
+            Query(WithdrawQuery { pda: address.clone(), amount: 200 }),
+        ).await;
+        assert!(matches!(withdrawn, Err(ApiError::ValidationError(_))));
+
+        delete_child::<_, Depositor>(State(Clone::clone(&db)), signer(&admin), child(&address, 1), Query(DeleteQuery { hard: true })).await?;
+        let community = load_community(&db, &address)?;
+        assert_eq!((community.total_deposit, community.depositor_count), (0, 0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delete_pda_requires_community_removed() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", "pda1", "daopda")?;
+        db.write("pda1", &encode(&community())?, "community")?;
+
+        let result = delete_pda(State(Clone::clone(&db)), caller(), pda("pda1"), Query(DeleteQuery { hard: false })).await;
+        assert!(matches!(result, Err(ApiError::ConflictError(_))));
+
+        db.delete("pda1", "community")?;
+        delete_pda(State(Clone::clone(&db)), caller(), pda("pda1"), Query(DeleteQuery { hard: false })).await?;
+        assert!(db.read("pda1", "daopda")?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_fields_are_reported_together() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        db.write("pda1", &encode(&community())?, "community")?;
+
+        let invalid = Content {
+            content_uri: "file:///etc/passwd".to_string(),
+            timestamp: u64::MAX,
+            ..content("author")
+        };
+        let result = save_content(State(Clone::clone(&db)), signer("author"), Query(ContentCreateQuery { pda: "pda1".to_string() }), Json(invalid)).await;
+        let Err(ApiError::InvalidFields(errors)) = result else {
+            return Err("expected InvalidFields".into());
+        };
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["author", "content_uri", "timestamp"]);
+        assert_eq!(load_community(&db, "pda1")?.content_count, 0);
+
+        // 다른 지갑 명의로는 올릴 수 없음
+        let result = save_content(State(Clone::clone(&db)), signer(&wallet(2)), Query(ContentCreateQuery { pda: "pda1".to_string() }), Json(content(&wallet(1)))).await;
+        assert!(matches!(result, Err(ApiError::ForbiddenError(_))));
+        assert_eq!(load_community(&db, "pda1")?.content_count, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pda_addresses_are_validated() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let (_, admin) = crate::auth::tests::test_wallet(1);
+        let (address, _) = sol::pda::find_pda(&[b"test".as_ref()], &Pubkey::new_from_array([7; 32]))?;
+        let address = address.to_string();
+        let chain = || Extension(ChainConfig::default());
+        let daopda = |address: &str| Json(Daopda { address: address.to_string(), admin: None });
+
+        // 형식이 틀린 주소와 지갑 주소는 PDA로 등록할 수 없음
+        for invalid in ["pda1", admin.as_str()] {
+            let result = save_pda(State(Clone::clone(&db)), chain(), signer(&admin), daopda(invalid)).await;
+            assert!(matches!(result, Err(ApiError::InvalidFields(errors)) if errors[0].field == "address"));
+        }
+        save_pda(State(Clone::clone(&db)), chain(), signer(&admin), daopda(&address)).await?;
+        let stored: Daopda = decode(db.read(&address, "daopda")?.unwrap())?;
+        assert_eq!(stored.admin.as_deref(), Some(admin.as_str()));
+
+        // 다른 지갑은 등록된 PDA를 가져갈 수 없음
+        let (_, other) = crate::auth::tests::test_wallet(2);
+        let result = save_pda(State(Clone::clone(&db)), chain(), signer(&other), daopda(&address)).await;
+        assert!(matches!(result, Err(ApiError::ConflictError(_))));
+        let result = save_pda(State(Clone::clone(&db)), chain(), signer(&other), Json(Daopda { address: address.clone(), admin: Some(admin.clone()) })).await;
+        assert!(matches!(result, Err(ApiError::ForbiddenError(_))));
+
+        // 관리자는 PDA가 아닌 지갑이어야 함
+        let result = save_community(State(Clone::clone(&db)), chain(), pda(&address), Json(Community { admin: address.clone(), ..community() })).await;
+        assert!(matches!(result, Err(ApiError::InvalidFields(errors)) if errors[0].field == "admin"));
+        save_community(State(Clone::clone(&db)), chain(), pda(&address), Json(Community { admin: admin.clone(), ..community() })).await?;
+
+        // 이미 저장된 예전 형식 키의 커뮤니티도 계속 수정 가능
+        let stored = Community {
+            admin: admin.clone(),
+            total_deposit: 500,
+            depositor_count: 2,
+            content_count: 3,
+            active_proposal_count: 1,
+            last_activity_timestamp: 42,
+            ..community()
+        };
+        db.write("pda1", &encode(&stored)?, "community")?;
+        let governance = GovernanceRules { quorum_percent: 1, ..GovernanceRules::default() };
+        save_community(State(Clone::clone(&db)), chain(), pda("pda1"), Json(Community { admin, base_fee: 1, governance, ..community() })).await?;
+
+        // 서버가 관리하는 집계와 거버넌스 규칙(거버넌스 제안으로만 변경)은 요청 값으로 덮어쓰지 않음
+        let updated = load_community(&db, "pda1")?;
+        assert_eq!(updated.base_fee, 1);
+        assert_eq!(updated.governance, stored.governance);
+        assert_eq!((updated.total_deposit, updated.depositor_count, updated.content_count), (500, 2, 3));
+        assert_eq!((updated.active_proposal_count, updated.last_activity_timestamp), (1, 42));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pda_must_derive_from_admin_with_program_id() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let chain = || Extension(ChainConfig::new(Some(program_id)));
+        let (_, admin) = crate::auth::tests::test_wallet(1);
+        let (_, other) = crate::auth::tests::test_wallet(3);
+        let (address, _) = sol::pda::community_pda(&parse_wallet(&admin)?, &program_id)?;
+        let address = address.to_string();
+        let daopda = || Json(Daopda { address: address.clone(), admin: None });
+
+        // 서명한 지갑에서 유도되지 않은 주소는 거부
+        let result = save_pda(State(Clone::clone(&db)), chain(), signer(&other), daopda()).await;
+        assert!(matches!(result, Err(ApiError::InvalidFields(errors)) if errors[0].field == "address"));
+        assert!(db.read(&address, "daopda")?.is_none());
+
+        // 일치하면 서명한 지갑을 관리자로 저장
+        save_pda(State(Clone::clone(&db)), chain(), signer(&admin), daopda()).await?;
+        let stored: Daopda = decode(db.read(&address, "daopda")?.unwrap())?;
+        assert_eq!(stored.admin.as_deref(), Some(admin.as_str()));
+
+        let result = save_community(State(Clone::clone(&db)), chain(), pda(&address), Json(Community { admin: other, ..community() })).await;
+        assert!(matches!(result, Err(ApiError::InvalidFields(errors)) if errors[0].field == "pda"));
+        save_community(State(Clone::clone(&db)), chain(), pda(&address), Json(Community { admin, ..community() })).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_community_is_not_found() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let result = get_community_by_pda(State(Clone::clone(&db)), pda("pda1")).await;
+        assert!(matches!(result, Err(ApiError::NotFoundError(_))));
+        let result = get_community_by_pda(State(Clone::clone(&db)), pda("")).await;
+        assert!(matches!(result, Err(ApiError::MissingFields(fields)) if fields == vec!["pda"]));
+
+        let result = save_proposal(State(Clone::clone(&db)), caller(), Query(ProposalCreateQuery { pda: "pda1".to_string() }), Json(open_proposal())).await;
+        assert!(matches!(result, Err(ApiError::NotFoundError(_))));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use crate::extract::{Json, Query};
+use serde::{Deserialize, Serialize};
+use turtle_database::basic_db::SafeDatabase;
+use turtle_service::delegation::{delegate, delegates_of, delegators_of, revoke, Delegation, DelegationError, Delegator};
+use crate::auth::WalletIdentity;
+use crate::error::ApiError;
+use crate::clock::unix_now;
+
+#[derive(Deserialize)]
+pub struct DelegateQuery {
+    pda: String,
+    delegate: String,
+}
+
+#[derive(Deserialize)]
+pub struct RevokeQuery {
+    pda: String,
+}
+
+#[derive(Deserialize)]
+pub struct DelegationLookupQuery {
+    pda: String,
+    wallet: String,
+}
+
+#[derive(Serialize)]
+pub struct DelegatesResponse {
+    delegates: Vec<String>,         // 직접 대리인부터 최종 대리인 순
+}
+
+#[derive(Serialize)]
+pub struct DelegatorsResponse {
+    delegators: Vec<Delegator>,
+}
+
+pub(crate) fn delegation_error(e: DelegationError) -> ApiError {
+    match e {
+        DelegationError::Storage(e) => e.into(),
+        DelegationError::Validation(msg) => ApiError::ValidationError(msg),
+        DelegationError::Cycle(_) => ApiError::ConflictError(e.to_string()),
+    }
+}
+
+// 서명한 지갑의 투표권을 다른 지갑에 위임 (기존 위임은 교체)
+pub async fn save_delegation<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<DelegateQuery>,
+) -> Result<Json<Delegation>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let now = unix_now();
+
+    let delegation = delegate(&database, &query.pda, &caller.pubkey, &query.delegate, now)
+        .map_err(delegation_error)?;
+
+    Ok(Json(delegation))
+}
+
+// 서명한 지갑의 위임 철회
+pub async fn delete_delegation<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<RevokeQuery>,
+) -> Result<StatusCode, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    revoke(&database, &query.pda, &caller.pubkey).map_err(delegation_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+pub async fn get_delegates<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<DelegationLookupQuery>,
+) -> Result<Json<DelegatesResponse>, ApiError> {
+    ApiError::require(&[("pda", query.pda.as_str()), ("wallet", query.wallet.as_str())])?;
+
+    let delegates = delegates_of(&database, &query.pda, &query.wallet).map_err(delegation_error)?;
+    Ok(Json(DelegatesResponse { delegates }))
+}
+
+pub async fn get_delegators<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<DelegationLookupQuery>,
+) -> Result<Json<DelegatorsResponse>, ApiError> {
+    ApiError::require(&[("pda", query.pda.as_str()), ("wallet", query.wallet.as_str())])?;
+
+    let delegators = delegators_of(&database, &query.pda, &query.wallet).map_err(delegation_error)?;
+    Ok(Json(DelegatorsResponse { delegators }))
+}
@@ -0,0 +1,338 @@
+use axum::extract::rejection::{JsonRejection, QueryRejection};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+use std::error::Error as StdError;
+use std::fmt;
+use turtle_database::error::StorageError;
+use turtle_service::validation::ValidationErrors;
+
+// 설정하면 500 응답에 내부 에러 메시지를 그대로 포함 (개발용, 기본은 숨김)
+pub const DEBUG_ERRORS_ENV: &str = "TURTLE_DEBUG_ERRORS";
+
+// 내부 에러를 숨길 때 응답 메시지
+const INTERNAL_ERROR_MESSAGE: &str = "Internal server error";
+
+// 필드 단위 검증 실패 사유
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl fmt::Display) -> Self {
+        FieldError {
+            field: field.into(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// 모든 에러 응답의 JSON 본문
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub error: &'static str,                // 안정적인 에러 코드
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'static str>,       // 인증/권한 거부의 세부 사유
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<FieldError>,
+}
+
+impl ErrorBody {
+    pub fn new(error: &'static str, message: impl Into<String>) -> Self {
+        ErrorBody {
+            error,
+            reason: None,
+            message: message.into(),
+            details: Vec::new(),
+        }
+    }
+
+    pub fn with_reason(mut self, reason: &'static str) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    pub fn respond(self, status: StatusCode) -> Response {
+        (status, Json(self)).into_response()
+    }
+}
+
+// API 핸들러 공통 에러 타입
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum ApiError {
+    MalformedRequest(String),           // 본문, 멀티파트, 쿼리를 해석할 수 없음
+    MissingFields(Vec<&'static str>),   // 필수 쿼리 파라미터나 필드가 비어 있음
+    DatabaseError(String),
+    SerializationError(String),
+    ValidationError(String),            // 요청 형식은 맞지만 현재 상태나 규칙에 맞지 않음
+    InvalidFields(Vec<FieldError>),     // 필드별 값 검증 실패
+    NotFoundError(String),
+    ConflictError(String),
+    UnauthorizedError(String),
+    ForbiddenError(String),
+    UnavailableError(String),           // 설정되지 않았거나 응답하지 않는 외부 의존성 (온체인 RPC 등)
+    RateLimited(u64),                   // 요청 제한 초과. 다시 시도할 수 있을 때까지의 초
+}
+
+impl ApiError {
+    pub fn missing_field(field: &'static str) -> Self {
+        ApiError::MissingFields(vec![field])
+    }
+
+    // 비어 있는 필수 값을 모두 모아 한 번에 거부
+    pub fn require(fields: &[(&'static str, &str)]) -> Result<(), Self> {
+        let missing: Vec<&'static str> = fields.iter()
+            .filter(|(_, value)| value.is_empty())
+            .map(|(name, _)| *name)
+            .collect();
+        if missing.is_empty() { Ok(()) } else { Err(ApiError::MissingFields(missing)) }
+    }
+
+    pub fn invalid_field(field: impl Into<String>, message: impl fmt::Display) -> Self {
+        ApiError::InvalidFields(vec![FieldError::new(field, message)])
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::MalformedRequest(_) | ApiError::MissingFields(_) => StatusCode::BAD_REQUEST,
+            ApiError::UnauthorizedError(_) => StatusCode::UNAUTHORIZED,
+            ApiError::ForbiddenError(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFoundError(_) => StatusCode::NOT_FOUND,
+            ApiError::ConflictError(_) => StatusCode::CONFLICT,
+            ApiError::UnavailableError(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::ValidationError(_) | ApiError::InvalidFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::DatabaseError(_) | ApiError::SerializationError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    // 클라이언트가 분기할 수 있는 고정 코드
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::MalformedRequest(_) => "malformed_request",
+            ApiError::MissingFields(_) => "missing_field",
+            ApiError::ValidationError(_) | ApiError::InvalidFields(_) => "validation_failed",
+            ApiError::NotFoundError(_) => "not_found",
+            ApiError::ConflictError(_) => "conflict",
+            ApiError::UnauthorizedError(_) => "unauthorized",
+            ApiError::ForbiddenError(_) => "forbidden",
+            ApiError::UnavailableError(_) => "unavailable",
+            ApiError::RateLimited(_) => "rate_limited",
+            ApiError::DatabaseError(_) | ApiError::SerializationError(_) => "internal_error",
+        }
+    }
+
+    fn is_internal(&self) -> bool {
+        matches!(self, ApiError::DatabaseError(_) | ApiError::SerializationError(_))
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::MalformedRequest(msg) => write!(f, "Malformed request: {}", msg),
+            ApiError::MissingFields(fields) => write!(f, "Required fields are missing: {}", fields.join(", ")),
+            ApiError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+            ApiError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
+            ApiError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
+            ApiError::InvalidFields(errors) => {
+                let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+                write!(f, "Invalid fields: {}", fields.join(", "))
+            },
+            ApiError::NotFoundError(msg) => write!(f, "Not found: {}", msg),
+            ApiError::ConflictError(msg) => write!(f, "Conflict: {}", msg),
+            ApiError::UnauthorizedError(msg) => write!(f, "Unauthorized: {}", msg),
+            ApiError::ForbiddenError(msg) => write!(f, "Forbidden: {}", msg),
+            ApiError::UnavailableError(msg) => write!(f, "Unavailable: {}", msg),
+            ApiError::RateLimited(retry_after) => write!(f, "Too many requests, retry after {} seconds", retry_after),
+        }
+    }
+}
+
+impl StdError for ApiError {}
+
+// 서비스 계층의 필드 규칙 위반을 그대로 필드 에러로 전달
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        ApiError::InvalidFields(errors.0.into_iter()
+            .map(|violation| FieldError {
+                field: violation.field,
+                message: violation.message,
+            })
+            .collect())
+    }
+}
+
+// 저장소 에러는 모듈과 관계없이 같은 응답으로 변환
+impl From<StorageError> for ApiError {
+    fn from(e: StorageError) -> Self {
+        match e {
+            StorageError::Database(msg) => ApiError::DatabaseError(msg),
+            StorageError::Serialization(msg) => ApiError::SerializationError(msg),
+            StorageError::NotFound(msg) => ApiError::NotFoundError(msg),
+        }
+    }
+}
+
+// 해석할 수 없는 JSON 본문 (Content-Type 누락, 문법 오류, 필드 타입 불일치)
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::MalformedRequest(rejection.body_text())
+    }
+}
+
+// 해석할 수 없는 쿼리 문자열
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::MalformedRequest(rejection.body_text())
+    }
+}
+
+fn debug_errors() -> bool {
+    std::env::var(DEBUG_ERRORS_ENV)
+        .map(|value| matches!(value.trim(), "1" | "true"))
+        .unwrap_or(false)
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        self.into_response_with(debug_errors())
+    }
+}
+
+impl ApiError {
+    // debug가 true면 내부 에러 메시지도 응답에 포함
+    fn into_response_with(self, debug: bool) -> Response {
+        let status = self.status();
+        let code = self.code();
+        let retry_after = match self {
+            ApiError::RateLimited(retry_after) => Some(retry_after),
+            _ => None,
+        };
+
+        // 내부 에러 메시지는 로그에만 남기고 응답에서는 숨김
+        let message = if self.is_internal() {
+            eprintln!("Internal API error: {}", self);
+            if debug { self.to_string() } else { INTERNAL_ERROR_MESSAGE.to_string() }
+        } else {
+            match &self {
+                ApiError::MalformedRequest(msg)
+                | ApiError::ValidationError(msg)
+                | ApiError::NotFoundError(msg)
+                | ApiError::ConflictError(msg)
+                | ApiError::UnauthorizedError(msg)
+                | ApiError::ForbiddenError(msg)
+                | ApiError::UnavailableError(msg) => msg.clone(),
+                _ => self.to_string(),
+            }
+        };
+
+        let details = match self {
+            ApiError::MissingFields(fields) => fields.into_iter().map(|field| FieldError::new(field, "is required")).collect(),
+            ApiError::InvalidFields(errors) => errors,
+            _ => Vec::new(),
+        };
+
+        let mut response = ErrorBody {
+            error: code,
+            reason: None,
+            message,
+            details,
+        }.respond(status);
+
+        if let Some(retry_after) = retry_after {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        response
+    }
+}
+
+// 등록되지 않은 경로
+pub async fn route_not_found(uri: axum::http::Uri) -> ApiError {
+    ApiError::NotFoundError(format!("No route for {}", uri.path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body_json(error: ApiError) -> Result<(StatusCode, serde_json::Value), Box<dyn std::error::Error>> {
+        response_json(error.into_response_with(false)).await
+    }
+
+    async fn response_json(response: Response) -> Result<(StatusCode, serde_json::Value), Box<dyn std::error::Error>> {
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        Ok((status, serde_json::from_slice(&body)?))
+    }
+
+    #[tokio::test]
+    async fn test_error_bodies_have_codes_and_details() -> Result<(), Box<dyn std::error::Error>> {
+        let missing = ApiError::require(&[("pda", "pda1"), ("wallet", ""), ("id", "")]).unwrap_err();
+        let (status, body) = body_json(missing).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "missing_field");
+        assert_eq!(body["details"][0]["field"], "wallet");
+        assert_eq!(body["details"][1]["field"], "id");
+
+        let (status, body) = body_json(ApiError::invalid_field("base_fee", "must be at most 10")).await?;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"], "validation_failed");
+        assert_eq!(body["details"][0]["message"], "must be at most 10");
+
+        let (status, body) = body_json(ApiError::NotFoundError("Community with PDA x not found".to_string())).await?;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "not_found");
+        assert!(body.get("details").is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_internal_errors_are_hidden_by_default() -> Result<(), Box<dyn std::error::Error>> {
+        let (status, body) = body_json(ApiError::DatabaseError("mdbx: page corrupted at /var/db".to_string())).await?;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"], "internal_error");
+        assert_eq!(body["message"], INTERNAL_ERROR_MESSAGE);
+
+        // 디버그 모드에서만 원본 메시지를 포함
+        let error = ApiError::DatabaseError("mdbx: page corrupted at /var/db".to_string());
+        let (_, body) = response_json(error.into_response_with(true)).await?;
+        assert_eq!(body["message"], "Database error: mdbx: page corrupted at /var/db");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_requests_use_error_body() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::extract::{Json, Query};
+        use axum::extract::{FromRequest, FromRequestParts};
+        use axum::http::Request;
+
+        #[derive(Debug, serde::Deserialize)]
+        struct Target {
+            #[allow(dead_code)]
+            id: u64,
+        }
+
+        let request = Request::builder()
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(r#"{"id":"one"}"#))?;
+        let rejection = Json::<Target>::from_request(request, &()).await.unwrap_err();
+        let (status, body) = response_json(rejection.into_response()).await?;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "malformed_request");
+
+        let (mut parts, _) = Request::builder().uri("/proposal?id=one").body(())?.into_parts();
+        let rejection = Query::<Target>::from_request_parts(&mut parts, &()).await.unwrap_err();
+        let (_, body) = response_json(rejection.into_response()).await?;
+        assert_eq!(body["error"], "malformed_request");
+
+        Ok(())
+    }
+}
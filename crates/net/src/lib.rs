@@ -1,5 +1,18 @@
 mod router;
 mod profile;
 pub mod server;
+pub mod error;
+pub mod extract;
+pub mod auth;
+pub mod policy;
+pub mod rate_limit;
 
-pub mod community;
\ No newline at end of file
+pub mod community;
+pub mod listing;
+pub mod archive;
+pub mod vote;
+pub mod scheduler;
+pub mod claim;
+pub mod delegation;
+pub mod chain;
+pub mod clock;
\ No newline at end of file
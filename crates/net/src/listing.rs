@@ -0,0 +1,381 @@
+use serde::Deserialize;
+use turtle_service::parser::community::{Community, Content, Depositor, Proposal};
+use crate::error::ApiError;
+
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+pub const MAX_PAGE_LIMIT: usize = 200;
+
+// 목록 API 공통 쿼리 (pagination, 정렬, 필터)
+#[derive(Deserialize, Default)]
+pub struct ListQuery {
+    pub limit: Option<usize>,
+    pub after: Option<String>,          // 이전 응답의 next_cursor
+    pub sort: Option<SortKey>,
+    pub order: Option<SortOrder>,
+    pub author: Option<String>,
+    pub from: Option<u64>,              // 시간 범위 시작(포함)
+    pub to: Option<u64>,                // 시간 범위 끝(포함)
+    pub is_executed: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    #[default]
+    Id,
+    Timestamp,
+    Votes,
+    Amount,
+    VotingEndTime,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+// 목록 정렬/필터 대상 리소스
+pub trait Listable {
+    // 지원하는 정렬 키와 필터(author, from/to, is_executed). 목록이 비어 있어도 요청을 검증할 수 있도록 타입 단위로 선언
+    const SORT_KEYS: &'static [SortKey];
+    const FILTERS: &'static [&'static str];
+
+    // 지원하지 않는 정렬 키면 None
+    fn sort_value(&self, key: SortKey, id: u64) -> Option<u64>;
+
+    // 시간 범위 필터 대상 필드
+    fn time(&self) -> Option<u64> {
+        None
+    }
+
+    fn author(&self) -> Option<&str> {
+        None
+    }
+
+    fn is_executed(&self) -> Option<bool> {
+        None
+    }
+}
+
+impl Listable for Community {
+    const SORT_KEYS: &'static [SortKey] = &[SortKey::Id, SortKey::Timestamp, SortKey::Amount];
+    const FILTERS: &'static [&'static str] = &["author", "from/to"];
+
+    fn sort_value(&self, key: SortKey, id: u64) -> Option<u64> {
+        match key {
+            SortKey::Id => Some(id),
+            SortKey::Timestamp => Some(self.last_activity_timestamp),
+            SortKey::Amount => Some(self.total_deposit),
+            _ => None,
+        }
+    }
+
+    fn time(&self) -> Option<u64> {
+        Some(self.last_activity_timestamp)
+    }
+
+    fn author(&self) -> Option<&str> {
+        Some(&self.admin)
+    }
+}
+
+impl Listable for Content {
+    const SORT_KEYS: &'static [SortKey] = &[SortKey::Id, SortKey::Timestamp, SortKey::Votes];
+    const FILTERS: &'static [&'static str] = &["author", "from/to"];
+
+    fn sort_value(&self, key: SortKey, id: u64) -> Option<u64> {
+        match key {
+            SortKey::Id => Some(id),
+            SortKey::Timestamp => Some(self.timestamp),
+            SortKey::Votes => Some(self.votes),
+            _ => None,
+        }
+    }
+
+    fn time(&self) -> Option<u64> {
+        Some(self.timestamp)
+    }
+
+    fn author(&self) -> Option<&str> {
+        Some(&self.author)
+    }
+}
+
+impl Listable for Depositor {
+    const SORT_KEYS: &'static [SortKey] = &[SortKey::Id, SortKey::Amount];
+    const FILTERS: &'static [&'static str] = &["author"];
+
+    fn sort_value(&self, key: SortKey, id: u64) -> Option<u64> {
+        match key {
+            SortKey::Id => Some(id),
+            SortKey::Amount => Some(self.amount),
+            _ => None,
+        }
+    }
+
+    fn author(&self) -> Option<&str> {
+        Some(&self.pubkey)
+    }
+}
+
+impl Listable for Proposal {
+    const SORT_KEYS: &'static [SortKey] = &[SortKey::Id, SortKey::Votes, SortKey::VotingEndTime];
+    const FILTERS: &'static [&'static str] = &["from/to", "is_executed"];
+
+    fn sort_value(&self, key: SortKey, id: u64) -> Option<u64> {
+        match key {
+            SortKey::Id => Some(id),
+            SortKey::Votes => Some(self.yes_votes.saturating_add(self.no_votes)),
+            SortKey::VotingEndTime => Some(self.voting_end_time),
+            _ => None,
+        }
+    }
+
+    fn time(&self) -> Option<u64> {
+        Some(self.voting_end_time)
+    }
+
+    fn is_executed(&self) -> Option<bool> {
+        Some(self.is_executed)
+    }
+}
+
+// 하위 리소스 키(pda_n)에서 n 추출. 커뮤니티처럼 번호가 없으면 0
+pub fn key_id(key: &str) -> u64 {
+    key.rsplit_once('_')
+        .and_then(|(_, n)| n.parse().ok())
+        .unwrap_or(0)
+}
+
+fn sort_name(key: SortKey) -> &'static str {
+    match key {
+        SortKey::Id => "id",
+        SortKey::Timestamp => "timestamp",
+        SortKey::Votes => "votes",
+        SortKey::Amount => "amount",
+        SortKey::VotingEndTime => "voting_end_time",
+    }
+}
+
+fn order_name(order: SortOrder) -> &'static str {
+    match order {
+        SortOrder::Asc => "asc",
+        SortOrder::Desc => "desc",
+    }
+}
+
+// 커서는 정렬 기준과 마지막 항목의 (정렬 값, 키)를 base58로 인코딩
+fn encode_cursor(sort: SortKey, order: SortOrder, value: u64, key: &str) -> String {
+    let raw = format!("{}|{}|{}|{}", sort_name(sort), order_name(order), value, key);
+    bs58::encode(raw.as_bytes()).into_string()
+}
+
+fn decode_cursor(cursor: &str, sort: SortKey, order: SortOrder) -> Result<(u64, String), ApiError> {
+    let invalid = || ApiError::MalformedRequest("Invalid cursor".to_string());
+
+    let raw = bs58::decode(cursor).into_vec().map_err(|_| invalid())?;
+    let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+
+    let mut parts = raw.splitn(4, '|');
+    let (Some(cursor_sort), Some(cursor_order), Some(value), Some(key)) =
+        (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+
+    if cursor_sort != sort_name(sort) || cursor_order != order_name(order) {
+        return Err(ApiError::MalformedRequest("Cursor does not match the requested sort".to_string()));
+    }
+
+    let value = value.parse().map_err(|_| invalid())?;
+    Ok((value, key.to_string()))
+}
+
+pub struct Page<T> {
+    pub items: Vec<(String, T)>,
+    pub next_cursor: Option<String>,
+}
+
+// (키, 항목) 목록에 필터, 정렬, 커서 pagination 적용. 동일 값은 키로 정렬해 결과가 항상 같음
+pub fn paginate<T: Listable>(items: Vec<(String, T)>, query: &ListQuery) -> Result<Page<T>, ApiError> {
+    let sort = query.sort.unwrap_or_default();
+    let order = query.order.unwrap_or_default();
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT);
+
+    let unsupported = |name: &str| ApiError::MalformedRequest(format!("Filter {} is not supported for this resource", name));
+    let unsupported_sort = || ApiError::MalformedRequest(format!("Sort key {} is not supported for this resource", sort_name(sort)));
+
+    // 항목이 없어도 같은 요청은 같은 에러가 되도록 정렬 키와 필터를 먼저 확인
+    if !T::SORT_KEYS.contains(&sort) {
+        return Err(unsupported_sort());
+    }
+    let requested = [
+        ("author", query.author.is_some()),
+        ("from/to", query.from.is_some() || query.to.is_some()),
+        ("is_executed", query.is_executed.is_some()),
+    ];
+    if let Some((name, _)) = requested.into_iter().find(|(name, requested)| *requested && !T::FILTERS.contains(name)) {
+        return Err(unsupported(name));
+    }
+
+    let mut keyed = Vec::with_capacity(items.len());
+    for (key, item) in items {
+        let value = item.sort_value(sort, key_id(&key)).ok_or_else(unsupported_sort)?;
+
+        if let Some(author) = &query.author {
+            if item.author().ok_or_else(|| unsupported("author"))? != author {
+                continue;
+            }
+        }
+
+        if query.from.is_some() || query.to.is_some() {
+            let time = item.time().ok_or_else(|| unsupported("from/to"))?;
+            if query.from.is_some_and(|from| time < from) || query.to.is_some_and(|to| time > to) {
+                continue;
+            }
+        }
+
+        if let Some(is_executed) = query.is_executed {
+            if item.is_executed().ok_or_else(|| unsupported("is_executed"))? != is_executed {
+                continue;
+            }
+        }
+
+        keyed.push((value, key, item));
+    }
+
+    keyed.sort_by(|(a_value, a_key, _), (b_value, b_key, _)| {
+        let ordering = a_value.cmp(b_value).then_with(|| a_key.cmp(b_key));
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+
+    // 커서 이후 항목부터 시작
+    let start = match &query.after {
+        Some(cursor) => {
+            let (cursor_value, cursor_key) = decode_cursor(cursor, sort, order)?;
+            keyed.partition_point(|(value, key, _)| {
+                let ordering = (*value, key.as_str()).cmp(&(cursor_value, cursor_key.as_str()));
+                match order {
+                    SortOrder::Asc => ordering.is_le(),
+                    SortOrder::Desc => ordering.is_ge(),
+                }
+            })
+        },
+        None => 0,
+    };
+
+    let remaining = keyed.len().saturating_sub(start);
+    let page: Vec<(u64, String, T)> = keyed.into_iter().skip(start).take(limit).collect();
+
+    let next_cursor = if remaining > limit {
+        page.last().map(|(value, key, _)| encode_cursor(sort, order, *value, key))
+    } else {
+        None
+    };
+
+    Ok(Page {
+        items: page.into_iter().map(|(_, key, item)| (key, item)).collect(),
+        next_cursor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use turtle_service::test_util::content;
+
+    fn contents() -> Vec<(String, Content)> {
+        [("pda_1", "alice", 100, 5), ("pda_2", "bob", 200, 9), ("pda_10", "alice", 300, 5), ("pda_3", "carol", 400, 1)]
+            .into_iter()
+            .map(|(key, author, timestamp, votes)| (key.to_string(), Content { timestamp, votes, ..content(author) }))
+            .collect()
+    }
+
+    fn keys<T>(page: &Page<T>) -> Vec<&str> {
+        page.items.iter().map(|(key, _)| key.as_str()).collect()
+    }
+
+    #[test]
+    fn test_paginate_walks_all_pages_with_cursor() {
+        let mut query = ListQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let first = paginate(contents(), &query).unwrap();
+        assert_eq!(keys(&first), vec!["pda_1", "pda_2"]);
+
+        query.after = first.next_cursor;
+        let second = paginate(contents(), &query).unwrap();
+        assert_eq!(keys(&second), vec!["pda_3", "pda_10"]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_paginate_sorts_with_key_tiebreak() {
+        let query = ListQuery {
+            sort: Some(SortKey::Votes),
+            order: Some(SortOrder::Desc),
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        let first = paginate(contents(), &query).unwrap();
+        assert_eq!(keys(&first), vec!["pda_2", "pda_10"]);
+
+        let second = paginate(contents(), &ListQuery { after: first.next_cursor, ..query }).unwrap();
+        assert_eq!(keys(&second), vec!["pda_1", "pda_3"]);
+    }
+
+    #[test]
+    fn test_paginate_filters_and_rejects_unsupported() {
+        let query = ListQuery {
+            author: Some("alice".to_string()),
+            from: Some(150),
+            ..Default::default()
+        };
+        assert_eq!(keys(&paginate(contents(), &query).unwrap()), vec!["pda_10"]);
+
+        let query = ListQuery {
+            is_executed: Some(true),
+            ..Default::default()
+        };
+        assert!(paginate(contents(), &query).is_err());
+
+        let query = ListQuery {
+            sort: Some(SortKey::VotingEndTime),
+            ..Default::default()
+        };
+        assert!(paginate(contents(), &query).is_err());
+
+        let query = ListQuery {
+            after: Some(encode_cursor(SortKey::Votes, SortOrder::Asc, 1, "pda_3")),
+            ..Default::default()
+        };
+        assert!(paginate(contents(), &query).is_err());
+    }
+
+    #[test]
+    fn test_empty_lists_reject_unsupported_sort_and_filters() {
+        let empty = Vec::<(String, Content)>::new;
+
+        let query = ListQuery {
+            sort: Some(SortKey::VotingEndTime),
+            ..Default::default()
+        };
+        assert!(matches!(paginate(empty(), &query), Err(ApiError::MalformedRequest(_))));
+
+        let query = ListQuery {
+            is_executed: Some(false),
+            ..Default::default()
+        };
+        assert!(matches!(paginate(empty(), &query), Err(ApiError::MalformedRequest(_))));
+
+        assert!(paginate(empty(), &ListQuery { sort: Some(SortKey::Votes), ..Default::default() }).unwrap().items.is_empty());
+    }
+}
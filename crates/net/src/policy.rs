@@ -0,0 +1,486 @@
+use axum::extract::{Query, Request, State};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::fmt;
+use turtle_database::basic_db::SafeDatabase;
+use turtle_service::parser::community::Community;
+use crate::community::pda_admin;
+use crate::error::{ApiError, ErrorBody};
+use crate::auth::{authenticate, AuthError, WalletIdentity, MAX_CLOCK_SKEW_SECS, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+
+// 재사용을 막기 위해 사용된 요청 서명을 보관하는 테이블 (key: 서명, value: 서명 타임스탬프)
+pub const AUTH_NONCE_TABLE: &str = "auth_nonce";
+
+// 서버 관리자 지갑 목록 환경 변수 (쉼표로 구분)
+pub const ADMIN_WALLETS_ENV: &str = "TURTLE_ADMIN_WALLETS";
+
+// 라우트별 접근 정책
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessPolicy {
+    Public,             // 인증 불필요
+    Wallet,             // 서명된 지갑이면 허용
+    CommunityAdmin,     // 기존 커뮤니티의 admin만 허용
+    CommunityUpsert,    // 커뮤니티가 없으면 등록된 PDA에 한해 생성, 있으면 admin만 수정
+    PdaAdmin,           // 서버 관리자, 또는 커뮤니티 admin(커뮤니티가 없으면 PDA를 등록한 지갑)만 허용
+    ProfileOwner,       // address 쿼리의 지갑 본인만 허용
+    ServerAdmin,        // TURTLE_ADMIN_WALLETS에 등록된 지갑만 허용
+}
+
+// 403 응답에 담기는 거부 사유
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DenyReason {
+    NotCommunityAdmin,
+    CommunityNotFound,
+    PdaNotRegistered,
+    NotPdaAdmin,
+    NotProfileOwner,
+    NotServerAdmin,
+}
+
+#[derive(Debug)]
+pub enum PolicyError {
+    Unauthorized(AuthError),
+    Forbidden(DenyReason, String),
+    MissingField(&'static str),
+    DatabaseError(String),
+}
+
+impl DenyReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DenyReason::NotCommunityAdmin => "not_community_admin",
+            DenyReason::CommunityNotFound => "community_not_found",
+            DenyReason::PdaNotRegistered => "pda_not_registered",
+            DenyReason::NotPdaAdmin => "not_pda_admin",
+            DenyReason::NotProfileOwner => "not_profile_owner",
+            DenyReason::NotServerAdmin => "not_server_admin",
+        }
+    }
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::Unauthorized(err) => write!(f, "Unauthorized: {}", err),
+            PolicyError::Forbidden(_, msg) => write!(f, "Forbidden: {}", msg),
+            PolicyError::MissingField(field) => write!(f, "{} is required", field),
+            PolicyError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+        }
+    }
+}
+
+impl StdError for PolicyError {}
+
+impl IntoResponse for PolicyError {
+    fn into_response(self) -> Response {
+        match self {
+            PolicyError::Unauthorized(err) => err.into_response(),
+            // 대상 커뮤니티가 없으면 권한 문제가 아니므로 404
+            PolicyError::Forbidden(DenyReason::CommunityNotFound, message) => ErrorBody::new("not_found", message)
+                .with_reason(DenyReason::CommunityNotFound.as_str())
+                .respond(StatusCode::NOT_FOUND),
+            PolicyError::Forbidden(reason, message) => ErrorBody::new("forbidden", message)
+                .with_reason(reason.as_str())
+                .respond(StatusCode::FORBIDDEN),
+            PolicyError::MissingField(field) => ApiError::missing_field(field).into_response(),
+            PolicyError::DatabaseError(msg) => ApiError::DatabaseError(msg).into_response(),
+        }
+    }
+}
+
+impl From<AuthError> for PolicyError {
+    fn from(err: AuthError) -> Self {
+        PolicyError::Unauthorized(err)
+    }
+}
+
+#[derive(Deserialize)]
+struct PolicyTargetQuery {
+    pda: Option<String>,
+    address: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct PolicyState<T> {
+    database: T,
+    policy: AccessPolicy,
+}
+
+fn load_community<T: SafeDatabase>(database: &T, pda: &str) -> Result<Option<Community>, PolicyError> {
+    let community_data = database.read(pda, "community")
+        .map_err(|e| PolicyError::DatabaseError(e.to_string()))?;
+
+    match community_data {
+        Some(data) => {
+            let community_str = String::from_utf8(data)
+                .map_err(|e| PolicyError::DatabaseError(format!("Invalid UTF-8: {}", e)))?;
+            let community: Community = serde_json::from_str(&community_str)
+                .map_err(|e| PolicyError::DatabaseError(format!("Invalid JSON: {}", e)))?;
+            Ok(Some(community))
+        },
+        None => Ok(None),
+    }
+}
+
+fn require_admin(community: &Community, caller: &WalletIdentity, pda: &str) -> Result<(), PolicyError> {
+    if community.admin == caller.pubkey {
+        Ok(())
+    } else {
+        Err(PolicyError::Forbidden(
+            DenyReason::NotCommunityAdmin,
+            format!("Wallet {} is not the admin of community {}", caller.pubkey, pda),
+        ))
+    }
+}
+
+fn admin_wallets() -> Vec<String> {
+    std::env::var(ADMIN_WALLETS_ENV)
+        .unwrap_or_default()
+        .split(',')
+        .map(|wallet| wallet.trim().to_string())
+        .filter(|wallet| !wallet.is_empty())
+        .collect()
+}
+
+// 호출자와 대상(커뮤니티 정책은 PDA, 프로필 정책은 주소)에 대해 정책 평가
+pub fn authorize<T: SafeDatabase>(
+    database: &T,
+    policy: AccessPolicy,
+    caller: &WalletIdentity,
+    target: Option<&str>,
+) -> Result<(), PolicyError> {
+    let community_pda = match policy {
+        AccessPolicy::Public | AccessPolicy::Wallet => return Ok(()),
+        AccessPolicy::ServerAdmin => {
+            if admin_wallets().contains(&caller.pubkey) {
+                return Ok(());
+            }
+            return Err(PolicyError::Forbidden(
+                DenyReason::NotServerAdmin,
+                format!("Wallet {} is not a server admin", caller.pubkey),
+            ));
+        },
+        AccessPolicy::ProfileOwner => {
+            let address = target
+                .filter(|address| !address.is_empty())
+                .ok_or_else(|| PolicyError::MissingField("address"))?;
+            if address == caller.pubkey {
+                return Ok(());
+            }
+            return Err(PolicyError::Forbidden(
+                DenyReason::NotProfileOwner,
+                format!("Wallet {} does not own profile {}", caller.pubkey, address),
+            ));
+        },
+        AccessPolicy::PdaAdmin if admin_wallets().contains(&caller.pubkey) => return Ok(()),
+        AccessPolicy::CommunityAdmin | AccessPolicy::CommunityUpsert | AccessPolicy::PdaAdmin => target
+            .filter(|pda| !pda.is_empty())
+            .ok_or_else(|| PolicyError::MissingField("pda"))?,
+    };
+
+    match (load_community(database, community_pda)?, policy) {
+        (Some(community), _) => require_admin(&community, caller, community_pda),
+        (None, AccessPolicy::PdaAdmin) => {
+            let registered = database.read(community_pda, "daopda")
+                .map_err(|e| PolicyError::DatabaseError(e.to_string()))?
+                .ok_or_else(|| PolicyError::Forbidden(
+                    DenyReason::PdaNotRegistered,
+                    format!("PDA {} is not registered", community_pda),
+                ))?;
+            // 관리자 없이 등록된 예전 PDA는 서버 관리자만 삭제
+            match pda_admin(registered) {
+                Some(admin) if admin == caller.pubkey => Ok(()),
+                _ => Err(PolicyError::Forbidden(
+                    DenyReason::NotPdaAdmin,
+                    format!("Wallet {} did not register PDA {}", caller.pubkey, community_pda),
+                )),
+            }
+        },
+        (None, AccessPolicy::CommunityUpsert) => {
+            let registered = database.read(community_pda, "daopda")
+                .map_err(|e| PolicyError::DatabaseError(e.to_string()))?
+                .ok_or_else(|| PolicyError::Forbidden(
+                    DenyReason::PdaNotRegistered,
+                    format!("PDA {} is not registered", community_pda),
+                ))?;
+            // PDA를 등록한 지갑만 커뮤니티 생성 (관리자 없이 등록된 예전 PDA는 허용)
+            match pda_admin(registered) {
+                Some(admin) if admin != caller.pubkey => Err(PolicyError::Forbidden(
+                    DenyReason::NotPdaAdmin,
+                    format!("Wallet {} did not register PDA {}", caller.pubkey, community_pda),
+                )),
+                _ => Ok(()),
+            }
+        },
+        (None, _) => Err(PolicyError::Forbidden(
+            DenyReason::CommunityNotFound,
+            format!("Community with PDA {} not found", community_pda),
+        )),
+    }
+}
+
+// 요청 서명을 한 번만 사용할 수 있도록 기록. 이미 사용된 서명이면 거부
+fn consume_signature<T: SafeDatabase>(database: &T, parts: &Parts) -> Result<(), PolicyError> {
+    let header = |name: &str| parts.headers.get(name).and_then(|value| value.to_str().ok()).unwrap_or_default();
+    let signature = header(SIGNATURE_HEADER);
+    let timestamp = header(TIMESTAMP_HEADER);
+
+    let fresh = database.transaction(|txn| {
+        if txn.read(signature, AUTH_NONCE_TABLE).map_err(|e| e.to_string())?.is_some() {
+            return Ok(false);
+        }
+        txn.write(signature, timestamp, AUTH_NONCE_TABLE).map_err(|e| e.to_string())?;
+        Ok(true)
+    })
+    .map_err(|e| PolicyError::DatabaseError(e.to_string()))?
+    .map_err(PolicyError::DatabaseError)?;
+
+    if fresh {
+        Ok(())
+    } else {
+        Err(PolicyError::Unauthorized(AuthError::ReplayedSignature))
+    }
+}
+
+// 허용 오차가 지나 어차피 거부될 서명 기록 삭제. 삭제한 개수 반환
+pub fn prune_nonces<T: SafeDatabase>(database: &T, now: u64) -> Result<usize, String> {
+    database.transaction(|txn| {
+        let mut pruned = 0;
+        for (signature, timestamp) in txn.read_all(AUTH_NONCE_TABLE).map_err(|e| e.to_string())? {
+            let timestamp: u64 = String::from_utf8_lossy(&timestamp).parse().unwrap_or(0);
+            if timestamp.saturating_add(MAX_CLOCK_SKEW_SECS) < now {
+                txn.delete(&String::from_utf8_lossy(&signature), AUTH_NONCE_TABLE).map_err(|e| e.to_string())?;
+                pruned += 1;
+            }
+        }
+        Ok(pruned)
+    })
+    .map_err(|e| e.to_string())?
+}
+
+pub async fn enforce_policy<T: SafeDatabase>(
+    State(state): State<PolicyState<T>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, PolicyError> {
+    if state.policy == AccessPolicy::Public {
+        return Ok(next.run(request).await);
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let caller = authenticate(&parts)?;
+
+    let target = Query::<PolicyTargetQuery>::try_from_uri(&parts.uri)
+        .ok()
+        .and_then(|Query(query)| match state.policy {
+            AccessPolicy::ProfileOwner => query.address,
+            _ => query.pda,
+        });
+    authorize(&state.database, state.policy, &caller, target.as_deref())?;
+    consume_signature(&state.database, &parts)?;
+
+    // 핸들러가 WalletIdentity 추출기로 호출자를 재사용할 수 있도록 저장
+    parts.extensions.insert(caller);
+
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+// 라우터 컴포넌트에 접근 정책 적용
+pub fn with_policy<T>(
+    (path, router): (String, Router<T>),
+    policy: AccessPolicy,
+    database: &T,
+) -> (String, Router<T>)
+where
+    T: SafeDatabase + Clone + Send + Sync + 'static,
+{
+    let state = PolicyState {
+        database: Clone::clone(database),
+        policy,
+    };
+
+    (path, router.route_layer(middleware::from_fn_with_state(state, enforce_policy::<T>)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::tests::{sign_headers, test_wallet};
+    use crate::chain::ChainConfig;
+    use crate::clock::unix_now;
+    use crate::community::save_community;
+    use crate::router::post_router_builder;
+    use axum::body::Body;
+    use axum::Extension;
+    use axum::http::Method;
+    use tempfile::tempdir;
+    use tower::ServiceExt;
+    use turtle_database::basic_db::InnerDatabase;
+    use turtle_service::test_util::community;
+
+    fn community_json(admin: &str, base_fee: u64) -> String {
+        let community = Community {
+            admin: admin.to_string(),
+            base_fee,
+            ..community()
+        };
+        serde_json::to_string(&community).unwrap()
+    }
+
+    fn community_app(db: &InnerDatabase) -> Router {
+        let (_, router) = with_policy(
+            post_router_builder("/api/dao/community".to_string(), save_community::<InnerDatabase>),
+            AccessPolicy::CommunityUpsert,
+            db,
+        );
+        router.layer(Extension(ChainConfig::default())).with_state(Clone::clone(db))
+    }
+
+    fn signed_request(seed: u8, uri: &str, body: String) -> Request {
+        let (signing_key, _) = test_wallet(seed);
+        let mut builder = Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header("content-type", "application/json");
+        for (name, value) in sign_headers(&signing_key, &Method::POST, uri) {
+            builder = builder.header(name, value);
+        }
+        builder.body(Body::from(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_community_update_requires_stored_admin() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let (_, admin) = test_wallet(1);
+        db.write("pda1", "pda1", "daopda")?;
+        db.write("pda1", &community_json(&admin, 100), "community")?;
+
+        // 다른 지갑의 수정 시도는 403
+        let response = community_app(&db)
+            .oneshot(signed_request(2, "/api/dao/community?pda=pda1", community_json(&admin, 1)))
+            .await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["reason"], "not_community_admin");
+
+        // 저장된 admin의 수정은 허용
+        let response = community_app(&db)
+            .oneshot(signed_request(1, "/api/dao/community?pda=pda1", community_json(&admin, 1)))
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_community_create_requires_registered_pda() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let (_, admin) = test_wallet(1);
+
+        let response = community_app(&db)
+            .oneshot(signed_request(1, "/api/dao/community?pda=unknown", community_json(&admin, 100)))
+            .await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(db.read("unknown", "community")?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_only_pda_registrant_creates_community() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let (_, admin) = test_wallet(1);
+        db.write("pda1", &serde_json::json!({ "address": "pda1", "admin": admin }).to_string(), "daopda")?;
+
+        let response = community_app(&db)
+            .oneshot(signed_request(2, "/api/dao/community?pda=pda1", community_json(&admin, 100)))
+            .await?;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let body: serde_json::Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await?)?;
+        assert_eq!(body["reason"], "not_pda_admin");
+        assert!(db.read("pda1", "community")?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pda_delete_requires_pda_admin() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let (_, admin) = test_wallet(1);
+        let caller = |pubkey: &str| WalletIdentity { pubkey: pubkey.to_string() };
+        db.write("pda1", &serde_json::json!({ "address": "pda1", "admin": admin }).to_string(), "daopda")?;
+        db.write("legacy", "legacy", "daopda")?;
+
+        // 커뮤니티가 삭제된 PDA는 등록한 지갑만 삭제 가능
+        authorize(&db, AccessPolicy::PdaAdmin, &caller(&admin), Some("pda1"))?;
+        let other = authorize(&db, AccessPolicy::PdaAdmin, &caller(&test_wallet(2).1), Some("pda1"));
+        assert!(matches!(other, Err(PolicyError::Forbidden(DenyReason::NotPdaAdmin, _))));
+
+        // 관리자 없이 등록된 예전 PDA는 서버 관리자만
+        let legacy = authorize(&db, AccessPolicy::PdaAdmin, &caller(&admin), Some("legacy"));
+        assert!(matches!(legacy, Err(PolicyError::Forbidden(DenyReason::NotPdaAdmin, _))));
+
+        // 커뮤니티가 남아 있으면 커뮤니티 admin 기준
+        db.write("pda1", &community_json(&test_wallet(3).1, 100), "community")?;
+        let replaced = authorize(&db, AccessPolicy::PdaAdmin, &caller(&admin), Some("pda1"));
+        assert!(matches!(replaced, Err(PolicyError::Forbidden(DenyReason::NotCommunityAdmin, _))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replayed_signature_is_rejected_until_pruned() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let (_, admin) = test_wallet(1);
+        db.write("pda1", "pda1", "daopda")?;
+        db.write("pda1", &community_json(&admin, 100), "community")?;
+
+        let request = signed_request(1, "/api/dao/community?pda=pda1", community_json(&admin, 1));
+        let mut replay = Request::builder()
+            .method(Method::POST)
+            .uri("/api/dao/community?pda=pda1")
+            .body(Body::from(community_json(&admin, 1)))?;
+        *replay.headers_mut() = request.headers().clone();
+        let response = community_app(&db).oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 같은 서명으로 다시 보낸 요청은 401
+        let response = community_app(&db).oneshot(replay).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // 허용 오차가 지난 서명 기록만 삭제
+        let now = unix_now();
+        assert_eq!(prune_nonces(&db, now)?, 0);
+        assert_eq!(prune_nonces(&db, now + MAX_CLOCK_SKEW_SECS + 1)?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_request_is_unauthorized() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/api/dao/community?pda=pda1")
+            .header("content-type", "application/json")
+            .body(Body::from(community_json("anyone", 100)))?;
+        let response = community_app(&db).oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        Ok(())
+    }
+}
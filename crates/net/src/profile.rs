@@ -1,12 +1,18 @@
-use axum::extract::{Multipart, Query, State};
-use axum::http::StatusCode;
+use axum::extract::{Multipart, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
-use std::error::Error as StdError;
-use std::fmt;
-use axum::Json;
+use crate::extract::{Json, Query};
 use serde::{Deserialize, Serialize};
 use turtle_database::basic_db::{SafeDatabase};
+use turtle_service::avatar::{avatar_hash, process_avatar, AVATAR_CONTENT_TYPE, THUMBNAIL_SIZES};
 use turtle_service::parser::profile::UserProfile;
+use turtle_service::validation::Validate;
+use crate::archive::{remove_record, DeleteQuery};
+use crate::auth::WalletIdentity;
+use crate::error::ApiError;
+use crate::clock::unix_now;
+use sol::pubkey::parse_wallet;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 
 // Query parameters struct for the get_profile_by_address endpoint
 #[derive(Deserialize)]
@@ -14,48 +20,50 @@ pub struct AddressQuery {
     address: String,
 }
 
-// Response struct for the get_profile_by_address endpoint
-
-
-#[derive(Debug)]
-pub enum ProfileError {
-    MultipartError(String),
-    DatabaseError(String),
-    SerializationError(String),
+// Query parameters struct for the get_avatar endpoint
+#[derive(Deserialize)]
+pub struct AvatarQuery {
+    address: String,
+    size: Option<u32>,
+    v: Option<String>,      // 아바타 해시. 일치하면 immutable 캐시 허용
 }
 
-// ProfileError에 Display 트레이트 구현 (Error 트레이트 구현에 필요)
-impl fmt::Display for ProfileError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ProfileError::MultipartError(msg) => write!(f, "Multipart error: {}", msg),
-            ProfileError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
-            ProfileError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
-        }
-    }
+// PATCH 요청 본문. 주소와 아바타는 수정 대상이 아님
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfilePatch {
+    user_id: Option<String>,
+    user_name: Option<String>,
+    github_account: Option<String>,
+    x_account: Option<String>,
+    tg_account: Option<String>,
+    user_bio: Option<String>,
 }
 
-// ProfileError에 std::error::Error 트레이트 구현
-impl StdError for ProfileError {}
-
-// ProfileError에 IntoResponse 트레이트 구현
-impl IntoResponse for ProfileError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            ProfileError::MultipartError(msg) => (StatusCode::BAD_REQUEST, msg),
-            ProfileError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            ProfileError::SerializationError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-        };
+// Response struct for the get_profile_by_address endpoint
+#[derive(Serialize)]
+pub struct ProfileResponse {
+    pub exists: bool,
+    pub profile: UserProfile,
+    pub avatar_url: Option<String>,
+}
 
-        // 에러 메시지와 상태 코드 반환
-        (status, error_message).into_response()
-    }
+// 아바타 바이트를 프로필 JSON에 저장하던 이전 형식의 행
+#[derive(Deserialize)]
+struct LegacyAvatarRow {
+    #[serde(default)]
+    user_avatar: Option<Vec<u8>>,
 }
 
+// 해시가 URL에 포함되므로 해시가 일치하는 요청은 1년간 캐시
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+const REVALIDATE_CACHE_CONTROL: &str = "public, no-cache";
+
 pub async fn profile_write<T: SafeDatabase>(
     State(database): State<T>,
+    caller: WalletIdentity,
     mut multipart: Multipart
-) -> Result<StatusCode, ProfileError>
+) -> Result<StatusCode, ApiError>
 {
     // 사용자 프로필 데이터 초기화
     let mut user_profile = UserProfile {
@@ -66,44 +74,44 @@ pub async fn profile_write<T: SafeDatabase>(
         x_account: String::new(),
         tg_account: String::new(),
         user_bio: String::new(),
-        user_avatar: None,
+        avatar_hash: None,
         avatar_content_type: None,
     };
+    let mut avatar_upload = None;
 
     // multipart 필드 처리
-    while let Some(field) = multipart.next_field().await.map_err(|e| ProfileError::MultipartError(e.to_string()))? {
+    while let Some(field) = multipart.next_field().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))? {
         let name = field.name().unwrap_or("").to_string();
 
         match name.as_str() {
             "user_id" => {
-                user_profile.user_id = field.text().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                user_profile.user_id = field.text().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
             },
             "user_name" => {
-                user_profile.user_name = field.text().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                user_profile.user_name = field.text().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
             },
             "user_address" => {
-                user_profile.user_address = field.text().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                user_profile.user_address = field.text().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
             },
             "github_account" => {
-                user_profile.github_account = field.text().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                user_profile.github_account = field.text().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
             },
             "x_account" => {
-                user_profile.x_account = field.text().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                user_profile.x_account = field.text().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
             },
             "tg_account" => {
-                user_profile.tg_account = field.text().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                user_profile.tg_account = field.text().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
             },
             "user_bio" => {
-                user_profile.user_bio = field.text().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                user_profile.user_bio = field.text().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
             },
             "user_avatar" => {
-                // 이미지 데이터 처리
+                // 이미지 처리는 다른 필드 검증을 통과한 뒤에
                 let content_type = field.content_type().map(|ct| ct.to_string());
-                let data = field.bytes().await.map_err(|e| ProfileError::MultipartError(e.to_string()))?;
+                let data = field.bytes().await.map_err(|e| ApiError::MalformedRequest(e.to_string()))?;
 
                 if !data.is_empty() {
-                    user_profile.user_avatar = Some(data.to_vec());
-                    user_profile.avatar_content_type = content_type;
+                    avatar_upload = Some((data, content_type));
                 }
             },
             _ => continue,
@@ -111,44 +119,132 @@ pub async fn profile_write<T: SafeDatabase>(
     }
 
     if user_profile.user_address.is_empty() {
-        return Err(ProfileError::MultipartError("User ID is required".to_string()));
+        return Err(ApiError::missing_field("user_address"));
+    }
+
+    // 주소, 계정 이름 형식, 길이 규칙 검사 (위반을 모두 모아 반환)
+    let now = unix_now();
+    user_profile.validate_fields(now)?;
+    check_wallet("user_address", &user_profile.user_address)?;
+
+    // 서명한 지갑 본인의 프로필만 저장
+    if user_profile.user_address != caller.pubkey {
+        return Err(ApiError::ForbiddenError(format!("Profile address must be the signing wallet {}", caller.pubkey)));
+    }
+
+    // 이미지 데이터 검증 후 PNG로 정규화. 디코딩과 리사이즈는 CPU 작업이라 blocking 스레드에서 실행
+    let processed_avatar = match avatar_upload {
+        Some((data, content_type)) => {
+            let avatar = tokio::task::spawn_blocking(move || process_avatar(&data, content_type.as_deref()))
+                .await
+                .map_err(|e| ApiError::UnavailableError(e.to_string()))?
+                .map_err(|e| ApiError::invalid_field("user_avatar", e))?;
+            user_profile.avatar_hash = Some(avatar.hash.clone());
+            user_profile.avatar_content_type = Some(avatar.content_type.to_string());
+            Some(avatar)
+        },
+        None => None,
+    };
+
+    // 아바타 저장 - key는 address(원본) 또는 address_size(썸네일), value는 PNG 바이트
+    if let Some(avatar) = processed_avatar {
+        let mut avatar_items = vec![(user_profile.user_address.clone(), avatar.image)];
+        avatar_items.extend(avatar.thumbnails.into_iter().map(|(size, bytes)| {
+            (format!("{}_{}", user_profile.user_address, size), bytes)
+        }));
+
+        database.batch_write(&avatar_items, "user_avatars")
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
     }
 
     let profile_json = serde_json::to_string(&user_profile)
-        .map_err(|e| ProfileError::SerializationError(e.to_string()))?;
+        .map_err(|e| ApiError::SerializationError(e.to_string()))?;
 
     database.write(&user_profile.user_address, &profile_json, "user_profiles")
-        .map_err(|e| ProfileError::DatabaseError(e.to_string()))?;
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
 
     Ok(StatusCode::OK)
 }
 
 
+// 프로필 읽기 (이전 형식 행의 아바타는 migrate_legacy_avatars로 옮김)
+fn load_profile<T: SafeDatabase>(database: &T, address: &str) -> Result<Option<UserProfile>, ApiError> {
+    let profile_data = database.read(address, "user_profiles")
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    let Some(data) = profile_data else {
+        return Ok(None);
+    };
+
+    let profile_str = String::from_utf8(data)
+        .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
+
+    let profile: UserProfile = serde_json::from_str(&profile_str)
+        .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
+
+    Ok(Some(profile))
+}
+
+// 아바타 바이트를 프로필 JSON에 저장하던 이전 형식 행을 user_avatars 테이블로 옮겨 다시 저장. 옮긴 프로필 수 반환.
+// 서버 시작 시 요청을 받기 전에 한 번 실행 (아바타를 먼저 쓰므로 중간에 실패해도 다시 실행하면 됨)
+pub fn migrate_legacy_avatars<T: SafeDatabase>(database: &T) -> Result<usize, ApiError> {
+    let mut migrated = 0;
+
+    for (key, value) in database.read_all("user_profiles").map_err(|e| ApiError::DatabaseError(e.to_string()))? {
+        let legacy: LegacyAvatarRow = serde_json::from_slice(&value)
+            .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
+        let Some(avatar) = legacy.user_avatar.filter(|avatar| !avatar.is_empty()) else {
+            continue;
+        };
+
+        let address = String::from_utf8(key)
+            .map_err(|e| ApiError::SerializationError(format!("Invalid UTF-8 in key: {}", e)))?;
+        let mut profile: UserProfile = serde_json::from_slice(&value)
+            .map_err(|e| ApiError::SerializationError(format!("Invalid JSON: {}", e)))?;
+        profile.avatar_hash = Some(avatar_hash(&avatar));
+
+        database.batch_write(&[(address.as_str(), avatar)], "user_avatars")
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+        let profile_json = serde_json::to_string(&profile)
+            .map_err(|e| ApiError::SerializationError(e.to_string()))?;
+
+        database.write(&address, &profile_json, "user_profiles")
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        migrated += 1;
+    }
+
+    Ok(migrated)
+}
+
+// 주소와 해시는 쿼리 값으로 인코딩 (이전 형식 주소에는 &, # 같은 문자가 있을 수 있음)
+fn avatar_url(profile: &UserProfile) -> Option<String> {
+    profile.avatar_hash.as_ref().map(|hash| {
+        format!(
+            "/api/profile/avatar?address={}&v={}",
+            utf8_percent_encode(&profile.user_address, NON_ALPHANUMERIC),
+            utf8_percent_encode(hash, NON_ALPHANUMERIC),
+        )
+    })
+}
+
 pub async fn get_profile_by_address<T: SafeDatabase>(
     State(database): State<T>,
     Query(query): Query<AddressQuery>,
-) -> Result<Json<UserProfile>, ProfileError> {
+) -> Result<Json<ProfileResponse>, ApiError> {
     // Validate address
     if query.address.is_empty() {
-        return Err(ProfileError::MultipartError("Address is required".to_string()));
+        return Err(ApiError::missing_field("address"));
     }
 
-    // Try to read the profile from the database
-    let profile_data = database.read(&query.address, "user_profiles")
-        .map_err(|e| ProfileError::DatabaseError(e.to_string()))?;
-
     // Check if the profile exists
-    if let Some(data) = profile_data {
-        // Parse the profile from JSON
-        let profile_str = String::from_utf8(data)
-            .map_err(|e| ProfileError::SerializationError(format!("Invalid UTF-8: {}", e)))?;
-
-        let profile: UserProfile = serde_json::from_str(&profile_str)
-            .map_err(|e| ProfileError::SerializationError(format!("Invalid JSON: {}", e)))?;
-
+    if let Some(profile) = load_profile(&database, &query.address)? {
         // Return the existing profile
-        Ok(Json(
-            profile))
+        Ok(Json(ProfileResponse {
+            exists: true,
+            avatar_url: avatar_url(&profile),
+            profile,
+        }))
     } else {
         // Create a default profile with only the address field
         let default_profile = UserProfile {
@@ -159,13 +255,182 @@ pub async fn get_profile_by_address<T: SafeDatabase>(
             x_account: String::new(),
             tg_account: String::new(),
             user_bio: String::new(),
-            user_avatar: None,
+            avatar_hash: None,
             avatar_content_type: None,
         };
 
         // Return the default profile
-        Ok(Json(default_profile))
+        Ok(Json(ProfileResponse {
+            exists: false,
+            profile: default_profile,
+            avatar_url: None,
+        }))
+    }
+}
+
+// 요청에 포함된 텍스트 필드만 수정
+pub async fn patch_profile<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<AddressQuery>,
+    Json(patch): Json<ProfilePatch>,
+) -> Result<StatusCode, ApiError> {
+    if query.address.is_empty() {
+        return Err(ApiError::missing_field("address"));
+    }
+
+    let mut profile = load_profile(&database, &query.address)?
+        .ok_or_else(|| ApiError::NotFoundError(format!("Profile {} not found", query.address)))?;
+
+    let fields = [
+        (patch.user_id, &mut profile.user_id),
+        (patch.user_name, &mut profile.user_name),
+        (patch.github_account, &mut profile.github_account),
+        (patch.x_account, &mut profile.x_account),
+        (patch.tg_account, &mut profile.tg_account),
+        (patch.user_bio, &mut profile.user_bio),
+    ];
+    for (value, field) in fields {
+        if let Some(value) = value {
+            *field = value;
+        }
+    }
+
+    let now = unix_now();
+    profile.validate_fields(now)?;
+
+    let profile_json = serde_json::to_string(&profile)
+        .map_err(|e| ApiError::SerializationError(e.to_string()))?;
+
+    database.write(&query.address, &profile_json, "user_profiles")
+        .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+
+    Ok(StatusCode::OK)
+}
+
+// 기본은 소프트 삭제(아바타는 복구를 위해 유지), hard=true면 아바타까지 영구 삭제
+pub async fn delete_profile<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<AddressQuery>,
+    Query(options): Query<DeleteQuery>,
+) -> Result<StatusCode, ApiError> {
+    if query.address.is_empty() {
+        return Err(ApiError::missing_field("address"));
+    }
+
+    let profile = load_profile(&database, &query.address)?
+        .ok_or_else(|| ApiError::NotFoundError(format!("Profile {} not found", query.address)))?;
+
+    let profile_json = serde_json::to_string(&profile)
+        .map_err(|e| ApiError::SerializationError(e.to_string()))?;
+
+    remove_record(&database, "user_profiles", &query.address, &profile_json, Some(&caller.pubkey), options.hard)
+        .map_err(ApiError::DatabaseError)?;
+
+    if options.hard {
+        let avatar_keys = std::iter::once(query.address.clone())
+            .chain(THUMBNAIL_SIZES.iter().map(|size| format!("{}_{}", query.address, size)));
+        for key in avatar_keys {
+            database.delete(&key, "user_avatars")
+                .map_err(|e| ApiError::DatabaseError(e.to_string()))?;
+        }
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 새로 쓰는 프로필 주소는 서명 가능한 지갑(곡선 위의 32바이트 공개키)이어야 함
+fn check_wallet(field: &'static str, address: &str) -> Result<(), ApiError> {
+    if address.is_empty() {
+        return Err(ApiError::missing_field(field));
     }
+    parse_wallet(address)
+        .map(|_| ())
+        .map_err(|e| ApiError::invalid_field(field, e))
+}
+
+// If-None-Match 헤더가 ETag와 일치하는지 확인 (약한 비교)
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|candidate| candidate.trim().trim_start_matches("W/"))
+        .any(|candidate| candidate == "*" || candidate == etag)
+}
+
+pub async fn get_avatar<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<AvatarQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    if query.address.is_empty() {
+        return Err(ApiError::missing_field("address"));
+    }
+
+    if let Some(size) = query.size {
+        if !THUMBNAIL_SIZES.contains(&size) {
+            return Err(ApiError::invalid_field("size", format!(
+                "Unsupported avatar size {}, expected one of {:?}", size, THUMBNAIL_SIZES
+            )));
+        }
+    }
+
+    let profile = load_profile(&database, &query.address)?
+        .ok_or_else(|| ApiError::NotFoundError(format!("Profile {} not found", query.address)))?;
+
+    let hash = profile.avatar_hash.clone()
+        .ok_or_else(|| ApiError::NotFoundError(format!("Profile {} has no avatar", query.address)))?;
+
+    let thumbnail = match query.size {
+        Some(size) => database.read(&format!("{}_{}", query.address, size), "user_avatars")
+            .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+            .map(|bytes| (bytes, AVATAR_CONTENT_TYPE.to_string(), format!("\"{}-{}\"", hash, size))),
+        None => None,
+    };
+
+    // 썸네일이 없는 이전 형식 아바타는 원본으로 응답
+    let (bytes, content_type, etag) = match thumbnail {
+        Some(thumbnail) => thumbnail,
+        None => {
+            let bytes = database.read(&query.address, "user_avatars")
+                .map_err(|e| ApiError::DatabaseError(e.to_string()))?
+                .ok_or_else(|| ApiError::NotFoundError(format!("Profile {} has no avatar", query.address)))?;
+            let content_type = profile.avatar_content_type.clone()
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+            (bytes, content_type, format!("\"{}\"", hash))
+        },
+    };
+
+    let cache_control = if query.v.as_deref() == Some(hash.as_str()) {
+        IMMUTABLE_CACHE_CONTROL
+    } else {
+        REVALIDATE_CACHE_CONTROL
+    };
+
+    let etag_value = HeaderValue::from_str(&etag)
+        .map_err(|e| ApiError::SerializationError(e.to_string()))?;
+
+    if etag_matches(&headers, &etag) {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(header::ETAG, etag_value), (header::CACHE_CONTROL, HeaderValue::from_static(cache_control))],
+        ).into_response());
+    }
+
+    let content_type_value = HeaderValue::from_str(&content_type)
+        .map_err(|e| ApiError::SerializationError(e.to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type_value),
+            (header::ETAG, etag_value),
+            (header::CACHE_CONTROL, HeaderValue::from_static(cache_control)),
+        ],
+        bytes,
+    ).into_response())
 }
 
 
@@ -179,10 +444,26 @@ mod tests {
     use axum::http::Request;
     use tempfile::tempdir;
     use turtle_database::basic_db::InnerDatabase;
+    use turtle_service::avatar::AvatarError;
     use turtle_service::parser::profile::UserProfile;
+    use crate::error::FieldError;
+
 
-    use axum::extract::Query;
+    // 테스트 지갑 주소 (곡선 위의 키: [1;32] 바이트, 시드 [2;32]의 ed25519 공개키)
+    const USER_ADDRESS: &str = "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi";
+    const AVATAR_OWNER: &str = "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu";
 
+    fn signer(address: &str) -> WalletIdentity {
+        WalletIdentity { pubkey: address.to_string() }
+    }
+
+    // 테스트용 JPEG 이미지 생성 함수
+    fn test_jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image = image::DynamicImage::ImageRgb8(image::ImageBuffer::from_pixel(width, height, image::Rgb([10u8, 20, 30])));
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut buffer, image::ImageOutputFormat::Jpeg(90)).unwrap();
+        buffer.into_inner()
+    }
 
     // 테스트용 멀티파트 바디 생성 함수
     fn create_multipart_body(fields: Vec<(&str, &str)>, file_field: Option<(&str, &str, &[u8])>) -> (String, Vec<u8>) {
@@ -228,7 +509,7 @@ mod tests {
         let fields = vec![
             ("user_id", "test_user"),
             ("user_name", "Test User"),
-            ("user_address", "0xabcdef123456789"),
+            ("user_address", USER_ADDRESS),
             ("github_account", "testuser"),
             ("x_account", "@testuser"),
             ("tg_account", "@test_user"),
@@ -236,7 +517,7 @@ mod tests {
         ];
 
         // 테스트용 아바타 이미지 데이터
-        let avatar_data = [1, 2, 3, 4, 5]; // 간단한 바이너리 데이터
+        let avatar_data = test_jpeg(32, 32);
         let file_field = Some(("user_avatar", "avatar.jpg", &avatar_data[..]));
 
         let (content_type, body_bytes) = create_multipart_body(fields, file_field);
@@ -249,13 +530,13 @@ mod tests {
         let multipart = Multipart::from_request(request, &()).await?;
 
         // profile_write 함수 호출
-        let result = profile_write(State(db.clone()), multipart).await?;
+        let result = profile_write(State(Clone::clone(&db)), signer(USER_ADDRESS), multipart).await?;
 
         // 결과 확인 - 성공해야 함
         assert_eq!(result, StatusCode::OK);
 
         // 데이터베이스에서 저장된 프로필 읽기
-        let profile_data = db.read("0xabcdef123456789", "user_profiles")?;
+        let profile_data = db.read(USER_ADDRESS, "user_profiles")?;
         assert!(profile_data.is_some(), "Profile data not found in database");
 
         // 저장된 데이터 검증
@@ -265,20 +546,62 @@ mod tests {
 
             assert_eq!(profile.user_id, "test_user");
             assert_eq!(profile.user_name, "Test User");
-            assert_eq!(profile.user_address, "0xabcdef123456789");
+            assert_eq!(profile.user_address, USER_ADDRESS);
             assert_eq!(profile.github_account, "testuser");
             assert_eq!(profile.x_account, "@testuser");
             assert_eq!(profile.tg_account, "@test_user");
             assert_eq!(profile.user_bio, "This is a test bio");
 
-            // 아바타 검증
-            assert!(profile.user_avatar.is_some());
-            if let Some(avatar) = &profile.user_avatar {
-                assert_eq!(avatar, &avatar_data);
-            }
-            assert_eq!(profile.avatar_content_type, Some("image/jpeg".to_string()));
+            // 아바타 검증 - 프로필에는 해시만, 바이트는 user_avatars 테이블에 PNG로 저장
+            assert!(profile.avatar_hash.is_some());
+            assert_eq!(profile.avatar_content_type, Some("image/png".to_string()));
+            assert!(!profile_str.contains("user_avatar"));
         }
 
+        let avatar = db.read(USER_ADDRESS, "user_avatars")?.expect("avatar not stored");
+        assert_eq!(image::guess_format(&avatar)?, image::ImageFormat::Png);
+        assert!(db.read(&format!("{}_64", USER_ADDRESS), "user_avatars")?.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_write_rejects_malformed_avatar() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let fields = vec![("user_address", USER_ADDRESS)];
+        let avatar_data = [1, 2, 3, 4, 5];
+        let (content_type, body_bytes) = create_multipart_body(fields, Some(("user_avatar", "avatar.jpg", &avatar_data[..])));
+
+        let request = Request::builder()
+            .header("content-type", content_type)
+            .body(Body::from(body_bytes))?;
+        let multipart = Multipart::from_request(request, &()).await?;
+
+        let result = profile_write(State(Clone::clone(&db)), signer(USER_ADDRESS), multipart).await;
+        assert!(matches!(result, Err(ApiError::InvalidFields(errors)) if errors == vec![FieldError::new("user_avatar", AvatarError::UnknownFormat)]));
+        assert!(db.read(USER_ADDRESS, "user_profiles")?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_profile_write_requires_signing_owner() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        // 다른 지갑의 프로필과 아바타는 덮어쓸 수 없음
+        let avatar_data = test_jpeg(16, 16);
+        let (content_type, body_bytes) = create_multipart_body(vec![("user_address", USER_ADDRESS)], Some(("user_avatar", "avatar.jpg", &avatar_data[..])));
+        let request = Request::builder()
+            .header("content-type", content_type)
+            .body(Body::from(body_bytes))?;
+        let result = profile_write(State(Clone::clone(&db)), signer(AVATAR_OWNER), Multipart::from_request(request, &()).await?).await;
+        assert!(matches!(result, Err(ApiError::ForbiddenError(_))));
+        assert!(db.read(USER_ADDRESS, "user_profiles")?.is_none());
+        assert!(db.read(USER_ADDRESS, "user_avatars")?.is_none());
+
         Ok(())
     }
 
@@ -309,18 +632,46 @@ mod tests {
         let multipart = Multipart::from_request(request, &()).await?;
 
         // profile_write 함수 호출 - 여기서는 에러를 기대하므로 ? 연산자를 사용하지 않음
-        let result = profile_write(State(db.clone()), multipart).await;
+        let result = profile_write(State(Clone::clone(&db)), signer(USER_ADDRESS), multipart).await;
 
-        // 결과 확인 - 에러가 발생해야 함
+        // 결과 확인 - 누락된 필드 이름으로 에러가 발생해야 함
         match result {
-            Err(ProfileError::MultipartError(msg)) => {
-                assert_eq!(msg, "User ID is required");
+            Err(ApiError::MissingFields(fields)) => {
+                assert_eq!(fields, vec!["user_address"]);
                 Ok(())
             },
-            _ => Err("Expected MultipartError with 'User ID is required' message".into()),
+            _ => Err("Expected MissingFields for user_address".into()),
         }
     }
 
+    #[tokio::test]
+    async fn test_profile_write_reports_invalid_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let fields = vec![
+            ("user_id", "Not Valid"),
+            ("user_address", "0xabcdef123456789"),
+            ("x_account", "@this_handle_is_too_long"),
+        ];
+        // 필드 규칙을 어긴 요청은 아바타를 처리하기 전에 거부
+        let (content_type, body_bytes) = create_multipart_body(fields, Some(("user_avatar", "avatar.jpg", &[1, 2, 3][..])));
+        let request = Request::builder()
+            .header("content-type", content_type)
+            .body(Body::from(body_bytes))?;
+        let multipart = Multipart::from_request(request, &()).await?;
+
+        let result = profile_write(State(Clone::clone(&db)), signer(USER_ADDRESS), multipart).await;
+        let Err(ApiError::InvalidFields(errors)) = result else {
+            return Err("Expected InvalidFields".into());
+        };
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["user_address", "user_id", "x_account"]);
+        assert!(db.read("0xabcdef123456789", "user_profiles")?.is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_profile_write_empty_fields() -> Result<(), Box<dyn std::error::Error>> {
         // 임시 디렉토리 생성
@@ -334,7 +685,7 @@ mod tests {
         let fields = vec![
             ("user_id", ""),
             ("user_name", ""),
-            ("user_address", "0xabcdef123456789"), // 이 필드만 값이 있음
+            ("user_address", USER_ADDRESS), // 이 필드만 값이 있음
             ("github_account", ""),
             ("x_account", ""),
             ("tg_account", ""),
@@ -351,13 +702,13 @@ mod tests {
         let multipart = Multipart::from_request(request, &()).await?;
 
         // profile_write 함수 호출
-        let result = profile_write(State(db.clone()), multipart).await?;
+        let result = profile_write(State(Clone::clone(&db)), signer(USER_ADDRESS), multipart).await?;
 
         // 결과 확인 - 성공해야 함 (user_address가 있으므로)
         assert_eq!(result, StatusCode::OK);
 
         // 데이터베이스에서 저장된 프로필 읽기
-        let profile_data = db.read("0xabcdef123456789", "user_profiles")?;
+        let profile_data = db.read(USER_ADDRESS, "user_profiles")?;
         assert!(profile_data.is_some(), "Profile data not found in database");
 
         // 저장된 데이터 검증
@@ -367,12 +718,12 @@ mod tests {
 
             assert_eq!(profile.user_id, "");
             assert_eq!(profile.user_name, "");
-            assert_eq!(profile.user_address, "0xabcdef123456789");
+            assert_eq!(profile.user_address, USER_ADDRESS);
             assert_eq!(profile.github_account, "");
             assert_eq!(profile.x_account, "");
             assert_eq!(profile.tg_account, "");
             assert_eq!(profile.user_bio, "");
-            assert!(profile.user_avatar.is_none());
+            assert!(profile.avatar_hash.is_none());
         }
 
         Ok(())
@@ -388,26 +739,25 @@ mod tests {
         let db = InnerDatabase::new(&db_path)?;
 
         // Create a test profile
-        let test_address = "0xabcdef123456789";
         let test_profile = UserProfile {
             user_id: "test_user".to_string(),
             user_name: "Test User".to_string(),
-            user_address: test_address.to_string(),
+            user_address: USER_ADDRESS.to_string(),
             github_account: "testuser".to_string(),
             x_account: "@testuser".to_string(),
             tg_account: "@test_user".to_string(),
             user_bio: "This is a test bio".to_string(),
-            user_avatar: None,
+            avatar_hash: None,
             avatar_content_type: None,
         };
 
         // Save the profile to the database
         let profile_json = serde_json::to_string(&test_profile)?;
-        db.write(test_address, &profile_json, "user_profiles")?;
+        db.write(USER_ADDRESS, &profile_json, "user_profiles")?;
 
         // Create query parameters
         let query = AddressQuery {
-            address: test_address.to_string(),
+            address: USER_ADDRESS.to_string(),
         };
 
         // Call get_profile_by_address function
@@ -418,7 +768,7 @@ mod tests {
         assert!(response.exists);
         assert_eq!(response.profile.user_id, "test_user");
         assert_eq!(response.profile.user_name, "Test User");
-        assert_eq!(response.profile.user_address, test_address);
+        assert_eq!(response.profile.user_address, USER_ADDRESS);
         assert_eq!(response.profile.github_account, "testuser");
         assert_eq!(response.profile.x_account, "@testuser");
         assert_eq!(response.profile.tg_account, "@test_user");
@@ -455,7 +805,8 @@ mod tests {
         assert!(response.profile.x_account.is_empty());
         assert!(response.profile.tg_account.is_empty());
         assert!(response.profile.user_bio.is_empty());
-        assert!(response.profile.user_avatar.is_none());
+        assert!(response.profile.avatar_hash.is_none());
+        assert!(response.avatar_url.is_none());
 
         Ok(())
     }
@@ -479,12 +830,149 @@ mod tests {
 
         // Check that it returns an error
         match result {
-            Err(ProfileError::MultipartError(msg)) => {
-                assert_eq!(msg, "Address is required");
+            Err(ApiError::MissingFields(fields)) => {
+                assert_eq!(fields, vec!["address"]);
                 Ok(())
             },
-            _ => Err("Expected MultipartError with 'Address is required' message".into()),
+            _ => Err("Expected MissingFields for address".into()),
+        }
+    }
+
+    fn avatar_query(address: &str, size: Option<u32>, v: Option<&str>) -> AvatarQuery {
+        AvatarQuery {
+            address: address.to_string(),
+            size,
+            v: v.map(|v| v.to_string()),
         }
     }
 
+    #[tokio::test]
+    async fn test_get_avatar_with_etag_and_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let avatar_data = test_jpeg(16, 16);
+        let fields = vec![("user_address", AVATAR_OWNER)];
+        let (content_type, body_bytes) = create_multipart_body(fields, Some(("user_avatar", "avatar.jpg", &avatar_data[..])));
+        let request = Request::builder()
+            .header("content-type", content_type)
+            .body(Body::from(body_bytes))?;
+        profile_write(State(Clone::clone(&db)), signer(AVATAR_OWNER), Multipart::from_request(request, &()).await?).await?;
+
+        let profile = get_profile_by_address(State(Clone::clone(&db)), Query(AddressQuery { address: AVATAR_OWNER.to_string() })).await?.0;
+        let hash = profile.profile.avatar_hash.clone().expect("hash missing");
+        assert_eq!(profile.avatar_url, Some(format!("/api/profile/avatar?address={}&v={}", AVATAR_OWNER, hash)));
+
+        // 버전이 일치하는 썸네일 요청
+        let response = get_avatar(State(Clone::clone(&db)), Query(avatar_query(AVATAR_OWNER, Some(64), Some(&hash))), HeaderMap::new()).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], IMMUTABLE_CACHE_CONTROL);
+        let etag = response.headers()[header::ETAG].clone();
+        assert_eq!(etag.to_str()?, format!("\"{}-64\"", hash));
+
+        // If-None-Match 일치 시 304
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag);
+        let response = get_avatar(State(Clone::clone(&db)), Query(avatar_query(AVATAR_OWNER, Some(64), None)), headers).await?;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::CACHE_CONTROL], REVALIDATE_CACHE_CONTROL);
+
+        // 지원하지 않는 크기와 없는 아바타
+        let result = get_avatar(State(Clone::clone(&db)), Query(avatar_query(AVATAR_OWNER, Some(13), None)), HeaderMap::new()).await;
+        assert!(matches!(result, Err(ApiError::InvalidFields(_))));
+        let result = get_avatar(State(Clone::clone(&db)), Query(avatar_query("0xnobody", None, None)), HeaderMap::new()).await;
+        assert!(matches!(result, Err(ApiError::NotFoundError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_legacy_avatar_row_is_migrated() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        // 아바타 바이트가 JSON에 들어 있던 이전 형식 행
+        let legacy_row = serde_json::json!({
+            "user_id": "", "user_name": "", "user_address": "0xlegacy", "github_account": "",
+            "x_account": "", "tg_account": "", "user_bio": "",
+            "user_avatar": [1, 2, 3], "avatar_content_type": "image/jpeg"
+        });
+        db.write("0xlegacy", &legacy_row.to_string(), "user_profiles")?;
+
+        // 조회는 저장된 행을 바꾸지 않음
+        let response = get_profile_by_address(State(Clone::clone(&db)), Query(AddressQuery { address: "0xlegacy".to_string() })).await?.0;
+        assert_eq!(response.profile.avatar_hash, None);
+        assert!(db.read("0xlegacy", "user_avatars")?.is_none());
+
+        assert_eq!(migrate_legacy_avatars(&db)?, 1);
+        assert_eq!(migrate_legacy_avatars(&db)?, 0);
+
+        let response = get_profile_by_address(State(Clone::clone(&db)), Query(AddressQuery { address: "0xlegacy".to_string() })).await?.0;
+        assert_eq!(response.profile.avatar_hash, Some(avatar_hash(&[1, 2, 3])));
+        assert_eq!(db.read("0xlegacy", "user_avatars")?, Some(vec![1, 2, 3]));
+
+        let stored = String::from_utf8(db.read("0xlegacy", "user_profiles")?.unwrap())?;
+        assert!(!stored.contains("user_avatar\""));
+
+        // 썸네일이 없으면 원본으로 응답
+        let response = get_avatar(State(Clone::clone(&db)), Query(avatar_query("0xlegacy", Some(64), None)), HeaderMap::new()).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/jpeg");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_avatar_url_encodes_query_values() {
+        let profile = UserProfile {
+            user_id: "1".to_string(),
+            user_name: "turtle".to_string(),
+            user_address: "0xa&b=c d#".to_string(),
+            github_account: String::new(),
+            x_account: String::new(),
+            tg_account: String::new(),
+            user_bio: String::new(),
+            avatar_hash: Some("ab+/".to_string()),
+            avatar_content_type: None,
+        };
+        assert_eq!(avatar_url(&profile).unwrap(), "/api/profile/avatar?address=0xa%26b%3Dc%20d%23&v=ab%2B%2F");
+    }
+
+    #[tokio::test]
+    async fn test_patch_and_soft_delete_profile() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let (content_type, body_bytes) = create_multipart_body(
+            vec![("user_name", "Before"), ("user_address", USER_ADDRESS), ("user_bio", "bio")],
+            Some(("user_avatar", "image/jpeg", &test_jpeg(32, 32))),
+        );
+        let request = Request::builder()
+            .header("content-type", content_type)
+            .body(Body::from(body_bytes))?;
+        profile_write(State(Clone::clone(&db)), signer(USER_ADDRESS), Multipart::from_request(request, &()).await?).await?;
+
+        let address = || Query(AddressQuery { address: USER_ADDRESS.to_string() });
+
+        // 요청에 포함된 필드만 바뀜
+        let patch: ProfilePatch = serde_json::from_str(r#"{"user_name":"After"}"#)?;
+        patch_profile(State(Clone::clone(&db)), address(), Json(patch)).await?;
+        let profile = load_profile(&db, USER_ADDRESS)?.unwrap();
+        assert_eq!(profile.user_name, "After");
+        assert_eq!(profile.user_bio, "bio");
+
+        // 주소는 수정할 수 없음
+        assert!(serde_json::from_str::<ProfilePatch>(r#"{"user_address":"other"}"#).is_err());
+
+        let caller = WalletIdentity { pubkey: USER_ADDRESS.to_string() };
+        let status = delete_profile(State(Clone::clone(&db)), caller, address(), Query(DeleteQuery { hard: false })).await?;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(load_profile(&db, USER_ADDRESS)?.is_none());
+        assert_eq!(db.read_all(crate::archive::DELETED_RECORDS_TABLE)?.len(), 1);
+        // 소프트 삭제는 아바타를 남김
+        assert!(db.read(USER_ADDRESS, "user_avatars")?.is_some());
+
+        Ok(())
+    }
 }
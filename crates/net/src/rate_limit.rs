@@ -0,0 +1,390 @@
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::Request;
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use crate::auth::authenticate;
+use crate::clock::unix_now_ms;
+use crate::error::ApiError;
+
+// 쉼표로 구분한 리버스 프록시 IP. 이 주소에서 온 요청만 X-Forwarded-For를 신뢰
+pub const TRUSTED_PROXIES_ENV: &str = "TURTLE_TRUSTED_PROXIES";
+
+// 메모리에 유지하는 기본 최대 버킷 수
+pub const MAX_TRACKED_BUCKETS: usize = 100_000;
+
+// 가득 찬(기본 상태와 같은) 버킷을 정리하는 주기
+const EVICTION_INTERVAL_MS: u64 = 60_000;
+
+// 라우트 그룹별 토큰 버킷 설정
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    pub capacity: u32,                  // 버킷 최대 토큰 수(버스트 허용량)
+    pub refill_per_sec: f64,            // 초당 충전 토큰 수
+    pub trusted_proxies: Vec<IpAddr>,   // 이 주소에서 온 요청만 X-Forwarded-For로 클라이언트 IP 판단
+    pub max_buckets: usize,             // 메모리에 유지하는 최대 버킷 수
+}
+
+impl RateLimitConfig {
+    pub fn per_minute(requests: u32) -> Self {
+        Self {
+            capacity: requests,
+            refill_per_sec: requests as f64 / 60.0,
+            trusted_proxies: Vec::new(),
+            max_buckets: MAX_TRACKED_BUCKETS,
+        }
+    }
+
+    pub fn with_trusted_proxies(mut self, proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = proxies;
+        self
+    }
+}
+
+// TRUSTED_PROXIES_ENV의 프록시 주소 (해석할 수 없는 항목은 무시)
+pub fn trusted_proxies() -> Vec<IpAddr> {
+    std::env::var(TRUSTED_PROXIES_ENV)
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at_ms: u64,
+}
+
+#[derive(Default)]
+struct Buckets {
+    entries: HashMap<String, Bucket>,
+    evicted_at_ms: u64,
+}
+
+// 메모리 토큰 버킷 리미터. 버킷은 프로세스 안에서만 유지하고 주기적으로 정리
+pub struct RateLimiter {
+    group: String,
+    config: RateLimitConfig,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(group: &str, config: RateLimitConfig) -> Self {
+        Self {
+            group: group.to_string(),
+            config,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    // 지금까지 충전되었으면 가득 찼을 버킷은 새 버킷과 같으므로 제거
+    fn evict_full(&self, buckets: &mut Buckets, now: u64) {
+        let capacity = self.config.capacity as f64;
+        let refill_per_sec = self.config.refill_per_sec;
+        buckets.entries.retain(|_, bucket| {
+            let elapsed_secs = now.saturating_sub(bucket.updated_at_ms) as f64 / 1000.0;
+            bucket.tokens + elapsed_secs * refill_per_sec < capacity
+        });
+        buckets.evicted_at_ms = now;
+    }
+
+    // 상한에 도달하면 가득 찬 버킷을 정리하고, 그래도 많으면 가장 오래 쓰지 않은 버킷부터 제거.
+    // 상한의 1/10만큼 여유를 남겨 새 키마다 전체를 훑지 않도록 함
+    fn make_room(&self, buckets: &mut Buckets, now: u64) {
+        self.evict_full(buckets, now);
+
+        let max_buckets = self.config.max_buckets.max(1);
+        let target = max_buckets - (max_buckets / 10).max(1);
+        let excess = buckets.entries.len().saturating_sub(target);
+        if excess == 0 {
+            return;
+        }
+
+        let mut by_age: Vec<(u64, String)> = buckets.entries.iter()
+            .map(|(key, bucket)| (bucket.updated_at_ms, key.clone()))
+            .collect();
+        by_age.select_nth_unstable(excess - 1);
+        for (_, key) in by_age.into_iter().take(excess) {
+            buckets.entries.remove(&key);
+        }
+    }
+
+    // 토큰 1개를 소비. 부족하면 다시 시도할 수 있을 때까지의 초를 반환
+    pub fn check_at(&self, key: &str, now: u64) -> Result<(), u64> {
+        let mut buckets = self.buckets.lock().expect("Failed to lock rate limit buckets");
+
+        if now.saturating_sub(buckets.evicted_at_ms) >= EVICTION_INTERVAL_MS {
+            self.evict_full(&mut buckets, now);
+        }
+
+        let key = format!("{}:{}", self.group, key);
+        if buckets.entries.len() >= self.config.max_buckets && !buckets.entries.contains_key(&key) {
+            self.make_room(&mut buckets, now);
+        }
+
+        let capacity = self.config.capacity as f64;
+        let bucket = buckets.entries
+            .entry(key)
+            .or_insert(Bucket { tokens: capacity, updated_at_ms: now });
+
+        let elapsed_secs = now.saturating_sub(bucket.updated_at_ms) as f64 / 1000.0;
+        bucket.tokens = (bucket.tokens + elapsed_secs * self.config.refill_per_sec).min(capacity);
+        bucket.updated_at_ms = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err((missing / self.config.refill_per_sec).ceil().max(1.0) as u64)
+        }
+    }
+
+    pub fn check(&self, key: &str) -> Result<(), u64> {
+        self.check_at(key, unix_now_ms())
+    }
+
+    // 연결 주소가 신뢰하는 프록시일 때만 X-Forwarded-For를 오른쪽부터 읽어 프록시가 아닌 첫 주소를 사용
+    fn client_ip(&self, request: &Request<Body>) -> String {
+        let Some(peer) = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip())
+        else {
+            return "unknown".to_string();
+        };
+        if !self.config.trusted_proxies.contains(&peer) {
+            return peer.to_string();
+        }
+
+        let forwarded: Vec<&str> = request
+            .headers()
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect();
+        for hop in forwarded.into_iter().rev() {
+            match hop.trim().parse::<IpAddr>() {
+                Ok(ip) if self.config.trusted_proxies.contains(&ip) => continue,
+                Ok(ip) => return ip.to_string(),
+                Err(_) => break,
+            }
+        }
+
+        peer.to_string()
+    }
+
+    // IP 버킷과 (서명된 요청이면) 지갑 버킷을 모두 확인
+    fn check_request(&self, request: Request<Body>) -> (Request<Body>, Result<(), u64>) {
+        let ip_result = self.check(&format!("ip:{}", self.client_ip(&request)));
+        if ip_result.is_err() {
+            return (request, ip_result);
+        }
+
+        let (parts, body) = request.into_parts();
+        let wallet_result = match authenticate(&parts) {
+            Ok(identity) => self.check(&format!("wallet:{}", identity.pubkey)),
+            Err(_) => Ok(()),
+        };
+
+        (Request::from_parts(parts, body), wallet_result)
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self {
+            limiter: Arc::new(limiter),
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: Arc::clone(&self.limiter),
+        }
+    }
+}
+
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S: Clone> Clone for RateLimitService<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            limiter: Arc::clone(&self.limiter),
+        }
+    }
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let (request, result) = self.limiter.check_request(request);
+
+        if let Err(retry_after) = result {
+            return Box::pin(async move { Ok(ApiError::RateLimited(retry_after).into_response()) });
+        }
+
+        // poll_ready를 거친 서비스를 사용하고 복제본을 남겨 둠
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move { inner.call(request).await })
+    }
+}
+
+// 라우터 컴포넌트에 라우트 그룹 리미터 적용
+pub fn with_rate_limit<S>(
+    (path, router): (String, Router<S>),
+    layer: &RateLimitLayer,
+) -> (String, Router<S>)
+where
+    S: Clone + Send + Sync + 'static,
+{
+    (path, router.route_layer(layer.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::get_router_builder;
+    use axum::http::{header, StatusCode};
+    use tower::ServiceExt;
+
+    async fn ok_handler() -> &'static str {
+        "ok"
+    }
+
+    fn config(capacity: u32, refill_per_sec: f64) -> RateLimitConfig {
+        RateLimitConfig {
+            capacity,
+            refill_per_sec,
+            trusted_proxies: Vec::new(),
+            max_buckets: MAX_TRACKED_BUCKETS,
+        }
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let limiter = RateLimiter::new("test", config(2, 1.0));
+
+        assert!(limiter.check_at("ip:1.1.1.1", 0).is_ok());
+        assert!(limiter.check_at("ip:1.1.1.1", 0).is_ok());
+        assert_eq!(limiter.check_at("ip:1.1.1.1", 0), Err(1));
+        // 다른 키는 별도 버킷
+        assert!(limiter.check_at("ip:2.2.2.2", 0).is_ok());
+        // 1초 후 토큰 1개 충전
+        assert!(limiter.check_at("ip:1.1.1.1", 1000).is_ok());
+    }
+
+    #[test]
+    fn test_full_buckets_are_evicted_periodically() {
+        let limiter = RateLimiter::new("test", config(1, 0.01));
+        assert!(limiter.check_at("ip:1.1.1.1", 0).is_ok());
+        assert!(limiter.check_at("ip:2.2.2.2", 0).is_ok());
+
+        // 정리 주기가 지나도 아직 충전 중인 버킷은 유지되어 제한이 풀리지 않음
+        assert!(limiter.check_at("ip:1.1.1.1", EVICTION_INTERVAL_MS).is_err());
+        assert_eq!(limiter.buckets.lock().unwrap().entries.len(), 2);
+
+        // 다시 가득 찬 버킷은 다음 정리 때 제거
+        assert!(limiter.check_at("ip:3.3.3.3", 3 * EVICTION_INTERVAL_MS).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_bucket_count_is_capped_by_evicting_oldest() {
+        let limiter = RateLimiter::new("test", RateLimitConfig { max_buckets: 20, ..config(1, 0.01) });
+        for now in 0..20 {
+            assert!(limiter.check_at(&format!("ip:10.0.0.{}", now), now).is_ok());
+        }
+
+        // 아직 충전 중인 버킷만 있어도 상한에서 가장 오래된 버킷부터 비우고 여유를 남김
+        assert!(limiter.check_at("ip:10.0.1.1", 30).is_ok());
+        {
+            let buckets = limiter.buckets.lock().unwrap();
+            assert_eq!(buckets.entries.len(), 19);
+            assert!(!buckets.entries.contains_key("test:ip:10.0.0.0"));
+            assert!(!buckets.entries.contains_key("test:ip:10.0.0.1"));
+            assert!(buckets.entries.contains_key("test:ip:10.0.0.2"));
+        }
+
+        // 여유가 생긴 뒤에는 새 키가 와도 다시 비우지 않음
+        assert!(limiter.check_at("ip:10.0.1.2", 31).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().entries.len(), 20);
+    }
+
+    #[test]
+    fn test_forwarded_for_is_trusted_only_from_proxies() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let limiter = RateLimiter::new("test", config(1, 1.0).with_trusted_proxies(vec![proxy]));
+        let request = |peer: &str, forwarded: &str| {
+            let mut request = Request::builder()
+                .header("x-forwarded-for", forwarded)
+                .body(Body::empty())
+                .unwrap();
+            request.extensions_mut().insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 443)));
+            request
+        };
+
+        // 직접 연결한 클라이언트는 헤더로 IP를 바꿀 수 없음
+        assert_eq!(limiter.client_ip(&request("203.0.113.9", "198.51.100.1")), "203.0.113.9");
+        // 프록시를 거친 요청은 클라이언트가 넣은 앞쪽 값 대신 프록시가 붙인 주소를 사용
+        assert_eq!(limiter.client_ip(&request("10.0.0.1", "198.51.100.1, 203.0.113.9")), "203.0.113.9");
+        assert_eq!(limiter.client_ip(&request("10.0.0.1", "203.0.113.9, 10.0.0.1")), "203.0.113.9");
+        assert_eq!(limiter.client_ip(&request("10.0.0.1", "not-an-ip")), "10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_layer_returns_429_with_retry_after() -> Result<(), Box<dyn std::error::Error>> {
+        let layer = RateLimitLayer::new(RateLimiter::new("read", RateLimitConfig::per_minute(1)));
+        let (_, router) = with_rate_limit(get_router_builder("/limited".to_string(), ok_handler), &layer);
+        let app: Router = router.with_state(());
+
+        let request = || Request::builder().uri("/limited").body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(request()).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(request()).await?;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "60");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+        let body: serde_json::Value = serde_json::from_slice(&body)?;
+        assert_eq!(body["error"], "rate_limited");
+
+        Ok(())
+    }
+}
@@ -1,9 +1,8 @@
 use axum::{
-    routing::get, routing::post,
+    routing::get, routing::post, routing::put, routing::patch, routing::delete,
     Router, handler::Handler
 };
-
-use std::fmt::Debug;
+use crate::error::route_not_found;
 
 
 
@@ -17,20 +16,21 @@ where
         app = app.merge(router);
     }
 
-    app.with_state(state)
+    // 등록되지 않은 경로도 JSON 에러 본문으로 응답
+    app.fallback(route_not_found).with_state(state)
 }
 
 
 
 pub fn get_router_builder<T, S>(
     path: String,
-    handler: impl Handler<T, S>  + Clone + Send + 'static
+    handler: impl Handler<T, S>
 ) -> (String, Router<S>)
 where
     T: 'static,
     S: Clone + Send + Sync + 'static,
 {
-    let mut app = Router::<S>::new();
+    let app = Router::<S>::new();
     let new_path = path.clone();
     (path, app.route(&new_path, get(handler)))
 }
@@ -38,35 +38,75 @@ where
 
 pub fn post_router_builder<T, S>(
     path: String,
-    handler: impl Handler<T, S>  + Clone + Send + 'static
+    handler: impl Handler<T, S>
 ) -> (String, Router<S>)
 where
     T: 'static,
     S: Clone + Send + Sync + 'static,
 {
-    let mut app = Router::<S>::new();
+    let app = Router::<S>::new();
     let new_path = path.clone();
     (path, app.route(&new_path, post(handler)))
 }
 
+
+pub fn put_router_builder<T, S>(
+    path: String,
+    handler: impl Handler<T, S>
+) -> (String, Router<S>)
+where
+    T: 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    let app = Router::<S>::new();
+    let new_path = path.clone();
+    (path, app.route(&new_path, put(handler)))
+}
+
+
+pub fn patch_router_builder<T, S>(
+    path: String,
+    handler: impl Handler<T, S>
+) -> (String, Router<S>)
+where
+    T: 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    let app = Router::<S>::new();
+    let new_path = path.clone();
+    (path, app.route(&new_path, patch(handler)))
+}
+
+
+pub fn delete_router_builder<T, S>(
+    path: String,
+    handler: impl Handler<T, S>
+) -> (String, Router<S>)
+where
+    T: 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    let app = Router::<S>::new();
+    let new_path = path.clone();
+    (path, app.route(&new_path, delete(handler)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use axum::{
         body::Body,
         http::{Request, StatusCode},
-        response::IntoResponse,
-        routing::get,
         Router,
     };
     use tower::ServiceExt;
-    use std::sync::Arc;
 
     #[axum::debug_handler]
     async fn hello_handler() -> String {
         "Hello, World!".to_string()
     }
 
+    #[allow(dead_code)]
     async fn echo_handler(body: String) -> String {
         body
     }
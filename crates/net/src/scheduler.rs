@@ -0,0 +1,300 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use crate::extract::{Json, Query};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use turtle_database::basic_db::SafeDatabase;
+use turtle_database::jobs::{acquire_job, complete_job, list_jobs, register_job, trigger_job, JobDefinition, JobError, JobRecord};
+use turtle_service::deposit::unlock_expired_deposits;
+use turtle_service::lifecycle::close_expired_proposals;
+use turtle_service::round::settle_expired_rounds;
+use crate::clock::unix_now;
+use crate::error::ApiError;
+use crate::policy::prune_nonces;
+
+// 스케줄러가 작업 실행 시점을 확인하는 주기(초)
+pub const SCHEDULER_TICK_SECS: u64 = 15;
+
+// 작업 본문. 성공하면 결과 요약, 실패하면 에러 메시지 반환
+pub type JobTask<T> = fn(&T, u64) -> Result<String, String>;
+
+#[derive(Deserialize)]
+pub struct JobQuery {
+    name: String,
+}
+
+#[derive(Serialize)]
+pub struct JobsResponse {
+    jobs: Vec<JobRecord>,
+}
+
+// 한 주기에 실행한 작업 결과
+#[derive(Debug, PartialEq, Eq)]
+pub struct JobRun {
+    pub name: String,
+    pub result: Result<String, String>,
+}
+
+// 잠금 소유자로 쓰는 인스턴스 ID. 컨테이너에서는 PID가 모두 1이므로 호스트 이름, PID와 함께 인스턴스마다 다른 난수를 붙임
+fn instance_owner() -> String {
+    let host = std::env::var("HOSTNAME").ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "turtle".to_string());
+    let nonce = RandomState::new().build_hasher().finish();
+    format!("{}-{}-{:016x}", host, std::process::id(), nonce)
+}
+
+// DB에 저장된 작업 정의와 잠금으로 실행을 조율하므로 여러 인스턴스가 같은 DB를 써도 작업은 한 곳에서만 실행됨
+#[derive(Clone)]
+pub struct Scheduler<T> {
+    database: T,
+    owner: String,
+    jobs: Vec<(JobDefinition, JobTask<T>)>,
+}
+
+impl<T> Scheduler<T>
+where
+    T: SafeDatabase + Clone + Send + Sync + 'static,
+{
+    pub fn new(database: T) -> Self {
+        Self {
+            database,
+            owner: instance_owner(),
+            jobs: Vec::new(),
+        }
+    }
+
+    pub fn with_owner(mut self, owner: &str) -> Self {
+        self.owner = owner.to_string();
+        self
+    }
+
+    pub fn with_job(mut self, definition: JobDefinition, task: JobTask<T>) -> Self {
+        self.jobs.push((definition, task));
+        self
+    }
+
+    pub fn register(&self, now: u64) -> Result<(), JobError> {
+        for (definition, _) in &self.jobs {
+            register_job(&self.database, definition.clone(), now)?;
+        }
+        Ok(())
+    }
+
+    // 실행 시점이 된 작업 중 잠금을 얻은 작업만 실행하고 결과 기록
+    pub fn run_due(&self, now: u64) -> Vec<JobRun> {
+        let mut runs = Vec::new();
+
+        for (definition, task) in &self.jobs {
+            match acquire_job(&self.database, &definition.name, &self.owner, now) {
+                Ok(true) => {},
+                Ok(false) => continue,
+                Err(e) => {
+                    eprintln!("Failed to acquire job {}: {}", definition.name, e);
+                    continue;
+                },
+            }
+
+            let result = task(&self.database, now);
+            if let Err(e) = complete_job(&self.database, &definition.name, &self.owner, now, result.clone()) {
+                eprintln!("Failed to record job {}: {}", definition.name, e);
+            }
+
+            runs.push(JobRun {
+                name: definition.name.clone(),
+                result,
+            });
+        }
+
+        runs
+    }
+
+    // 백그라운드에서 주기적으로 run_due 실행
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.register(unix_now()) {
+                eprintln!("Failed to register scheduler jobs: {}", e);
+                return;
+            }
+
+            let mut interval = tokio::time::interval(Duration::from_secs(SCHEDULER_TICK_SECS));
+            loop {
+                interval.tick().await;
+
+                // 작업은 DB를 동기로 사용하므로 블로킹 스레드에서 실행
+                let scheduler = self.clone();
+                if let Err(e) = tokio::task::spawn_blocking(move || scheduler.run_due(unix_now())).await {
+                    eprintln!("Scheduler tick panicked: {}", e);
+                }
+            }
+        })
+    }
+}
+
+fn job(name: &str, interval_secs: u64) -> JobDefinition {
+    JobDefinition {
+        name: name.to_string(),
+        interval_secs,
+        max_retries: 3,
+        backoff_secs: 10,
+        lease_secs: 120,
+    }
+}
+
+fn close_proposals_task<T: SafeDatabase>(database: &T, now: u64) -> Result<String, String> {
+    let closed = close_expired_proposals(database, now)
+        .map_err(|e| e.to_string())?;
+    Ok(format!("closed {} proposals", closed.len()))
+}
+
+fn unlock_deposits_task<T: SafeDatabase>(database: &T, now: u64) -> Result<String, String> {
+    let unlocked = unlock_expired_deposits(database, now)
+        .map_err(|e| e.to_string())?;
+    Ok(format!("unlocked {} deposits", unlocked.len()))
+}
+
+fn settle_rounds_task<T: SafeDatabase>(database: &T, now: u64) -> Result<String, String> {
+    let settled = settle_expired_rounds(database, now)
+        .map_err(|e| e.to_string())?;
+    Ok(format!("settled {} rounds", settled.len()))
+}
+
+fn prune_nonces_task<T: SafeDatabase>(database: &T, now: u64) -> Result<String, String> {
+    let pruned = prune_nonces(database, now)?;
+    Ok(format!("pruned {} nonces", pruned))
+}
+
+// 서버 기본 작업 목록
+pub fn default_scheduler<T>(database: T) -> Scheduler<T>
+where
+    T: SafeDatabase + Clone + Send + Sync + 'static,
+{
+    Scheduler::new(database)
+        .with_job(job("close_proposals", 60), close_proposals_task::<T>)
+        .with_job(job("unlock_deposits", 300), unlock_deposits_task::<T>)
+        .with_job(job("settle_rounds", 60), settle_rounds_task::<T>)
+        .with_job(job("prune_nonces", 600), prune_nonces_task::<T>)
+}
+
+// 등록된 작업과 실행 상태 조회 (서버 관리자 전용)
+pub async fn get_jobs<T: SafeDatabase>(
+    State(database): State<T>,
+) -> Result<Json<JobsResponse>, ApiError> {
+    let jobs = list_jobs(&database)?;
+    Ok(Json(JobsResponse { jobs }))
+}
+
+// 작업을 다음 스케줄러 주기에 실행하도록 예약 (서버 관리자 전용)
+pub async fn trigger_job_now<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<JobQuery>,
+) -> Result<(StatusCode, Json<JobRecord>), ApiError> {
+    if query.name.is_empty() {
+        return Err(ApiError::missing_field("name"));
+    }
+
+    let record = trigger_job(&database, &query.name, unix_now())?;
+    Ok((StatusCode::ACCEPTED, Json(record)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use turtle_database::basic_db::InnerDatabase;
+
+    fn counting_task(database: &InnerDatabase, _now: u64) -> Result<String, String> {
+        let count = database.read("runs", "test_counter").map_err(|e| e.to_string())?
+            .map(|data| String::from_utf8_lossy(&data).parse::<u64>().unwrap_or(0))
+            .unwrap_or(0);
+        database.write("runs", &(count + 1).to_string(), "test_counter").map_err(|e| e.to_string())?;
+        Ok(format!("run {}", count + 1))
+    }
+
+    fn failing_task(_database: &InnerDatabase, _now: u64) -> Result<String, String> {
+        Err("unavailable".to_string())
+    }
+
+    #[test]
+    fn test_run_due_runs_each_job_once_per_interval() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let scheduler = Scheduler::new(Clone::clone(&db))
+            .with_owner("a")
+            .with_job(job("count", 60), counting_task)
+            .with_job(job("fail", 60), failing_task);
+        let other = Scheduler::new(Clone::clone(&db))
+            .with_owner("b")
+            .with_job(job("count", 60), counting_task);
+        let now = unix_now();
+        scheduler.register(now)?;
+
+        let runs = scheduler.run_due(now);
+        assert_eq!(runs, vec![
+            JobRun { name: "count".to_string(), result: Ok("run 1".to_string()) },
+            JobRun { name: "fail".to_string(), result: Err("unavailable".to_string()) },
+        ]);
+
+        // 이미 실행한 작업은 다른 인스턴스도 다음 주기 전까지 실행하지 않음
+        assert!(other.run_due(now + 1).is_empty());
+
+        // 실패한 작업은 백오프 후 재시도
+        let runs = scheduler.run_due(now + 10);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].name, "fail");
+
+        let jobs = list_jobs(&db)?;
+        assert_eq!(jobs[1].state.failures, 2);
+        assert_eq!(jobs[1].state.last_error.as_deref(), Some("unavailable"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_owners_do_not_share_a_lease() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        // 같은 호스트, 같은 PID에서 만든 스케줄러도 서로 다른 소유자로 잠금을 잡음
+        let first = Scheduler::new(Clone::clone(&db)).with_job(job("count", 60), counting_task);
+        let second = Scheduler::new(Clone::clone(&db)).with_job(job("count", 60), counting_task);
+        assert_ne!(first.owner, second.owner);
+
+        let now = unix_now();
+        first.register(now)?;
+        assert!(acquire_job(&db, "count", &first.owner, now)?);
+        assert!(!acquire_job(&db, "count", &second.owner, now)?);
+        assert!(second.run_due(now).is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_trigger_job_makes_it_due() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let scheduler = Scheduler::new(Clone::clone(&db)).with_job(job("count", 3600), counting_task);
+        let now = unix_now();
+        scheduler.register(now)?;
+        assert_eq!(scheduler.run_due(now).len(), 1);
+        assert!(scheduler.run_due(now).is_empty());
+
+        let (status, _) = trigger_job_now(State(Clone::clone(&db)), Query(JobQuery { name: "count".to_string() })).await?;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(scheduler.run_due(unix_now()).len(), 1);
+
+        let missing = trigger_job_now(State(Clone::clone(&db)), Query(JobQuery { name: "missing".to_string() })).await;
+        assert!(matches!(missing, Err(ApiError::NotFoundError(_))));
+
+        let Json(response) = get_jobs(State(db)).await?;
+        assert_eq!(response.jobs[0].state.last_result.as_deref(), Some("run 2"));
+
+        Ok(())
+    }
+}
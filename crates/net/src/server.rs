@@ -1,14 +1,34 @@
-use axum::{http, Router};
+use axum::{http, Extension, Router};
 use crate::router::*;
 use crate::profile::*;
 use crate::community::*;
+use crate::vote::*;
+use crate::claim::get_claims;
+use crate::delegation::*;
+use crate::scheduler::{default_scheduler, get_jobs, trigger_job_now};
+use crate::policy::{with_policy, AccessPolicy};
+use crate::chain::ChainConfig;
+use crate::rate_limit::{trusted_proxies, with_rate_limit, RateLimitConfig, RateLimitLayer, RateLimiter};
+use std::net::SocketAddr;
 use turtle_database::basic_db::{SafeDatabase, InnerDatabase};
+use turtle_service::parser::community::{Content, Depositor, Proposal};
 use tower_http::cors::{Any, CorsLayer};
 
-pub async fn build_server() {
-    let shared_state = InnerDatabase::new(".").unwrap();
-    let shared_state2 = Clone::clone(&shared_state);
-    let components = collect_components();
+pub async fn build_server() -> Result<(), Box<dyn std::error::Error>> {
+    // 프로그램 ID와 Anchor IDL은 시작 시 한 번만 읽고, 형식이 틀리면 서버를 띄우지 않음
+    let chain = ChainConfig::from_env()?;
+    let shared_state = InnerDatabase::new(".")?;
+
+    // 프로필 JSON에 아바타 바이트가 남은 이전 형식 행은 요청을 받기 전에 옮김
+    let migrated = migrate_legacy_avatars(&shared_state)?;
+    if migrated > 0 {
+        eprintln!("Migrated {} legacy profile avatars", migrated);
+    }
+
+    let components = collect_components(&shared_state);
+
+    // 만료 제안 마감, 예치 잠금 해제 등 주기 작업
+    default_scheduler(Clone::clone(&shared_state)).spawn();
 
 
     let cors = CorsLayer::new()
@@ -17,6 +37,7 @@ pub async fn build_server() {
             http::Method::GET,
             http::Method::POST,
             http::Method::PUT,
+            http::Method::PATCH,
             http::Method::DELETE,
             http::Method::OPTIONS
         ])
@@ -27,59 +48,328 @@ pub async fn build_server() {
 
 
     // Use just one type parameter
-    let mut app = main_router(components, shared_state);
+    let app = main_router(components, shared_state);
+
+    let app = app.layer(Extension(chain)).layer(cors);
 
-    let app = app.layer(cors);
 
 
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:443").await?;
+    // IP 기반 요청 제한을 위해 클라이언트 주소를 함께 전달
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:443").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    Ok(())
 }
 
 
 
-fn collect_components() ->  Vec<(String,Router<InnerDatabase>)> {
-    let router_profile_post = post_router_builder("/api/profile".to_string(),profile_write::<InnerDatabase>);
+fn collect_components(database: &InnerDatabase) ->  Vec<(String,Router<InnerDatabase>)> {
+    // 프로필 등록은 서명한 지갑 본인 주소로만
+    let router_profile_post = with_policy(
+        post_router_builder("/api/profile".to_string(), profile_write::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
     let router_profile_get = get_router_builder("/api/profile".to_string(),get_profile_by_address::<InnerDatabase>);
-    // DAO PDA 관련 라우터
-    let router_pda_post = post_router_builder("/api/dao/pda".to_string(), save_pda::<InnerDatabase>);
+    let router_avatar_get = get_router_builder("/api/profile/avatar".to_string(), get_avatar::<InnerDatabase>);
+    // 프로필 수정/삭제는 본인 지갑만 허용
+    let router_profile_patch = with_policy(
+        patch_router_builder("/api/profile".to_string(), patch_profile::<InnerDatabase>),
+        AccessPolicy::ProfileOwner,
+        database,
+    );
+    let router_profile_delete = with_policy(
+        delete_router_builder("/api/profile".to_string(), delete_profile::<InnerDatabase>),
+        AccessPolicy::ProfileOwner,
+        database,
+    );
+    // DAO PDA 관련 라우터 (등록한 지갑이 PDA 관리자가 됨)
+    let router_pda_post = with_policy(
+        post_router_builder("/api/dao/pda".to_string(), save_pda::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
     let router_pda_get = get_router_builder("/api/dao/pdas".to_string(), get_all_pdas::<InnerDatabase>);
+    let router_pda_delete = with_policy(
+        delete_router_builder("/api/dao/pda".to_string(), delete_pda::<InnerDatabase>),
+        AccessPolicy::PdaAdmin,
+        database,
+    );
 
     // DAO Community 관련 라우터
-    let router_community_post = post_router_builder("/api/dao/community".to_string(), save_community::<InnerDatabase>);
+    // 커뮤니티 생성은 등록된 PDA만, 수정은 저장된 admin만 허용
+    let router_community_post = with_policy(
+        post_router_builder("/api/dao/community".to_string(), save_community::<InnerDatabase>),
+        AccessPolicy::CommunityUpsert,
+        database,
+    );
     let router_community_get_all = get_router_builder("/api/dao/communities".to_string(), get_all_communities::<InnerDatabase>);
     let router_community_get = get_router_builder("/api/dao/community".to_string(), get_community_by_pda::<InnerDatabase>);
+    let router_community_status_get = get_router_builder("/api/dao/community/status".to_string(), get_community_status::<InnerDatabase>);
 
-    // DAO Content 관련 라우터
-    let router_content_post = post_router_builder("/api/dao/content".to_string(), save_content::<InnerDatabase>);
+    // DAO Content 관련 라우터 (작성은 서명한 작성자 본인, 수정/삭제는 커뮤니티 admin만 허용)
+    let router_content_post = with_policy(
+        post_router_builder("/api/dao/content".to_string(), save_content::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
     let router_content_get = get_router_builder("/api/dao/contents".to_string(), get_contents_by_pda::<InnerDatabase>);
+    let router_content_get_one = get_router_builder("/api/dao/content".to_string(), get_child::<InnerDatabase, Content>);
+    let router_content_put = with_policy(
+        put_router_builder("/api/dao/content".to_string(), update_child::<InnerDatabase, Content>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+    let router_content_patch = with_policy(
+        patch_router_builder("/api/dao/content".to_string(), patch_child::<InnerDatabase, Content>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+    let router_content_delete = with_policy(
+        delete_router_builder("/api/dao/content".to_string(), delete_child::<InnerDatabase, Content>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+
+    // 콘텐츠 투표 (서명된 지갑당 1표)
+    let router_content_vote_post = with_policy(
+        post_router_builder("/api/dao/content/vote".to_string(), upvote_content::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
+    let router_content_vote_delete = with_policy(
+        delete_router_builder("/api/dao/content/vote".to_string(), unvote_content::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
+    let router_content_vote_get = get_router_builder("/api/dao/content/vote".to_string(), get_content_vote::<InnerDatabase>);
 
     // DAO Depositor 관련 라우터
-    let router_depositor_post = post_router_builder("/api/dao/depositor".to_string(), save_depositor::<InnerDatabase>);
+    // 예치는 서명한 지갑 본인 명의로, 온체인 예치 계정으로 확인한 만큼만
+    let router_depositor_post = with_policy(
+        post_router_builder("/api/dao/depositor".to_string(), save_depositor::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
     let router_depositor_get = get_router_builder("/api/dao/depositors".to_string(), get_depositors_by_pda::<InnerDatabase>);
+    let router_depositor_get_one = get_router_builder("/api/dao/depositor".to_string(), get_child::<InnerDatabase, Depositor>);
+    let router_depositor_history_get = get_router_builder("/api/dao/depositor/history".to_string(), get_depositor_history::<InnerDatabase>);
+    // 인출은 서명한 지갑 본인의 포지션에서만
+    let router_depositor_withdraw = with_policy(
+        post_router_builder("/api/dao/depositor/withdraw".to_string(), withdraw_depositor::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
+    let router_depositor_put = with_policy(
+        put_router_builder("/api/dao/depositor".to_string(), update_child::<InnerDatabase, Depositor>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+    let router_depositor_patch = with_policy(
+        patch_router_builder("/api/dao/depositor".to_string(), patch_child::<InnerDatabase, Depositor>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+    let router_depositor_delete = with_policy(
+        delete_router_builder("/api/dao/depositor".to_string(), delete_child::<InnerDatabase, Depositor>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
 
     // DAO Proposal 관련 라우터
-    let router_proposal_post = post_router_builder("/api/dao/proposal".to_string(), save_proposal::<InnerDatabase>);
+    // 제안 생성은 커뮤니티에 투표 파워가 있는 서명된 지갑만
+    let router_proposal_post = with_policy(
+        post_router_builder("/api/dao/proposal".to_string(), save_proposal::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
     let router_proposal_get = get_router_builder("/api/dao/proposals".to_string(), get_proposals_by_pda::<InnerDatabase>);
+    let router_proposal_get_one = get_router_builder("/api/dao/proposal".to_string(), get_child::<InnerDatabase, Proposal>);
+    // 가결 시 커뮤니티 변화 미리보기 (저장하지 않으므로 조회 그룹으로 제한)
+    let router_proposal_preview = post_router_builder("/api/dao/proposal/preview".to_string(), preview_proposal::<InnerDatabase>);
+    let router_proposal_put = with_policy(
+        put_router_builder("/api/dao/proposal".to_string(), update_child::<InnerDatabase, Proposal>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+    let router_proposal_patch = with_policy(
+        patch_router_builder("/api/dao/proposal".to_string(), patch_child::<InnerDatabase, Proposal>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+    let router_proposal_delete = with_policy(
+        delete_router_builder("/api/dao/proposal".to_string(), delete_child::<InnerDatabase, Proposal>),
+        AccessPolicy::CommunityAdmin,
+        database,
+    );
+
+    // 서명된 제안 투표 (요청 본문의 서명으로 인증)
+    let router_proposal_vote_post = post_router_builder("/api/dao/proposal/vote".to_string(), vote_proposal::<InnerDatabase>);
+    let router_proposal_votes_get = get_router_builder("/api/dao/proposal/votes".to_string(), get_proposal_votes::<InnerDatabase>);
+    // 제안 생성 시점의 투표 파워 스냅샷 (투표 가능 지갑)
+    let router_proposal_snapshot_get = get_router_builder("/api/dao/proposal/snapshot".to_string(), get_proposal_snapshot::<InnerDatabase>);
+
+    // 투표권 위임 (설정/철회는 서명한 지갑 본인만)
+    let router_delegation_post = with_policy(
+        post_router_builder("/api/dao/delegation".to_string(), save_delegation::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
+    let router_delegation_delete = with_policy(
+        delete_router_builder("/api/dao/delegation".to_string(), delete_delegation::<InnerDatabase>),
+        AccessPolicy::Wallet,
+        database,
+    );
+    let router_delegates_get = get_router_builder("/api/dao/delegation/delegates".to_string(), get_delegates::<InnerDatabase>);
+    let router_delegators_get = get_router_builder("/api/dao/delegation/delegators".to_string(), get_delegators::<InnerDatabase>);
+
+    // 라운드 보상 청구 금액과 머클 증명
+    let router_claims_get = get_router_builder("/api/dao/claims".to_string(), get_claims::<InnerDatabase>);
 
-    vec![
-        // 프로필 라우터
+    // 스케줄러 작업 조회/수동 실행 (서버 관리자 전용)
+    let router_jobs_get = with_policy(
+        get_router_builder("/api/admin/jobs".to_string(), get_jobs::<InnerDatabase>),
+        AccessPolicy::ServerAdmin,
+        database,
+    );
+    let router_jobs_trigger = with_policy(
+        post_router_builder("/api/admin/jobs/trigger".to_string(), trigger_job_now::<InnerDatabase>),
+        AccessPolicy::ServerAdmin,
+        database,
+    );
+
+    // 라우트 그룹별 요청 제한 (메모리 버킷, 설정된 프록시에서 온 요청만 X-Forwarded-For 사용)
+    let proxies = trusted_proxies();
+    let limit = |group: &str, requests: u32| RateLimitLayer::new(RateLimiter::new(
+        group,
+        RateLimitConfig::per_minute(requests).with_trusted_proxies(proxies.clone()),
+    ));
+    let read_limit = limit("read", 300);
+    let profile_write_limit = limit("profile_write", 10);
+    let dao_write_limit = limit("dao_write", 30);
+
+    let read_components = vec![
         router_profile_get,
+        router_avatar_get,
+        router_pda_get,
+        router_community_get_all,
+        router_community_get,
+        router_community_status_get,
+        router_content_get,
+        router_content_get_one,
+        router_content_vote_get,
+        router_depositor_get,
+        router_depositor_get_one,
+        router_depositor_history_get,
+        router_proposal_get,
+        router_proposal_get_one,
+        router_proposal_preview,
+        router_proposal_votes_get,
+        router_proposal_snapshot_get,
+        router_delegates_get,
+        router_delegators_get,
+        router_claims_get,
+        router_jobs_get,
+    ];
+
+    let profile_write_components = vec![
         router_profile_post,
+        router_profile_patch,
+        router_profile_delete,
+    ];
 
-        // DAO 라우터
+    let dao_write_components = vec![
         router_pda_post,
-        router_pda_get,
+        router_pda_delete,
         router_community_post,
-        router_community_get_all,
-        router_community_get,
         router_content_post,
-        router_content_get,
+        router_content_put,
+        router_content_patch,
+        router_content_delete,
+        router_content_vote_post,
+        router_content_vote_delete,
         router_depositor_post,
-        router_depositor_get,
+        router_depositor_put,
+        router_depositor_patch,
+        router_depositor_delete,
+        router_depositor_withdraw,
         router_proposal_post,
-        router_proposal_get
-    ]
+        router_proposal_put,
+        router_proposal_patch,
+        router_proposal_delete,
+        router_proposal_vote_post,
+        router_delegation_post,
+        router_delegation_delete,
+        router_jobs_trigger,
+    ];
+
+    read_components.into_iter().map(|component| with_rate_limit(component, &read_limit))
+        .chain(profile_write_components.into_iter().map(|component| with_rate_limit(component, &profile_write_limit)))
+        .chain(dao_write_components.into_iter().map(|component| with_rate_limit(component, &dao_write_limit)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tempfile::tempdir;
+    use tower::ServiceExt;
 
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn test_components_share_paths_across_methods() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+        let app = main_router(collect_components(&db), Clone::clone(&db));
+
+        // 같은 경로의 GET은 공개, POST와 DELETE는 서명 필요
+        let request = Request::builder()
+            .method(http::Method::POST)
+            .uri("/api/dao/content?pda=pda1")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({
+                "author": bs58::encode([1u8; 32]).into_string(),
+                "content_hash": "h",
+                "content_uri": "ipfs://u",
+                "timestamp": 0,
+                "votes": 0,
+            }).to_string()))?;
+        let response = app.clone().oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method(http::Method::DELETE)
+            .uri("/api/dao/content?pda=pda1&id=1")
+            .body(Body::empty())?;
+        let response = app.clone().oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .uri("/api/dao/content?pda=pda1&id=1")
+            .body(Body::empty())?;
+        let response = app.clone().oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // 필수 쿼리가 비어 있으면 필드 정보와 함께 400
+        let request = Request::builder()
+            .uri("/api/dao/community?pda=")
+            .body(Body::empty())?;
+        let response = app.clone().oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await?)?;
+        assert_eq!(body["error"], "missing_field");
+        assert_eq!(body["details"][0]["field"], "pda");
+
+        // 등록되지 않은 경로도 JSON 404
+        let request = Request::builder()
+            .uri("/api/unknown")
+            .body(Body::empty())?;
+        let response = app.oneshot(request).await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: serde_json::Value = serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await?)?;
+        assert_eq!(body["error"], "not_found");
+
+        Ok(())
+    }
+}
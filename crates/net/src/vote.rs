@@ -0,0 +1,636 @@
+use axum::extract::State;
+use crate::extract::{Json, Query};
+use serde::{Deserialize, Serialize};
+use turtle_database::basic_db::SafeDatabase;
+use turtle_service::parser::community::{Content, Depositor, Proposal, ProposalKind};
+use turtle_service::voting_power::voting_power as depositor_power;
+use turtle_service::delegation::effective_power_in;
+use turtle_service::snapshot::{load_snapshot, read_snapshot, VotingPowerSnapshot};
+use std::collections::{BTreeSet, HashMap};
+use crate::auth::{verify_wallet_signature, WalletIdentity};
+use crate::community::{database_error, decode, encode, load_community};
+use crate::error::ApiError;
+use crate::delegation::delegation_error;
+use crate::clock::unix_now;
+
+// 콘텐츠 투표 기록 테이블 (key: pda_id:voter)
+pub const CONTENT_VOTE_TABLE: &str = "content_vote";
+
+// 서명된 제안 투표 기록 테이블 (key: pda_id:voter)
+pub const PROPOSAL_VOTE_TABLE: &str = "proposal_vote";
+
+#[derive(Deserialize)]
+pub struct ContentVoteQuery {
+    pda: String,
+    id: u64,
+}
+
+#[derive(Deserialize)]
+pub struct ContentVoteLookupQuery {
+    pda: String,
+    id: u64,
+    voter: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ContentVote {
+    pub voter: String,
+    pub weight: u64,            // 취소 시 그대로 차감하기 위해 투표 시점의 가중치를 저장
+    pub voted_at: u64,
+}
+
+#[derive(Serialize)]
+pub struct ContentVoteResponse {
+    votes: u64,                 // 변경 후 콘텐츠 득표 수
+}
+
+#[derive(Serialize)]
+pub struct ContentVoteStatusResponse {
+    voted: bool,
+    vote: Option<ContentVote>,
+}
+
+// 지갑이 해당 DAO에 가진 voting_power 합계 (커뮤니티 투표 파워 모델로 다시 계산)
+pub(crate) fn voting_power<T: SafeDatabase>(database: &T, pda: &str, pubkey: &str) -> Result<u64, ApiError> {
+    let community = load_community(database, pda)?;
+    let now = unix_now();
+
+    let prefix = format!("{}_", pda);
+    let depositor_entries = database.read_all("depositor")
+        .map_err(database_error)?;
+
+    let mut power: u64 = 0;
+    for (key_bytes, value_bytes) in depositor_entries {
+        if !key_bytes.starts_with(prefix.as_bytes()) {
+            continue;
+        }
+
+        let depositor: Depositor = decode(value_bytes)?;
+        if depositor.pubkey == pubkey {
+            power = power.saturating_add(depositor_power(&community.voting_power, &depositor, now));
+        }
+    }
+
+    Ok(power)
+}
+
+fn vote_key(content_key: &str, voter: &str) -> String {
+    format!("{}:{}", content_key, voter)
+}
+
+// 콘텐츠 추천. 지갑당 한 번만 가능하고 커뮤니티 설정에 따라 voting_power로 가중
+pub async fn upvote_content<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<ContentVoteQuery>,
+) -> Result<Json<ContentVoteResponse>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let community = load_community(&database, &query.pda)?;
+    let weight = if community.weighted_content_votes {
+        let power = voting_power(&database, &query.pda, &caller.pubkey)?;
+        if power == 0 {
+            return Err(ApiError::ValidationError(format!(
+                "Wallet {} has no voting power in community {}", caller.pubkey, query.pda
+            )));
+        }
+        power
+    } else {
+        1
+    };
+
+    let vote = ContentVote {
+        voter: caller.pubkey.clone(),
+        weight,
+        voted_at: unix_now(),
+    };
+
+    let content_key = format!("{}_{}", query.pda, query.id);
+    let vote_key = vote_key(&content_key, &caller.pubkey);
+
+    // 중복 확인과 득표 수 증가를 하나의 트랜잭션으로 처리
+    let votes = database.transaction(|txn| {
+        let mut content: Content = decode(txn.read(&content_key, "content").map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("content {} not found", content_key)))?)?;
+
+        if txn.read(&vote_key, CONTENT_VOTE_TABLE).map_err(database_error)?.is_some() {
+            return Err(ApiError::ConflictError(format!(
+                "Wallet {} already voted for content {}", caller.pubkey, content_key
+            )));
+        }
+
+        content.votes = content.votes.saturating_add(weight);
+        txn.write(&content_key, &encode(&content)?, "content").map_err(database_error)?;
+        txn.write(&vote_key, &encode(&vote)?, CONTENT_VOTE_TABLE).map_err(database_error)?;
+
+        Ok(content.votes)
+    }).map_err(database_error)??;
+
+    Ok(Json(ContentVoteResponse { votes }))
+}
+
+// 추천 취소. 투표 시점의 가중치만큼 차감
+pub async fn unvote_content<T: SafeDatabase>(
+    State(database): State<T>,
+    caller: WalletIdentity,
+    Query(query): Query<ContentVoteQuery>,
+) -> Result<Json<ContentVoteResponse>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let content_key = format!("{}_{}", query.pda, query.id);
+    let vote_key = vote_key(&content_key, &caller.pubkey);
+
+    let votes = database.transaction(|txn| {
+        let mut content: Content = decode(txn.read(&content_key, "content").map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("content {} not found", content_key)))?)?;
+
+        let vote: ContentVote = decode(txn.read(&vote_key, CONTENT_VOTE_TABLE).map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!(
+                "Wallet {} has not voted for content {}", caller.pubkey, content_key
+            )))?)?;
+
+        content.votes = content.votes.saturating_sub(vote.weight);
+        txn.write(&content_key, &encode(&content)?, "content").map_err(database_error)?;
+        txn.delete(&vote_key, CONTENT_VOTE_TABLE).map_err(database_error)?;
+
+        Ok(content.votes)
+    }).map_err(database_error)??;
+
+    Ok(Json(ContentVoteResponse { votes }))
+}
+
+// 지갑의 투표 여부 조회
+pub async fn get_content_vote<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<ContentVoteLookupQuery>,
+) -> Result<Json<ContentVoteStatusResponse>, ApiError> {
+    ApiError::require(&[("pda", query.pda.as_str()), ("voter", query.voter.as_str())])?;
+
+    let content_key = format!("{}_{}", query.pda, query.id);
+    let vote = database.read(&vote_key(&content_key, &query.voter), CONTENT_VOTE_TABLE)
+        .map_err(database_error)?
+        .map(decode::<ContentVote>)
+        .transpose()?;
+
+    Ok(Json(ContentVoteStatusResponse {
+        voted: vote.is_some(),
+        vote,
+    }))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteChoice {
+    Yes,
+    No,
+}
+
+impl VoteChoice {
+    fn as_str(&self) -> &'static str {
+        match self {
+            VoteChoice::Yes => "yes",
+            VoteChoice::No => "no",
+        }
+    }
+}
+
+// 서명 메시지에 들어가는 제안 내용 (유형=값)
+fn kind_label(kind: &ProposalKind) -> String {
+    match kind {
+        ProposalKind::TimeLimit(secs) => format!("time_limit={}", secs),
+        ProposalKind::BaseFee(fee) => format!("base_fee={}", fee),
+        ProposalKind::AiModeration(enabled) => format!("ai_moderation={}", enabled),
+        ProposalKind::DepositShare(share) => format!("deposit_share={}", share),
+        ProposalKind::AdminTransfer(admin) => format!("admin_transfer={}", admin),
+        ProposalKind::TreasurySpend { recipient, amount } => format!("treasury_spend={}/{}", recipient, amount),
+        ProposalKind::Governance(rules) => format!(
+            "governance={}/{}/{}/{}",
+            rules.quorum_percent, rules.approval_percent, rules.min_voting_period_secs, rules.max_active_proposals,
+        ),
+        ProposalKind::Unknown { proposal_type, new_value } => format!("unknown={}/{}", proposal_type, new_value),
+    }
+}
+
+// 투표자가 서명해야 하는 메시지 (제안 키, 제안 내용, 선택, 가중치). 서명 후 내용이 바뀐 제안에는 쓸 수 없음
+pub fn proposal_vote_message(proposal_key: &str, kind: &ProposalKind, choice: VoteChoice, weight: u64) -> String {
+    format!("turtle:vote:{}:{}:{}:{}", proposal_key, kind_label(kind), choice.as_str(), weight)
+}
+
+#[derive(Deserialize)]
+pub struct ProposalVoteQuery {
+    pda: String,
+    id: u64,
+}
+
+#[derive(Deserialize)]
+pub struct ProposalVoteRequest {
+    voter: String,              // base58 지갑 공개키
+    choice: VoteChoice,
+    weight: u64,                // voting_power 이하
+    signature: String,          // proposal_vote_message에 대한 base58 ed25519 서명
+}
+
+// 누구나 다시 검증하고 집계할 수 있도록 원본 서명과 메시지를 함께 저장
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SignedProposalVote {
+    pub voter: String,
+    pub choice: VoteChoice,
+    pub weight: u64,
+    pub message: String,
+    pub signature: String,
+    pub voted_at: u64,
+    #[serde(default)]
+    pub delegators: Vec<String>,    // weight에 위임 파워가 포함된 위임자
+}
+
+#[derive(Serialize)]
+pub struct ProposalTallyResponse {
+    yes_votes: u64,
+    no_votes: u64,
+}
+
+#[derive(Serialize)]
+pub struct ProposalVotesResponse {
+    votes: Vec<SignedProposalVote>,
+}
+
+// 서명된 제안 투표. 서명, 투표 기간, 예치자 voting_power를 확인한 뒤 집계에 반영
+pub async fn vote_proposal<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<ProposalVoteQuery>,
+    Json(request): Json<ProposalVoteRequest>,
+) -> Result<Json<ProposalTallyResponse>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    if request.weight == 0 {
+        return Err(ApiError::ValidationError("Vote weight must be greater than zero".to_string()));
+    }
+
+    let proposal_key = format!("{}_{}", query.pda, query.id);
+    let signed: Proposal = decode(database.read(&proposal_key, "proposal").map_err(database_error)?
+        .ok_or_else(|| ApiError::NotFoundError(format!("proposal {} not found", proposal_key)))?)?;
+    let message = proposal_vote_message(&proposal_key, &signed.kind, request.choice, request.weight);
+    verify_wallet_signature(&request.voter, message.as_bytes(), &request.signature)
+        .map_err(|e| ApiError::UnauthorizedError(e.to_string()))?;
+
+    let now = unix_now();
+    let vote_key = vote_key(&proposal_key, &request.voter);
+
+    // 기간 확인, 중복 확인, 투표 파워 계산, 집계 반영을 하나의 트랜잭션으로 처리
+    let proposal = database.transaction(|txn| {
+        let mut proposal: Proposal = decode(txn.read(&proposal_key, "proposal").map_err(database_error)?
+            .ok_or_else(|| ApiError::NotFoundError(format!("proposal {} not found", proposal_key)))?)?;
+
+        if proposal.is_executed || proposal.outcome.is_some() || now > proposal.voting_end_time {
+            return Err(ApiError::ValidationError(format!("Voting for proposal {} is closed", proposal_key)));
+        }
+        if proposal.kind != signed.kind {
+            return Err(ApiError::ConflictError(format!("Proposal {} changed after the vote was signed", proposal_key)));
+        }
+
+        if txn.read(&vote_key, PROPOSAL_VOTE_TABLE).map_err(database_error)?.is_some() {
+            return Err(ApiError::ConflictError(format!(
+                "Wallet {} already voted on proposal {}", request.voter, proposal_key
+            )));
+        }
+
+        // 다른 투표에 위임 파워로 이미 포함된 지갑은 다시 투표할 수 없음
+        let existing_votes = proposal_votes(txn.read_all(PROPOSAL_VOTE_TABLE).map_err(database_error)?, &proposal_key)?;
+        if let Some(existing) = existing_votes.iter().find(|existing| existing.delegators.contains(&request.voter)) {
+            return Err(ApiError::ConflictError(format!(
+                "Wallet {} is already represented by {} on proposal {}", request.voter, existing.voter, proposal_key
+            )));
+        }
+
+        // 이미 투표했거나 다른 투표에 포함된 지갑은 위임 파워에서 제외.
+        // 제안 생성 시점 스냅샷의 투표 파워와 위임 기준 (스냅샷 도입 전 제안은 현재 예치 기준)
+        let counted: BTreeSet<String> = existing_votes.into_iter()
+            .flat_map(|vote| std::iter::once(vote.voter).chain(vote.delegators))
+            .collect();
+        let snapshot = read_snapshot(txn, &proposal_key)?;
+        let power = effective_power_in(txn, &query.pda, &request.voter, snapshot.as_ref(), now, &counted)
+            .map_err(delegation_error)?;
+        if request.weight > power.total() {
+            return Err(ApiError::ValidationError(format!(
+                "Vote weight {} exceeds voting power {} of wallet {}", request.weight, power.total(), request.voter
+            )));
+        }
+
+        let vote = SignedProposalVote {
+            voter: request.voter,
+            choice: request.choice,
+            weight: request.weight,
+            message,
+            signature: request.signature,
+            voted_at: now,
+            delegators: power.delegators,
+        };
+
+        match vote.choice {
+            VoteChoice::Yes => proposal.yes_votes = proposal.yes_votes.saturating_add(vote.weight),
+            VoteChoice::No => proposal.no_votes = proposal.no_votes.saturating_add(vote.weight),
+        }
+
+        txn.write(&proposal_key, &encode(&proposal)?, "proposal").map_err(database_error)?;
+        txn.write(&vote_key, &encode(&vote)?, PROPOSAL_VOTE_TABLE).map_err(database_error)?;
+
+        Ok(proposal)
+    }).map_err(database_error)??;
+
+    Ok(Json(ProposalTallyResponse {
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+    }))
+}
+
+// 제안에 저장된 서명 투표 (투표자 순)
+fn proposal_votes(entries: HashMap<Vec<u8>, Vec<u8>>, proposal_key: &str) -> Result<Vec<SignedProposalVote>, ApiError> {
+    let prefix = vote_key(proposal_key, "");
+    let mut votes = Vec::new();
+    for (key_bytes, value_bytes) in entries {
+        if key_bytes.starts_with(prefix.as_bytes()) {
+            votes.push(decode::<SignedProposalVote>(value_bytes)?);
+        }
+    }
+    votes.sort_by(|a, b| a.voter.cmp(&b.voter));
+    Ok(votes)
+}
+
+// 제안에 저장된 모든 서명 투표 (재집계용, 투표자 순 정렬)
+pub async fn get_proposal_votes<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<ProposalVoteQuery>,
+) -> Result<Json<ProposalVotesResponse>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let vote_entries = database.read_all(PROPOSAL_VOTE_TABLE)
+        .map_err(database_error)?;
+    let votes = proposal_votes(vote_entries, &format!("{}_{}", query.pda, query.id))?;
+
+    Ok(Json(ProposalVotesResponse { votes }))
+}
+
+// 제안 생성 시점의 투표 가능 지갑과 투표 파워
+pub async fn get_proposal_snapshot<T: SafeDatabase>(
+    State(database): State<T>,
+    Query(query): Query<ProposalVoteQuery>,
+) -> Result<Json<VotingPowerSnapshot>, ApiError> {
+    if query.pda.is_empty() {
+        return Err(ApiError::missing_field("pda"));
+    }
+
+    let proposal_key = format!("{}_{}", query.pda, query.id);
+    let snapshot = load_snapshot(&database, &proposal_key)?
+        .ok_or_else(|| ApiError::NotFoundError(format!("Voting power snapshot for proposal {} not found", proposal_key)))?;
+
+    Ok(Json(snapshot))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use turtle_database::basic_db::InnerDatabase;
+    use crate::auth::tests::test_wallet;
+    use ed25519_dalek::Signer;
+    use turtle_service::test_util::{community, content, depositor, proposal};
+    use turtle_service::parser::community::{Community, ProposalKind};
+
+    fn setup(weighted: bool) -> Result<(tempfile::TempDir, InnerDatabase), Box<dyn std::error::Error>> {
+        let temp_dir = tempdir()?;
+        let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+
+        let community = Community {
+            content_count: 1,
+            depositor_count: 1,
+            weighted_content_votes: weighted,
+            ..community()
+        };
+
+        db.write("pda1", &encode(&community)?, "community")?;
+        db.write("pda1_1", &encode(&content("author"))?, "content")?;
+        db.write("pda1_1", &encode(&depositor("whale", 40))?, "depositor")?;
+
+        Ok((temp_dir, db))
+    }
+
+    fn wallet(pubkey: &str) -> WalletIdentity {
+        WalletIdentity {
+            pubkey: pubkey.to_string(),
+        }
+    }
+
+    fn target() -> Query<ContentVoteQuery> {
+        Query(ContentVoteQuery {
+            pda: "pda1".to_string(),
+            id: 1,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_one_vote_per_wallet_and_unvote() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(false)?;
+
+        let response = upvote_content(State(Clone::clone(&db)), wallet("alice"), target()).await?;
+        assert_eq!(response.votes, 1);
+
+        let duplicate = upvote_content(State(Clone::clone(&db)), wallet("alice"), target()).await;
+        assert!(matches!(duplicate, Err(ApiError::ConflictError(_))));
+
+        let response = upvote_content(State(Clone::clone(&db)), wallet("bob"), target()).await?;
+        assert_eq!(response.votes, 2);
+
+        let lookup = |voter: &str| Query(ContentVoteLookupQuery {
+            pda: "pda1".to_string(),
+            id: 1,
+            voter: voter.to_string(),
+        });
+        assert!(get_content_vote(State(Clone::clone(&db)), lookup("alice")).await?.voted);
+
+        let response = unvote_content(State(Clone::clone(&db)), wallet("alice"), target()).await?;
+        assert_eq!(response.votes, 1);
+        assert!(!get_content_vote(State(Clone::clone(&db)), lookup("alice")).await?.voted);
+
+        let missing = unvote_content(State(Clone::clone(&db)), wallet("alice"), target()).await;
+        assert!(matches!(missing, Err(ApiError::NotFoundError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_weighted_votes_use_voting_power() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(true)?;
+
+        let response = upvote_content(State(Clone::clone(&db)), wallet("whale"), target()).await?;
+        assert_eq!(response.votes, 40);
+
+        // 예치 기록이 없는 지갑은 가중 투표 불가
+        let result = upvote_content(State(Clone::clone(&db)), wallet("nobody"), target()).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+
+        let response = unvote_content(State(Clone::clone(&db)), wallet("whale"), target()).await?;
+        assert_eq!(response.votes, 0);
+
+        Ok(())
+    }
+
+    fn signed_vote(seed: u8, choice: VoteChoice, weight: u64) -> Json<ProposalVoteRequest> {
+        let (signing_key, voter) = test_wallet(seed);
+        let message = proposal_vote_message("pda1_1", &ProposalKind::BaseFee(500), choice, weight);
+        Json(ProposalVoteRequest {
+            voter,
+            choice,
+            weight,
+            signature: bs58::encode(signing_key.sign(message.as_bytes()).to_bytes()).into_string(),
+        })
+    }
+
+    fn proposal_target() -> Query<ProposalVoteQuery> {
+        Query(ProposalVoteQuery {
+            pda: "pda1".to_string(),
+            id: 1,
+        })
+    }
+
+    fn add_depositor(db: &InnerDatabase, key: &str, seed: u8, voting_power: u64) -> Result<(), Box<dyn std::error::Error>> {
+        db.write(key, &encode(&depositor(&test_wallet(seed).1, voting_power))?, "depositor")?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_proposal_votes_are_tallied_and_stored() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(false)?;
+        db.write("pda1_1", &encode(&Proposal { voting_end_time: u64::MAX, ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+        add_depositor(&db, "pda1_2", 1, 30)?;
+        add_depositor(&db, "pda1_3", 2, 20)?;
+
+        let tally = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(1, VoteChoice::Yes, 30)).await?;
+        assert_eq!((tally.yes_votes, tally.no_votes), (30, 0));
+
+        let tally = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(2, VoteChoice::No, 15)).await?;
+        assert_eq!((tally.yes_votes, tally.no_votes), (30, 15));
+
+        let duplicate = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(1, VoteChoice::No, 1)).await;
+        assert!(matches!(duplicate, Err(ApiError::ConflictError(_))));
+
+        // 저장된 서명 투표만으로 집계를 다시 검증할 수 있음
+        let stored = get_proposal_votes(State(Clone::clone(&db)), proposal_target()).await?;
+        assert_eq!(stored.votes.len(), 2);
+        let mut recount = (0, 0);
+        for vote in &stored.votes {
+            verify_wallet_signature(&vote.voter, vote.message.as_bytes(), &vote.signature)?;
+            match vote.choice {
+                VoteChoice::Yes => recount.0 += vote.weight,
+                VoteChoice::No => recount.1 += vote.weight,
+            }
+        }
+        assert_eq!(recount, (30, 15));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delegated_power_is_counted_once() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(false)?;
+        db.write("pda1_1", &encode(&Proposal { voting_end_time: u64::MAX, ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+        add_depositor(&db, "pda1_2", 1, 30)?;
+        add_depositor(&db, "pda1_3", 2, 20)?;
+        turtle_service::delegation::delegate(&db, "pda1", &test_wallet(1).1, &test_wallet(2).1, 0)?;
+
+        // 위임한 지갑은 직접 투표할 파워가 없음
+        let result = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(1, VoteChoice::No, 30)).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+
+        let tally = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(2, VoteChoice::Yes, 50)).await?;
+        assert_eq!((tally.yes_votes, tally.no_votes), (50, 0));
+
+        // 철회해도 이미 대리인 투표에 포함된 파워로 다시 투표할 수 없음
+        turtle_service::delegation::revoke(&db, "pda1", &test_wallet(1).1)?;
+        let result = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(1, VoteChoice::No, 30)).await;
+        assert!(matches!(result, Err(ApiError::ConflictError(_))));
+
+        let stored = get_proposal_votes(State(Clone::clone(&db)), proposal_target()).await?;
+        assert_eq!(stored.votes[0].delegators, vec![test_wallet(1).1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_votes_use_snapshot_power() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(false)?;
+        db.write("pda1_1", &encode(&Proposal { voting_end_time: u64::MAX, ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+        add_depositor(&db, "pda1_2", 1, 30)?;
+        db.transaction(|txn| turtle_service::snapshot::record_snapshot(txn, "pda1", "pda1_1", 0))??;
+
+        // 제안 생성 이후 예치한 지갑과 늘어난 예치는 투표 파워에 포함되지 않음
+        add_depositor(&db, "pda1_2", 1, 300)?;
+        add_depositor(&db, "pda1_3", 2, 20)?;
+        let result = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(1, VoteChoice::Yes, 31)).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+        let result = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(2, VoteChoice::No, 1)).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+
+        let tally = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(1, VoteChoice::Yes, 30)).await?;
+        assert_eq!((tally.yes_votes, tally.no_votes), (30, 0));
+
+        let snapshot = get_proposal_snapshot(State(Clone::clone(&db)), proposal_target()).await?;
+        assert_eq!(snapshot.total_power, 70);
+        let missing = get_proposal_snapshot(State(Clone::clone(&db)), Query(ProposalVoteQuery { pda: "pda1".to_string(), id: 9 })).await;
+        assert!(matches!(missing, Err(ApiError::NotFoundError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_proposal_vote_rejections() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(false)?;
+        db.write("pda1_1", &encode(&Proposal { voting_end_time: u64::MAX, ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+        db.write("pda1_2", &encode(&Proposal { id: 2, voting_end_time: 1, ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+        add_depositor(&db, "pda1_2", 1, 10)?;
+
+        // 가중치가 voting_power를 넘으면 거부
+        let result = vote_proposal(State(Clone::clone(&db)), proposal_target(), signed_vote(1, VoteChoice::Yes, 11)).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+
+        // 서명한 내용과 다른 가중치는 서명 검증 실패
+        let Json(mut tampered) = signed_vote(1, VoteChoice::Yes, 5);
+        tampered.weight = 10;
+        let result = vote_proposal(State(Clone::clone(&db)), proposal_target(), Json(tampered)).await;
+        assert!(matches!(result, Err(ApiError::UnauthorizedError(_))));
+
+        // 다른 제안 내용에 대한 서명은 쓸 수 없음
+        let (signing_key, voter) = test_wallet(1);
+        let message = proposal_vote_message("pda1_1", &ProposalKind::BaseFee(1), VoteChoice::Yes, 5);
+        let request = ProposalVoteRequest {
+            voter,
+            choice: VoteChoice::Yes,
+            weight: 5,
+            signature: bs58::encode(signing_key.sign(message.as_bytes()).to_bytes()).into_string(),
+        };
+        let result = vote_proposal(State(Clone::clone(&db)), proposal_target(), Json(request)).await;
+        assert!(matches!(result, Err(ApiError::UnauthorizedError(_))));
+
+        // 투표 기간이 끝난 제안
+        let (signing_key, voter) = test_wallet(1);
+        let message = proposal_vote_message("pda1_2", &ProposalKind::BaseFee(500), VoteChoice::Yes, 5);
+        let request = ProposalVoteRequest {
+            voter,
+            choice: VoteChoice::Yes,
+            weight: 5,
+            signature: bs58::encode(signing_key.sign(message.as_bytes()).to_bytes()).into_string(),
+        };
+        let closed = Query(ProposalVoteQuery { pda: "pda1".to_string(), id: 2 });
+        let result = vote_proposal(State(Clone::clone(&db)), closed, Json(request)).await;
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use image::imageops::FilterType;
+use image::io::{Limits, Reader};
+use image::{DynamicImage, ImageError, ImageFormat, ImageOutputFormat};
+use sha2::{Digest, Sha256};
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::Cursor;
+
+// 업로드 가능한 최대 아바타 크기(바이트)
+pub const MAX_AVATAR_BYTES: usize = 2 * 1024 * 1024;
+
+// 디코딩 허용 최대 가로/세로 픽셀
+pub const MAX_AVATAR_DIMENSION: u32 = 4096;
+
+// 정규화된 아바타의 최대 가로/세로 픽셀
+pub const NORMALIZED_AVATAR_DIMENSION: u32 = 512;
+
+// 생성하는 정사각형 썸네일 크기
+pub const THUMBNAIL_SIZES: [u32; 2] = [64, 256];
+
+// 정규화된 아바타와 썸네일의 MIME 타입
+pub const AVATAR_CONTENT_TYPE: &str = "image/png";
+
+const ALLOWED_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AvatarError {
+    Empty,
+    TooLarge { size: usize, max: usize },
+    UnknownFormat,
+    UnsupportedFormat(String),
+    ContentTypeMismatch { declared: String, detected: String },
+    DimensionsTooLarge { max: u32 },
+    Malformed(String),
+    EncodingFailed(String),
+}
+
+impl fmt::Display for AvatarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AvatarError::Empty => write!(f, "Avatar file is empty"),
+            AvatarError::TooLarge { size, max } => write!(f, "Avatar is {} bytes, maximum is {} bytes", size, max),
+            AvatarError::UnknownFormat => write!(f, "Avatar format could not be detected"),
+            AvatarError::UnsupportedFormat(format) => write!(f, "Avatar format {} is not supported", format),
+            AvatarError::ContentTypeMismatch { declared, detected } => {
+                write!(f, "Avatar declared as {} but is {}", declared, detected)
+            },
+            AvatarError::DimensionsTooLarge { max } => write!(f, "Avatar dimensions exceed {}x{}", max, max),
+            AvatarError::Malformed(msg) => write!(f, "Avatar could not be decoded: {}", msg),
+            AvatarError::EncodingFailed(msg) => write!(f, "Avatar could not be encoded: {}", msg),
+        }
+    }
+}
+
+impl StdError for AvatarError {}
+
+// 검증 및 정규화가 끝난 아바타
+#[derive(Debug)]
+pub struct ProcessedAvatar {
+    pub content_type: &'static str,
+    pub hash: String,                       // 정규화된 이미지의 SHA-256 (hex)
+    pub image: Vec<u8>,
+    pub thumbnails: Vec<(u32, Vec<u8>)>,    // (크기, PNG 바이트)
+}
+
+fn mime_type(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Png => "image/png",
+        ImageFormat::Jpeg => "image/jpeg",
+        ImageFormat::Gif => "image/gif",
+        ImageFormat::WebP => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+// 아바타 바이트의 SHA-256 hex 문자열 (ETag 및 캐시 버전으로 사용)
+pub fn avatar_hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn encode_png(image: &DynamicImage) -> Result<Vec<u8>, AvatarError> {
+    let mut buffer = Cursor::new(Vec::new());
+    image
+        .write_to(&mut buffer, ImageOutputFormat::Png)
+        .map_err(|e| AvatarError::EncodingFailed(e.to_string()))?;
+    Ok(buffer.into_inner())
+}
+
+// 실제 포맷을 확인하고 디코딩한 뒤 메타데이터 없는 PNG와 썸네일로 재인코딩
+pub fn process_avatar(data: &[u8], declared_content_type: Option<&str>) -> Result<ProcessedAvatar, AvatarError> {
+    if data.is_empty() {
+        return Err(AvatarError::Empty);
+    }
+
+    if data.len() > MAX_AVATAR_BYTES {
+        return Err(AvatarError::TooLarge {
+            size: data.len(),
+            max: MAX_AVATAR_BYTES,
+        });
+    }
+
+    let format = image::guess_format(data).map_err(|_| AvatarError::UnknownFormat)?;
+    if !ALLOWED_FORMATS.contains(&format) {
+        return Err(AvatarError::UnsupportedFormat(format!("{:?}", format)));
+    }
+
+    let detected = mime_type(format);
+    if let Some(declared) = declared_content_type {
+        // 클라이언트가 보낸 타입은 실제 포맷과 일치할 때만 허용
+        if declared != detected && declared != "application/octet-stream" {
+            return Err(AvatarError::ContentTypeMismatch {
+                declared: declared.to_string(),
+                detected: detected.to_string(),
+            });
+        }
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_AVATAR_DIMENSION);
+    limits.max_image_height = Some(MAX_AVATAR_DIMENSION);
+
+    let mut reader = Reader::with_format(Cursor::new(data), format);
+    reader.limits(limits);
+
+    let decoded = reader.decode().map_err(|e| match e {
+        ImageError::Limits(_) => AvatarError::DimensionsTooLarge { max: MAX_AVATAR_DIMENSION },
+        other => AvatarError::Malformed(other.to_string()),
+    })?;
+
+    // 픽셀만 다시 인코딩하므로 EXIF 등 메타데이터는 남지 않음
+    let normalized = if decoded.width() > NORMALIZED_AVATAR_DIMENSION || decoded.height() > NORMALIZED_AVATAR_DIMENSION {
+        decoded.resize(NORMALIZED_AVATAR_DIMENSION, NORMALIZED_AVATAR_DIMENSION, FilterType::Lanczos3)
+    } else {
+        decoded
+    };
+
+    let thumbnails = THUMBNAIL_SIZES
+        .iter()
+        .map(|&size| {
+            let thumbnail = normalized.resize_to_fill(size, size, FilterType::Lanczos3);
+            encode_png(&thumbnail).map(|bytes| (size, bytes))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let image = encode_png(&normalized)?;
+
+    Ok(ProcessedAvatar {
+        content_type: AVATAR_CONTENT_TYPE,
+        hash: avatar_hash(&image),
+        image,
+        thumbnails,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+
+    fn encoded_image(width: u32, height: u32, format: ImageOutputFormat) -> Vec<u8> {
+        let image = DynamicImage::ImageRgb8(ImageBuffer::from_pixel(width, height, Rgb([200u8, 100, 50])));
+        let mut buffer = Cursor::new(Vec::new());
+        image.write_to(&mut buffer, format).unwrap();
+        buffer.into_inner()
+    }
+
+    #[test]
+    fn test_process_avatar_normalizes_to_png_with_thumbnails() {
+        let jpeg = encoded_image(800, 600, ImageOutputFormat::Jpeg(90));
+
+        let avatar = process_avatar(&jpeg, Some("image/jpeg")).unwrap();
+        assert_eq!(avatar.content_type, "image/png");
+        assert_eq!(image::guess_format(&avatar.image).unwrap(), ImageFormat::Png);
+        assert_eq!(avatar.hash, avatar_hash(&avatar.image));
+        assert_eq!(avatar.hash.len(), 64);
+
+        let normalized = image::load_from_memory(&avatar.image).unwrap();
+        assert_eq!((normalized.width(), normalized.height()), (512, 384));
+
+        for (size, bytes) in &avatar.thumbnails {
+            let thumbnail = image::load_from_memory(bytes).unwrap();
+            assert_eq!((thumbnail.width(), thumbnail.height()), (*size, *size));
+        }
+        assert_eq!(avatar.thumbnails.len(), THUMBNAIL_SIZES.len());
+    }
+
+    #[test]
+    fn test_process_avatar_rejects_invalid_input() {
+        assert_eq!(process_avatar(&[], None).unwrap_err(), AvatarError::Empty);
+        assert_eq!(process_avatar(&[1, 2, 3, 4, 5], None).unwrap_err(), AvatarError::UnknownFormat);
+
+        let oversized = vec![0u8; MAX_AVATAR_BYTES + 1];
+        assert!(matches!(process_avatar(&oversized, None), Err(AvatarError::TooLarge { .. })));
+
+        let bmp = encoded_image(8, 8, ImageOutputFormat::Bmp);
+        assert!(matches!(process_avatar(&bmp, None), Err(AvatarError::UnsupportedFormat(_))));
+
+        let png = encoded_image(8, 8, ImageOutputFormat::Png);
+        assert!(matches!(process_avatar(&png, Some("image/jpeg")), Err(AvatarError::ContentTypeMismatch { .. })));
+
+        // 시그니처만 PNG이고 나머지는 손상된 파일
+        let mut truncated = png.clone();
+        truncated.truncate(24);
+        assert!(matches!(process_avatar(&truncated, None), Err(AvatarError::Malformed(_))));
+    }
+
+    #[test]
+    fn test_process_avatar_rejects_huge_dimensions() {
+        let png = encoded_image(MAX_AVATAR_DIMENSION + 1, 1, ImageOutputFormat::Png);
+
+        assert_eq!(
+            process_avatar(&png, None).unwrap_err(),
+            AvatarError::DimensionsTooLarge { max: MAX_AVATAR_DIMENSION }
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use std::fmt;
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error::{database_error, decode, encode, StorageError};
+use crate::settlement::PayoutTable;
+use sol::pubkey::parse_pubkey;
+
+// 라운드별 머클 루트 (key: pda:round_id)
+pub const CLAIM_ROOT_TABLE: &str = "claim_root";
+
+// 지갑별 청구 리프와 증명 (key: wallet:pda:round_id)
+pub const CLAIM_LEAF_TABLE: &str = "claim_leaf";
+
+// 온체인 merkle-distributor와 같은 도메인 구분 접두사
+const LEAF_PREFIX: &[u8] = &[0];
+const INTERMEDIATE_PREFIX: &[u8] = &[1];
+
+pub type MerkleHash = [u8; 32];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimRoot {
+    pub pda: String,
+    pub round_id: u64,
+    pub root: String,               // base58
+    pub leaf_count: u64,
+    pub total_amount: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimLeaf {
+    pub wallet: String,
+    pub pda: String,
+    pub round_id: u64,
+    pub index: u64,                 // 지갑 pubkey 순 리프 위치
+    pub amount: u64,                // lamports
+    pub proof: Vec<String>,         // base58, 리프에서 루트 방향
+    pub root: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ClaimError {
+    Storage(StorageError),
+    InvalidWallet(String),
+    Overflow(String),
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClaimError::Storage(e) => write!(f, "{}", e),
+            ClaimError::InvalidWallet(wallet) => write!(f, "Invalid wallet pubkey: {}", wallet),
+            ClaimError::Overflow(wallet) => write!(f, "Claim amount for {} exceeds u64", wallet),
+        }
+    }
+}
+
+impl StdError for ClaimError {}
+
+impl From<StorageError> for ClaimError {
+    fn from(e: StorageError) -> Self {
+        ClaimError::Storage(e)
+    }
+}
+
+fn hashv(parts: &[&[u8]]) -> MerkleHash {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn wallet_bytes(wallet: &str) -> Result<[u8; 32], ClaimError> {
+    parse_pubkey(wallet)
+        .map(|pubkey| pubkey.to_bytes())
+        .map_err(|_| ClaimError::InvalidWallet(wallet.to_string()))
+}
+
+// sha256(0x00 || sha256(wallet || amount_le || round_le))
+pub fn leaf_hash(wallet: &str, amount: u64, round_id: u64) -> Result<MerkleHash, ClaimError> {
+    let wallet = wallet_bytes(wallet)?;
+    let inner = hashv(&[&wallet, &amount.to_le_bytes(), &round_id.to_le_bytes()]);
+    Ok(hashv(&[LEAF_PREFIX, &inner]))
+}
+
+// 정렬된 두 노드를 합쳐 증명에 좌우 정보가 필요 없게 함
+fn parent_hash(a: &MerkleHash, b: &MerkleHash) -> MerkleHash {
+    if a <= b {
+        hashv(&[INTERMEDIATE_PREFIX, a, b])
+    } else {
+        hashv(&[INTERMEDIATE_PREFIX, b, a])
+    }
+}
+
+// 리프들로 루트와 리프별 증명 생성. 짝이 없는 마지막 노드는 그대로 윗단으로 올라감. 리프가 없으면 루트는 0
+pub fn build_tree(leaves: &[MerkleHash]) -> (MerkleHash, Vec<Vec<MerkleHash>>) {
+    let mut proofs = vec![Vec::new(); leaves.len()];
+    let mut positions: Vec<usize> = (0..leaves.len()).collect();
+    let mut level = leaves.to_vec();
+
+    if level.is_empty() {
+        return ([0; 32], proofs);
+    }
+
+    while level.len() > 1 {
+        for (leaf, position) in positions.iter_mut().enumerate() {
+            let sibling = *position ^ 1;
+            if sibling < level.len() {
+                proofs[leaf].push(level[sibling]);
+            }
+            *position /= 2;
+        }
+
+        level = level.chunks(2)
+            .map(|pair| match pair {
+                [a, b] => parent_hash(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    (level[0], proofs)
+}
+
+pub fn verify_proof(leaf: MerkleHash, proof: &[MerkleHash], root: &MerkleHash) -> bool {
+    proof.iter().fold(leaf, |node, sibling| parent_hash(&node, sibling)) == *root
+}
+
+fn encode_hash(hash: &MerkleHash) -> String {
+    bs58::encode(hash).into_string()
+}
+
+pub fn decode_hash(hash: &str) -> Option<MerkleHash> {
+    bs58::decode(hash).into_vec().ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+}
+
+// 분배표의 작성자/예치자 몫을 지갑별로 합쳐 머클 트리를 만들고 루트와 리프 저장 (라운드 종료 트랜잭션 안에서 호출)
+pub fn record_claims(
+    txn: &DatabaseTransaction<'_, '_>,
+    pda: &str,
+    round_id: u64,
+    table: &PayoutTable,
+) -> Result<ClaimRoot, ClaimError> {
+    let mut amounts: BTreeMap<&str, u64> = BTreeMap::new();
+    for payout in table.author_payouts.iter().chain(&table.depositor_payouts) {
+        let amount = amounts.entry(&payout.pubkey).or_insert(0);
+        *amount = amount.checked_add(payout.lamports)
+            .ok_or_else(|| ClaimError::Overflow(payout.pubkey.clone()))?;
+    }
+
+    let leaves = amounts.iter()
+        .map(|(wallet, amount)| leaf_hash(wallet, *amount, round_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (root, proofs) = build_tree(&leaves);
+
+    let claim_root = ClaimRoot {
+        pda: pda.to_string(),
+        round_id,
+        root: encode_hash(&root),
+        leaf_count: leaves.len() as u64,
+        total_amount: amounts.values().sum(),
+    };
+
+    for (index, ((wallet, amount), proof)) in amounts.into_iter().zip(proofs).enumerate() {
+        let leaf = ClaimLeaf {
+            wallet: wallet.to_string(),
+            pda: pda.to_string(),
+            round_id,
+            index: index as u64,
+            amount,
+            proof: proof.iter().map(encode_hash).collect(),
+            root: claim_root.root.clone(),
+        };
+        txn.write(&format!("{}:{}:{}", wallet, pda, round_id), &encode(&leaf)?, CLAIM_LEAF_TABLE)
+            .map_err(database_error)?;
+    }
+
+    txn.write(&format!("{}:{}", pda, round_id), &encode(&claim_root)?, CLAIM_ROOT_TABLE).map_err(database_error)?;
+
+    Ok(claim_root)
+}
+
+// 지갑의 모든 청구 리프 (pda, round_id 순)
+pub fn claims_for_wallet<T: SafeDatabase>(database: &T, wallet: &str) -> Result<Vec<ClaimLeaf>, ClaimError> {
+    let prefix = format!("{}:", wallet);
+    let mut claims = Vec::new();
+
+    for (key, value) in database.read_all(CLAIM_LEAF_TABLE).map_err(database_error)? {
+        if key.starts_with(prefix.as_bytes()) {
+            claims.push(decode::<ClaimLeaf>(&value)?);
+        }
+    }
+
+    claims.sort_by(|a, b| a.pda.cmp(&b.pda).then(a.round_id.cmp(&b.round_id)));
+    Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settlement::Payout;
+    use crate::test_util::{temp_db, wallet};
+
+    fn payout(pubkey: &str, lamports: u64) -> Payout {
+        Payout {
+            pubkey: pubkey.to_string(),
+            lamports,
+        }
+    }
+
+    #[test]
+    fn test_every_proof_verifies_against_root() -> Result<(), ClaimError> {
+        for count in 1..=9u8 {
+            let leaves = (0..count)
+                .map(|seed| leaf_hash(&wallet(seed), seed as u64 * 100, 3))
+                .collect::<Result<Vec<_>, _>>()?;
+            let (root, proofs) = build_tree(&leaves);
+
+            for (leaf, proof) in leaves.iter().zip(&proofs) {
+                assert!(verify_proof(*leaf, proof, &root));
+            }
+
+            // 금액이나 라운드가 다르면 검증 실패
+            assert!(!verify_proof(leaf_hash(&wallet(0), 1, 3)?, &proofs[0], &root));
+            assert!(!verify_proof(leaf_hash(&wallet(0), 0, 4)?, &proofs[0], &root));
+        }
+
+        assert_eq!(leaf_hash("not-a-wallet", 1, 1), Err(ClaimError::InvalidWallet("not-a-wallet".to_string())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_claims_merges_roles_and_stores_proofs() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = temp_db()?;
+        let table = PayoutTable {
+            total_deposit: 100,
+            reward_pool: 30,
+            author_payouts: vec![payout(&wallet(1), 30)],
+            depositor_payouts: vec![payout(&wallet(1), 40), payout(&wallet(2), 30)],
+            dust: 0,
+            skipped: Vec::new(),
+        };
+
+        let claim_root = db.transaction(|txn| record_claims(txn, "pda1", 7, &table))??;
+        assert_eq!((claim_root.leaf_count, claim_root.total_amount), (2, 100));
+
+        let claims = claims_for_wallet(&db, &wallet(1))?;
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].amount, 70);
+
+        let root = decode_hash(&claim_root.root).unwrap();
+        let proof = claims[0].proof.iter().map(|hash| decode_hash(hash).unwrap()).collect::<Vec<_>>();
+        assert!(verify_proof(leaf_hash(&wallet(1), 70, 7)?, &proof, &root));
+
+        Ok(())
+    }
+}
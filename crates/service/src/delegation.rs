@@ -0,0 +1,365 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error as StdError;
+use std::fmt;
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error::{database_error, decode, encode, not_found, StorageError};
+use crate::parser::community::{Community, Depositor};
+use crate::snapshot::VotingPowerSnapshot;
+use crate::voting_power::voting_power;
+
+// 커뮤니티별 투표권 위임 (key: pda:delegator)
+pub const DELEGATION_TABLE: &str = "delegation";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegation {
+    pub delegator: String,
+    pub delegate: String,
+    pub created_at: u64,
+}
+
+// 위임받은 지갑 입장에서 본 위임자
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegator {
+    pub wallet: String,
+    pub direct: bool,               // false면 다른 위임자를 거쳐 연결됨
+}
+
+// 투표 시점에 지갑이 행사할 수 있는 투표 파워
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectivePower {
+    pub own: u64,                   // 위임한 지갑이면 0
+    pub delegated: u64,
+    pub delegators: Vec<String>,    // delegated에 포함된 위임자
+}
+
+impl EffectivePower {
+    pub fn total(&self) -> u64 {
+        self.own.saturating_add(self.delegated)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DelegationError {
+    Storage(StorageError),
+    Validation(String),
+    Cycle(Vec<String>),             // 순환이 발생한 위임 경로
+}
+
+impl fmt::Display for DelegationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DelegationError::Storage(e) => write!(f, "{}", e),
+            DelegationError::Validation(msg) => write!(f, "Validation error: {}", msg),
+            DelegationError::Cycle(path) => write!(f, "Delegation cycle: {}", path.join(" -> ")),
+        }
+    }
+}
+
+impl StdError for DelegationError {}
+
+impl From<StorageError> for DelegationError {
+    fn from(e: StorageError) -> Self {
+        DelegationError::Storage(e)
+    }
+}
+
+fn delegation_key(pda: &str, delegator: &str) -> String {
+    format!("{}:{}", pda, delegator)
+}
+
+// 커뮤니티의 모든 위임 (delegator -> delegate)
+pub(crate) fn load_delegations(txn: &DatabaseTransaction<'_, '_>, pda: &str) -> Result<BTreeMap<String, String>, StorageError> {
+    let prefix = delegation_key(pda, "");
+    let mut delegations = BTreeMap::new();
+
+    for (key, value) in txn.read_all(DELEGATION_TABLE).map_err(database_error)? {
+        if key.starts_with(prefix.as_bytes()) {
+            let delegation: Delegation = decode(&value)?;
+            delegations.insert(delegation.delegator, delegation.delegate);
+        }
+    }
+
+    Ok(delegations)
+}
+
+// wallet에서 시작하는 위임 경로 (wallet 제외, 마지막이 최종 대리인). 순환이 있으면 에러
+pub fn delegation_chain(delegations: &BTreeMap<String, String>, wallet: &str) -> Result<Vec<String>, DelegationError> {
+    let mut visited = BTreeSet::from([wallet.to_string()]);
+    let mut chain = Vec::new();
+    let mut current = wallet;
+
+    while let Some(next) = delegations.get(current) {
+        chain.push(next.clone());
+        if !visited.insert(next.clone()) {
+            let mut path = vec![wallet.to_string()];
+            path.extend(chain);
+            return Err(DelegationError::Cycle(path));
+        }
+        current = next;
+    }
+
+    Ok(chain)
+}
+
+// 위임을 거쳐 최종적으로 투표권을 행사하는 지갑
+pub fn resolve_delegate(delegations: &BTreeMap<String, String>, wallet: &str) -> Result<String, DelegationError> {
+    Ok(delegation_chain(delegations, wallet)?
+        .pop()
+        .unwrap_or_else(|| wallet.to_string()))
+}
+
+fn load_community(txn: &DatabaseTransaction<'_, '_>, pda: &str) -> Result<Community, DelegationError> {
+    Ok(decode(&txn.read(pda, "community").map_err(database_error)?
+        .ok_or_else(|| not_found(format!("Community with PDA {} not found", pda)))?)?)
+}
+
+// 커뮤니티 모델로 계산한 지갑별 투표 파워
+fn wallet_powers(txn: &DatabaseTransaction<'_, '_>, pda: &str, now: u64) -> Result<BTreeMap<String, u64>, DelegationError> {
+    let community = load_community(txn, pda)?;
+    let prefix = format!("{}_", pda);
+    let mut powers = BTreeMap::new();
+
+    for (key, value) in txn.read_all("depositor").map_err(database_error)? {
+        if key.starts_with(prefix.as_bytes()) {
+            let depositor: Depositor = decode(&value)?;
+            let power = powers.entry(depositor.pubkey.clone()).or_insert(0u64);
+            *power = power.saturating_add(voting_power(&community.voting_power, &depositor, now));
+        }
+    }
+
+    Ok(powers)
+}
+
+// 예치 포지션이 있는 지갑만 다른 지갑에 위임 가능. 기존 위임은 교체되고 순환이 생기면 거부
+pub fn delegate<T: SafeDatabase>(
+    database: &T,
+    pda: &str,
+    delegator: &str,
+    delegate: &str,
+    now: u64,
+) -> Result<Delegation, DelegationError> {
+    if delegate.is_empty() {
+        return Err(DelegationError::Validation("Delegate cannot be empty".to_string()));
+    }
+    if delegator == delegate {
+        return Err(DelegationError::Validation("Cannot delegate to yourself".to_string()));
+    }
+
+    database.transaction(|txn| {
+        if !wallet_powers(txn, pda, now)?.contains_key(delegator) {
+            return Err(DelegationError::Validation(format!(
+                "Wallet {} has no deposit in community {}", delegator, pda
+            )));
+        }
+
+        let mut delegations = load_delegations(txn, pda)?;
+        delegations.insert(delegator.to_string(), delegate.to_string());
+        delegation_chain(&delegations, delegator)?;
+
+        let delegation = Delegation {
+            delegator: delegator.to_string(),
+            delegate: delegate.to_string(),
+            created_at: now,
+        };
+        txn.write(&delegation_key(pda, delegator), &encode(&delegation)?, DELEGATION_TABLE).map_err(database_error)?;
+
+        Ok(delegation)
+    }).map_err(database_error)?
+}
+
+// 위임 철회. 이후 투표부터 본인 투표 파워로 직접 투표
+pub fn revoke<T: SafeDatabase>(database: &T, pda: &str, delegator: &str) -> Result<Delegation, DelegationError> {
+    database.transaction(|txn| {
+        let key = delegation_key(pda, delegator);
+        let delegation: Delegation = decode(&txn.read(&key, DELEGATION_TABLE).map_err(database_error)?
+            .ok_or_else(|| not_found(format!("Wallet {} has no delegation in community {}", delegator, pda)))?)?;
+
+        txn.delete(&key, DELEGATION_TABLE).map_err(database_error)?;
+        Ok(delegation)
+    }).map_err(database_error)?
+}
+
+// 지갑이 위임한 경로 (직접 대리인부터 최종 대리인까지)
+pub fn delegates_of<T: SafeDatabase>(database: &T, pda: &str, wallet: &str) -> Result<Vec<String>, DelegationError> {
+    database.transaction(|txn| delegation_chain(&load_delegations(txn, pda)?, wallet))
+        .map_err(database_error)?
+}
+
+// 지갑에 직접 또는 다른 위임자를 거쳐 위임한 지갑 (지갑 순)
+pub fn delegators_of<T: SafeDatabase>(database: &T, pda: &str, wallet: &str) -> Result<Vec<Delegator>, DelegationError> {
+    database.transaction(|txn| {
+        let delegations = load_delegations(txn, pda)?;
+        let mut delegators = Vec::new();
+
+        for (delegator, delegate) in &delegations {
+            if delegation_chain(&delegations, delegator)?.iter().any(|hop| hop == wallet) {
+                delegators.push(Delegator {
+                    wallet: delegator.clone(),
+                    direct: delegate == wallet,
+                });
+            }
+        }
+
+        Ok(delegators)
+    }).map_err(database_error)?
+}
+
+// 지갑별 투표 파워에 위임을 반영. excluded(이미 직접 투표했거나 다른 투표에 포함된 지갑)는 위임 파워에서 제외
+fn combine_power(
+    powers: &BTreeMap<String, u64>,
+    delegations: &BTreeMap<String, String>,
+    wallet: &str,
+    excluded: &BTreeSet<String>,
+) -> Result<EffectivePower, DelegationError> {
+    let own = if delegations.contains_key(wallet) {
+        0
+    } else {
+        powers.get(wallet).copied().unwrap_or(0)
+    };
+
+    let mut delegated: u64 = 0;
+    let mut delegators = Vec::new();
+    for (delegator, power) in powers {
+        if delegator == wallet || excluded.contains(delegator) || !delegations.contains_key(delegator) {
+            continue;
+        }
+        if resolve_delegate(delegations, delegator)? == wallet {
+            delegated = delegated.saturating_add(*power);
+            delegators.push(delegator.clone());
+        }
+    }
+
+    Ok(EffectivePower { own, delegated, delegators })
+}
+
+// 트랜잭션 안에서 계산한 투표 파워. 스냅샷이 있으면 생성 시점의 파워와 위임(위임이 기록되지 않은 예전 스냅샷은 현재 위임),
+// 없으면 현재 예치와 위임 기준
+pub fn effective_power_in(
+    txn: &DatabaseTransaction<'_, '_>,
+    pda: &str,
+    wallet: &str,
+    snapshot: Option<&VotingPowerSnapshot>,
+    now: u64,
+    excluded: &BTreeSet<String>,
+) -> Result<EffectivePower, DelegationError> {
+    let powers = match snapshot {
+        Some(snapshot) => snapshot.powers(),
+        None => wallet_powers(txn, pda, now)?,
+    };
+    let delegations = match snapshot.and_then(|snapshot| snapshot.delegations.as_ref()) {
+        Some(delegations) => delegations.clone(),
+        None => load_delegations(txn, pda)?,
+    };
+    combine_power(&powers, &delegations, wallet, excluded)
+}
+
+// 지갑의 투표 파워와 위임받은 투표 파워 (현재 예치 기준)
+pub fn effective_power<T: SafeDatabase>(
+    database: &T,
+    pda: &str,
+    wallet: &str,
+    now: u64,
+    excluded: &BTreeSet<String>,
+) -> Result<EffectivePower, DelegationError> {
+    database.transaction(|txn| effective_power_in(txn, pda, wallet, None, now, excluded))
+        .map_err(database_error)?
+}
+
+// 제안 생성 시점 스냅샷의 투표 파워와 위임 기준
+pub fn effective_power_from<T: SafeDatabase>(
+    database: &T,
+    pda: &str,
+    wallet: &str,
+    snapshot: &VotingPowerSnapshot,
+    excluded: &BTreeSet<String>,
+) -> Result<EffectivePower, DelegationError> {
+    database.transaction(|txn| effective_power_in(txn, pda, wallet, Some(snapshot), snapshot.taken_at, excluded))
+        .map_err(database_error)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{community, depositor, temp_db};
+    use turtle_database::basic_db::InnerDatabase;
+
+    fn setup() -> Result<(tempfile::TempDir, InnerDatabase), Box<dyn std::error::Error>> {
+        let (temp_dir, db) = temp_db()?;
+
+        let community = Community {
+            total_deposit: 60,
+            depositor_count: 3,
+            ..community()
+        };
+        db.write("pda1", &serde_json::to_string(&community)?, "community")?;
+
+        for (id, (pubkey, amount)) in [("a", 10), ("b", 20), ("c", 30)].into_iter().enumerate() {
+            db.write(&format!("pda1_{}", id + 1), &serde_json::to_string(&depositor(pubkey, amount))?, "depositor")?;
+        }
+
+        Ok((temp_dir, db))
+    }
+
+    #[test]
+    fn test_chains_resolve_and_cycles_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+
+        delegate(&db, "pda1", "a", "b", 1)?;
+        delegate(&db, "pda1", "b", "c", 1)?;
+        assert_eq!(delegates_of(&db, "pda1", "a")?, vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(delegators_of(&db, "pda1", "c")?, vec![
+            Delegator { wallet: "a".to_string(), direct: false },
+            Delegator { wallet: "b".to_string(), direct: true },
+        ]);
+
+        assert_eq!(
+            delegate(&db, "pda1", "c", "a", 2),
+            Err(DelegationError::Cycle(vec!["c".to_string(), "a".to_string(), "b".to_string(), "c".to_string()])),
+        );
+        assert!(matches!(delegate(&db, "pda1", "stranger", "a", 2), Err(DelegationError::Validation(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_effective_power_includes_delegated_and_skips_excluded() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        delegate(&db, "pda1", "a", "b", 1)?;
+        delegate(&db, "pda1", "b", "c", 1)?;
+
+        let power = effective_power(&db, "pda1", "c", 1, &BTreeSet::new())?;
+        assert_eq!((power.own, power.delegated, power.total()), (30, 30, 60));
+        assert_eq!(effective_power(&db, "pda1", "b", 1, &BTreeSet::new())?.total(), 0);
+
+        // 이미 직접 투표한 위임자는 대리인 파워에서 제외
+        let power = effective_power(&db, "pda1", "c", 1, &BTreeSet::from(["a".to_string()]))?;
+        assert_eq!((power.delegated, power.delegators), (20, vec!["b".to_string()]));
+
+        // 철회하면 본인 파워로 돌아옴
+        revoke(&db, "pda1", "b")?;
+        assert_eq!(effective_power(&db, "pda1", "b", 1, &BTreeSet::new())?.total(), 30);
+        assert!(matches!(revoke(&db, "pda1", "b"), Err(DelegationError::Storage(StorageError::NotFound(_)))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_power_follows_delegations_at_creation() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        delegate(&db, "pda1", "a", "c", 1)?;
+        let snapshot = db.transaction(|txn| crate::snapshot::record_snapshot(txn, "pda1", "pda1_1", 1))??;
+
+        // 제안 생성 이후 위임을 옮겨도 스냅샷 시점의 대리인이 위임 파워를 행사
+        delegate(&db, "pda1", "a", "b", 2)?;
+        let power = effective_power_from(&db, "pda1", "c", &snapshot, &BTreeSet::new())?;
+        assert_eq!((power.own, power.delegated), (30, 10));
+        assert_eq!(effective_power_from(&db, "pda1", "b", &snapshot, &BTreeSet::new())?.total(), 20);
+
+        // 위임이 기록되지 않은 예전 스냅샷은 현재 위임 기준
+        let legacy = crate::snapshot::VotingPowerSnapshot { delegations: None, ..snapshot };
+        assert_eq!(effective_power_from(&db, "pda1", "b", &legacy, &BTreeSet::new())?.total(), 30);
+
+        Ok(())
+    }
+}
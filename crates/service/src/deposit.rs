@@ -0,0 +1,591 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use std::fmt;
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error::{database_error, decode, encode, not_found, StorageError};
+use crate::parser::community::{Community, Depositor};
+use crate::sequence::next_id;
+use crate::settlement::split_by_weight;
+use crate::voting_power::voting_power;
+
+// 지갑별 예치 포지션 인덱스 (key: pda:pubkey, value: depositor 키 pda_n)
+pub const DEPOSITOR_POSITION_TABLE: &str = "depositor_position";
+
+// 포지션별 예치/인출 기록 (key: depositor 키 pda_n, value: DepositEvent 목록)
+pub const DEPOSIT_HISTORY_TABLE: &str = "deposit_history";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DepositError {
+    Storage(StorageError),
+    Validation(String),
+    Locked(u64),                        // 잠금 해제 시간
+    InsufficientBalance { available: u64, requested: u64 },
+    Unbacked { backed: u64, requested: u64 },   // 온체인 예치 계정 잔액을 넘는 포지션
+}
+
+impl fmt::Display for DepositError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepositError::Storage(e) => write!(f, "{}", e),
+            DepositError::Validation(msg) => write!(f, "Validation error: {}", msg),
+            DepositError::Locked(until) => write!(f, "Deposit is locked until {}", until),
+            DepositError::InsufficientBalance { available, requested } => {
+                write!(f, "Cannot withdraw {} lamports, only {} deposited", requested, available)
+            },
+            DepositError::Unbacked { backed, requested } => {
+                write!(f, "Position of {} lamports exceeds the {} lamports held on chain", requested, backed)
+            },
+        }
+    }
+}
+
+impl StdError for DepositError {}
+
+impl From<StorageError> for DepositError {
+    fn from(e: StorageError) -> Self {
+        DepositError::Storage(e)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositAction {
+    Deposit,
+    Withdraw,
+    Settle,                             // 라운드 정산으로 분배됨
+    Spend,                              // 가결된 지출 제안으로 차감됨
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositEvent {
+    pub action: DepositAction,
+    pub amount: u64,
+    pub balance: u64,                   // 처리 후 포지션 잔액
+    pub locked_until: u64,
+    pub at: u64,
+}
+
+// 지갑의 예치 포지션 (키는 pda_n)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DepositPosition {
+    pub key: String,
+    pub depositor: Depositor,
+}
+
+fn position_index_key(pda: &str, pubkey: &str) -> String {
+    format!("{}:{}", pda, pubkey)
+}
+
+// 인덱스에 기록된 포지션 키. 전액 인출된 포지션도 기록을 이어가기 위해 인덱스는 남겨 둠
+fn indexed_key(txn: &DatabaseTransaction<'_, '_>, pda: &str, pubkey: &str) -> Result<Option<String>, DepositError> {
+    Ok(txn.read(&position_index_key(pda, pubkey), DEPOSITOR_POSITION_TABLE).map_err(database_error)?
+        .map(|key| String::from_utf8_lossy(&key).to_string()))
+}
+
+// 인덱스가 없거나 어긋난 예전 데이터에서 같은 지갑의 포지션 행 (ID 오름차순)
+fn legacy_positions(txn: &DatabaseTransaction<'_, '_>, pda: &str, pubkey: &str) -> Result<Vec<DepositPosition>, DepositError> {
+    let prefix = format!("{}_", pda);
+    let mut matches = Vec::new();
+    for (key, value) in txn.read_all("depositor").map_err(database_error)? {
+        let Ok(key) = String::from_utf8(key) else {
+            continue;
+        };
+        let Some(id) = key.strip_prefix(&prefix).and_then(|id| id.parse::<u64>().ok()) else {
+            continue;
+        };
+        let depositor: Depositor = decode(&value)?;
+        if depositor.pubkey == pubkey {
+            matches.push((id, DepositPosition { key, depositor }));
+        }
+    }
+
+    matches.sort_by_key(|(id, _)| *id);
+    Ok(matches.into_iter().map(|(_, position)| position).collect())
+}
+
+fn indexed_position(txn: &DatabaseTransaction<'_, '_>, pda: &str, pubkey: &str) -> Result<Option<DepositPosition>, DepositError> {
+    let Some(key) = indexed_key(txn, pda, pubkey)? else {
+        return Ok(None);
+    };
+    let Some(data) = txn.read(&key, "depositor").map_err(database_error)? else {
+        return Ok(None);
+    };
+    let depositor: Depositor = decode(&data)?;
+    Ok((depositor.pubkey == pubkey).then_some(DepositPosition { key, depositor }))
+}
+
+// (pda, pubkey) 포지션 조회 (읽기 전용). 예전 데이터는 같은 지갑의 가장 작은 ID 행을 사용
+fn find_position(txn: &DatabaseTransaction<'_, '_>, pda: &str, pubkey: &str) -> Result<Option<DepositPosition>, DepositError> {
+    if let Some(position) = indexed_position(txn, pda, pubkey)? {
+        return Ok(Some(position));
+    }
+    Ok(legacy_positions(txn, pda, pubkey)?.into_iter().next())
+}
+
+// 쓰기 전 포지션 조회. 예전 데이터에 같은 지갑의 행이 여러 개면 가장 작은 ID 행으로 합치고
+// 나머지 행과 기록을 옮긴 뒤 지움. 합친 행 수만큼 depositor_count 감소
+fn take_position(txn: &DatabaseTransaction<'_, '_>, community: &mut Community, pda: &str, pubkey: &str) -> Result<Option<DepositPosition>, DepositError> {
+    if let Some(position) = indexed_position(txn, pda, pubkey)? {
+        return Ok(Some(position));
+    }
+
+    let mut positions = legacy_positions(txn, pda, pubkey)?.into_iter();
+    let Some(mut kept) = positions.next() else {
+        return Ok(None);
+    };
+
+    let mut history = read_history(txn, &kept.key)?;
+    let mut merged = false;
+    for duplicate in positions {
+        kept.depositor.amount = kept.depositor.amount.checked_add(duplicate.depositor.amount)
+            .ok_or_else(|| DepositError::Validation("Deposit amount overflows".to_string()))?;
+        kept.depositor.locked_until = kept.depositor.locked_until.max(duplicate.depositor.locked_until);
+        // 한 행이라도 아직 잠겨 있으면 합친 포지션도 잠금 해제 전
+        kept.depositor.unlocked_at = kept.depositor.unlocked_at.zip(duplicate.depositor.unlocked_at).map(|(a, b)| a.max(b));
+        history.extend(read_history(txn, &duplicate.key)?);
+
+        txn.delete(&duplicate.key, "depositor").map_err(database_error)?;
+        txn.delete(&duplicate.key, DEPOSIT_HISTORY_TABLE).map_err(database_error)?;
+        community.depositor_count = community.depositor_count.saturating_sub(1);
+        merged = true;
+    }
+
+    if merged {
+        txn.write(&kept.key, &encode(&kept.depositor)?, "depositor").map_err(database_error)?;
+        history.sort_by_key(|event| event.at);
+        txn.write(&kept.key, &encode(&history)?, DEPOSIT_HISTORY_TABLE).map_err(database_error)?;
+    }
+    txn.write(&position_index_key(pda, pubkey), &kept.key, DEPOSITOR_POSITION_TABLE).map_err(database_error)?;
+
+    Ok(Some(kept))
+}
+
+fn read_history(txn: &DatabaseTransaction<'_, '_>, key: &str) -> Result<Vec<DepositEvent>, DepositError> {
+    Ok(txn.read(key, DEPOSIT_HISTORY_TABLE).map_err(database_error)?
+        .map(|data| decode(&data))
+        .transpose()?
+        .unwrap_or_default())
+}
+
+fn append_history(txn: &DatabaseTransaction<'_, '_>, key: &str, event: DepositEvent) -> Result<(), DepositError> {
+    let mut history = read_history(txn, key)?;
+    history.push(event);
+    txn.write(key, &encode(&history)?, DEPOSIT_HISTORY_TABLE).map_err(database_error)?;
+    Ok(())
+}
+
+fn load_community(txn: &DatabaseTransaction<'_, '_>, pda: &str) -> Result<Community, DepositError> {
+    Ok(decode(&txn.read(pda, "community").map_err(database_error)?
+        .ok_or_else(|| not_found(format!("Community with PDA {} not found", pda)))?)?)
+}
+
+// 지갑의 포지션에 예치 금액을 더함. 포지션이 없으면 새로 만들고 depositor_count 증가.
+// 잠금 시간은 기존과 새 값 중 늦은 쪽을 사용
+pub fn deposit<T: SafeDatabase>(
+    database: &T,
+    pda: &str,
+    pubkey: &str,
+    amount: u64,
+    locked_until: u64,
+    now: u64,
+) -> Result<DepositPosition, DepositError> {
+    add_deposit(database, pda, pubkey, amount, locked_until, None, now)
+}
+
+// 온체인 예치 계정으로 확인한 예치. 합산한 포지션이 계정 잔액(backed)을 넘으면 거부
+pub fn deposit_backed<T: SafeDatabase>(
+    database: &T,
+    pda: &str,
+    pubkey: &str,
+    amount: u64,
+    locked_until: u64,
+    backed: u64,
+    now: u64,
+) -> Result<DepositPosition, DepositError> {
+    add_deposit(database, pda, pubkey, amount, locked_until, Some(backed), now)
+}
+
+fn add_deposit<T: SafeDatabase>(
+    database: &T,
+    pda: &str,
+    pubkey: &str,
+    amount: u64,
+    locked_until: u64,
+    backed: Option<u64>,
+    now: u64,
+) -> Result<DepositPosition, DepositError> {
+    if pubkey.is_empty() {
+        return Err(DepositError::Validation("Depositor pubkey cannot be empty".to_string()));
+    }
+    if amount == 0 {
+        return Err(DepositError::Validation("Deposit amount must be greater than zero".to_string()));
+    }
+
+    database.transaction(|txn| {
+        let mut community = load_community(txn, pda)?;
+
+        let mut position = match take_position(txn, &mut community, pda, pubkey)? {
+            Some(mut position) => {
+                position.depositor.amount = position.depositor.amount.checked_add(amount)
+                    .ok_or_else(|| DepositError::Validation("Deposit amount overflows".to_string()))?;
+                position.depositor.locked_until = position.depositor.locked_until.max(locked_until);
+                position
+            },
+            None => {
+                community.depositor_count += 1;
+                let key = match indexed_key(txn, pda, pubkey)? {
+                    Some(key) if txn.read(&key, "depositor").map_err(database_error)?.is_none() => key,
+                    _ => format!("{}_{}", pda, next_id(txn, "depositor", pda)?),
+                };
+                DepositPosition {
+                    key,
+                    depositor: Depositor {
+                        pubkey: pubkey.to_string(),
+                        amount,
+                        locked_until,
+                        voting_power: 0,
+                        unlocked_at: None,
+                    },
+                }
+            },
+        };
+
+        if let Some(backed) = backed.filter(|backed| position.depositor.amount > *backed) {
+            return Err(DepositError::Unbacked { backed, requested: position.depositor.amount });
+        }
+
+        position.depositor.voting_power = voting_power(&community.voting_power, &position.depositor, now);
+        if position.depositor.locked_until > now {
+            position.depositor.unlocked_at = None;
+        }
+
+        community.total_deposit = community.total_deposit.checked_add(amount)
+            .ok_or_else(|| DepositError::Validation("Total deposit overflows".to_string()))?;
+        community.last_activity_timestamp = now;
+
+        txn.write(&position.key, &encode(&position.depositor)?, "depositor").map_err(database_error)?;
+        txn.write(&position_index_key(pda, pubkey), &position.key, DEPOSITOR_POSITION_TABLE).map_err(database_error)?;
+        txn.write(pda, &encode(&community)?, "community").map_err(database_error)?;
+        append_history(txn, &position.key, DepositEvent {
+            action: DepositAction::Deposit,
+            amount,
+            balance: position.depositor.amount,
+            locked_until: position.depositor.locked_until,
+            at: now,
+        })?;
+
+        Ok(position)
+    }).map_err(database_error)?
+}
+
+// 잠금 기간이 지난 포지션에서 인출. 잔액이 0이 되면 포지션 행을 지우고 depositor_count 감소
+pub fn withdraw<T: SafeDatabase>(
+    database: &T,
+    pda: &str,
+    pubkey: &str,
+    amount: u64,
+    now: u64,
+) -> Result<DepositPosition, DepositError> {
+    if amount == 0 {
+        return Err(DepositError::Validation("Withdrawal amount must be greater than zero".to_string()));
+    }
+
+    database.transaction(|txn| {
+        let mut community = load_community(txn, pda)?;
+        let mut position = take_position(txn, &mut community, pda, pubkey)?
+            .ok_or_else(|| not_found(format!("Wallet {} has no deposit in community {}", pubkey, pda)))?;
+
+        if position.depositor.locked_until > now {
+            return Err(DepositError::Locked(position.depositor.locked_until));
+        }
+        if amount > position.depositor.amount {
+            return Err(DepositError::InsufficientBalance {
+                available: position.depositor.amount,
+                requested: amount,
+            });
+        }
+
+        position.depositor.amount -= amount;
+        position.depositor.voting_power = voting_power(&community.voting_power, &position.depositor, now);
+        community.total_deposit = community.total_deposit.saturating_sub(amount);
+
+        if position.depositor.amount == 0 {
+            txn.delete(&position.key, "depositor").map_err(database_error)?;
+            community.depositor_count = community.depositor_count.saturating_sub(1);
+        } else {
+            txn.write(&position.key, &encode(&position.depositor)?, "depositor").map_err(database_error)?;
+        }
+
+        txn.write(pda, &encode(&community)?, "community").map_err(database_error)?;
+        append_history(txn, &position.key, DepositEvent {
+            action: DepositAction::Withdraw,
+            amount,
+            balance: position.depositor.amount,
+            locked_until: position.depositor.locked_until,
+            at: now,
+        })?;
+
+        Ok(position)
+    }).map_err(database_error)?
+}
+
+// 라운드 정산으로 분배한 커뮤니티의 모든 포지션을 비움 (라운드 종료 트랜잭션 안에서 호출).
+// 포지션 행은 지우고 기록에 정산을 남기며, 커뮤니티 예치 합계와 예치자 수에서 뺌. 비운 포지션 반환
+pub fn settle_positions(
+    txn: &DatabaseTransaction<'_, '_>,
+    pda: &str,
+    community: &mut Community,
+    now: u64,
+) -> Result<Vec<Depositor>, DepositError> {
+    let prefix = format!("{}_", pda);
+    let mut settled = Vec::new();
+
+    for (key, value) in txn.read_all("depositor").map_err(database_error)? {
+        let Ok(key) = String::from_utf8(key) else {
+            continue;
+        };
+        if !key.starts_with(&prefix) {
+            continue;
+        }
+        let depositor: Depositor = decode(&value)?;
+
+        txn.delete(&key, "depositor").map_err(database_error)?;
+        append_history(txn, &key, DepositEvent {
+            action: DepositAction::Settle,
+            amount: depositor.amount,
+            balance: 0,
+            locked_until: depositor.locked_until,
+            at: now,
+        })?;
+
+        community.total_deposit = community.total_deposit.saturating_sub(depositor.amount);
+        community.depositor_count = community.depositor_count.saturating_sub(1);
+        settled.push(depositor);
+    }
+
+    Ok(settled)
+}
+
+// 가결된 지출 금액을 예치 금액 비례로 커뮤니티 포지션에서 차감 (제안 마감 트랜잭션 안에서 호출).
+// 잔액이 0이 된 포지션은 지우고 커뮤니티 합계도 함께 줄임. 포지션 키별 차감액 반환
+pub fn debit_positions(
+    txn: &DatabaseTransaction<'_, '_>,
+    pda: &str,
+    community: &mut Community,
+    amount: u64,
+    now: u64,
+) -> Result<Vec<(String, u64)>, DepositError> {
+    let prefix = format!("{}_", pda);
+    let mut positions = BTreeMap::new();
+    for (key, value) in txn.read_all("depositor").map_err(database_error)? {
+        let Ok(key) = String::from_utf8(key) else {
+            continue;
+        };
+        if key.starts_with(&prefix) {
+            positions.insert(key, decode::<Depositor>(&value)?);
+        }
+    }
+
+    let available: u64 = positions.values().map(|depositor| depositor.amount).sum();
+    if amount > available {
+        return Err(DepositError::InsufficientBalance { available, requested: amount });
+    }
+
+    let weights = positions.iter().map(|(key, depositor)| (key.clone(), depositor.amount as u128)).collect();
+    let (shares, _) = split_by_weight(amount, weights);
+
+    let mut debits = Vec::new();
+    for share in shares {
+        let Some(mut depositor) = positions.remove(&share.pubkey) else {
+            continue;
+        };
+        depositor.amount = depositor.amount.saturating_sub(share.lamports);
+        depositor.voting_power = voting_power(&community.voting_power, &depositor, now);
+
+        if depositor.amount == 0 {
+            txn.delete(&share.pubkey, "depositor").map_err(database_error)?;
+            community.depositor_count = community.depositor_count.saturating_sub(1);
+        } else {
+            txn.write(&share.pubkey, &encode(&depositor)?, "depositor").map_err(database_error)?;
+        }
+        append_history(txn, &share.pubkey, DepositEvent {
+            action: DepositAction::Spend,
+            amount: share.lamports,
+            balance: depositor.amount,
+            locked_until: depositor.locked_until,
+            at: now,
+        })?;
+
+        community.total_deposit = community.total_deposit.saturating_sub(share.lamports);
+        debits.push((share.pubkey, share.lamports));
+    }
+
+    Ok(debits)
+}
+
+// 지갑 포지션의 예치/인출 기록 (오래된 순). 전액 인출된 포지션도 조회 가능
+pub fn deposit_history<T: SafeDatabase>(database: &T, pda: &str, pubkey: &str) -> Result<(String, Vec<DepositEvent>), DepositError> {
+    database.transaction(|txn| {
+        let key = match find_position(txn, pda, pubkey)? {
+            Some(position) => position.key,
+            None => indexed_key(txn, pda, pubkey)?
+                .ok_or_else(|| not_found(format!("Wallet {} has no deposit in community {}", pubkey, pda)))?,
+        };
+        Ok((key, read_history(txn, &key)?))
+    }).map_err(database_error)?
+}
+
+// 잠금 기간(locked_until)이 지난 예치를 잠금 해제로 기록. 해제된 키(pda_n) 목록 반환
+pub fn unlock_expired_deposits<T: SafeDatabase>(database: &T, now: u64) -> Result<Vec<String>, DepositError> {
+    database.transaction(|txn| {
+        let mut unlocked = Vec::new();
+
+        for (key, value) in txn.read_all("depositor").map_err(database_error)? {
+            let Ok(key) = String::from_utf8(key) else {
+                continue;
+            };
+            let mut depositor: Depositor = decode(&value)?;
+            if depositor.unlocked_at.is_some() || depositor.locked_until > now {
+                continue;
+            }
+
+            depositor.unlocked_at = Some(now);
+            txn.write(&key, &encode(&depositor)?, "depositor").map_err(database_error)?;
+            unlocked.push(key);
+        }
+
+        unlocked.sort();
+        Ok(unlocked)
+    }).map_err(database_error)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{community, depositor, temp_db};
+    use turtle_database::basic_db::InnerDatabase;
+
+    fn setup() -> Result<(tempfile::TempDir, InnerDatabase), Box<dyn std::error::Error>> {
+        let (temp_dir, db) = temp_db()?;
+        db.write("pda1", &encode(&community())?, "community")?;
+
+        Ok((temp_dir, db))
+    }
+
+    fn load_community(db: &InnerDatabase) -> Community {
+        decode(&db.read("pda1", "community").unwrap().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_top_ups_aggregate_into_one_position() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+
+        let first = deposit(&db, "pda1", "alice", 100, 500, 10)?;
+        let second = deposit(&db, "pda1", "alice", 50, 300, 20)?;
+        deposit(&db, "pda1", "bob", 30, 0, 30)?;
+
+        assert_eq!(first.key, second.key);
+        assert_eq!(second.depositor.amount, 150);
+        assert_eq!(second.depositor.voting_power, 150);
+        assert_eq!(second.depositor.locked_until, 500);
+
+        let community = load_community(&db);
+        assert_eq!((community.total_deposit, community.depositor_count), (180, 2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_withdraw_enforces_lock_and_keeps_totals_exact() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        deposit(&db, "pda1", "alice", 100, 500, 10)?;
+
+        assert_eq!(withdraw(&db, "pda1", "alice", 10, 499).err(), Some(DepositError::Locked(500)));
+        assert_eq!(
+            withdraw(&db, "pda1", "alice", 101, 500).err(),
+            Some(DepositError::InsufficientBalance { available: 100, requested: 101 }),
+        );
+
+        assert_eq!(withdraw(&db, "pda1", "alice", 40, 500)?.depositor.voting_power, 60);
+        let position = withdraw(&db, "pda1", "alice", 60, 600)?;
+        assert!(db.read(&position.key, "depositor")?.is_none());
+        let community = load_community(&db);
+        assert_eq!((community.total_deposit, community.depositor_count), (0, 0));
+
+        // 다시 예치하면 같은 포지션의 기록을 이어감
+        assert_eq!(deposit(&db, "pda1", "alice", 5, 0, 700)?.key, position.key);
+        let (_, history) = deposit_history(&db, "pda1", "alice")?;
+        let actions: Vec<(DepositAction, u64, u64)> = history.iter()
+            .map(|event| (event.action, event.amount, event.balance))
+            .collect();
+        assert_eq!(actions, vec![
+            (DepositAction::Deposit, 100, 100),
+            (DepositAction::Withdraw, 40, 60),
+            (DepositAction::Withdraw, 60, 0),
+            (DepositAction::Deposit, 5, 5),
+        ]);
+        assert_eq!(load_community(&db).depositor_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_backed_deposit_cannot_exceed_chain_balance() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+
+        deposit_backed(&db, "pda1", "alice", 100, 0, 150, 10)?;
+        assert_eq!(
+            deposit_backed(&db, "pda1", "alice", 60, 0, 150, 20).err(),
+            Some(DepositError::Unbacked { backed: 150, requested: 160 }),
+        );
+        assert_eq!(deposit_backed(&db, "pda1", "alice", 50, 0, 150, 30)?.depositor.amount, 150);
+        assert_eq!(load_community(&db).total_deposit, 150);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_legacy_positions_are_merged() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+
+        // 인덱스 도입 전 같은 지갑으로 두 번 저장된 행
+        let legacy = Community {
+            total_deposit: 130,
+            depositor_count: 2,
+            ..community()
+        };
+        db.write("pda1", &encode(&legacy)?, "community")?;
+        for (key, amount, locked_until) in [("pda1_2", 100, 500), ("pda1_5", 30, 900)] {
+            db.write(key, &encode(&Depositor { locked_until, ..depositor("alice", amount) })?, "depositor")?;
+        }
+
+        let position = deposit(&db, "pda1", "alice", 20, 0, 10)?;
+        assert_eq!(position.key, "pda1_2");
+        assert_eq!((position.depositor.amount, position.depositor.locked_until), (150, 900));
+        assert!(db.read("pda1_5", "depositor")?.is_none());
+
+        let community = load_community(&db);
+        assert_eq!((community.total_deposit, community.depositor_count), (150, 1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unlock_expired_deposits_once() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = temp_db()?;
+
+        for (key, locked_until) in [("pda1_1", 100), ("pda1_2", 500)] {
+            db.write(key, &encode(&Depositor { locked_until, ..depositor(key, 10) })?, "depositor")?;
+        }
+
+        assert_eq!(unlock_expired_deposits(&db, 200)?, vec!["pda1_1".to_string()]);
+        // 이미 해제된 예치는 다시 처리하지 않음
+        assert!(unlock_expired_deposits(&db, 300)?.is_empty());
+
+        let depositor: Depositor = decode(&db.read("pda1_1", "depositor")?.unwrap())?;
+        assert_eq!(depositor.unlocked_at, Some(200));
+
+        Ok(())
+    }
+}
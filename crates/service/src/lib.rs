@@ -1,4 +1,18 @@
 mod handler;
 pub mod parser;
+pub mod avatar;
+pub mod lifecycle;
+pub mod deposit;
+pub mod sequence;
+pub mod round;
+pub mod settlement;
+pub mod claim;
+pub mod voting_power;
+pub mod delegation;
+pub mod snapshot;
+pub mod preview;
+pub mod validation;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
 mod config;
 
@@ -0,0 +1,410 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::fmt;
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error::{database_error, decode, encode, StorageError};
+use crate::parser::community::{Community, Depositor, GovernanceRules, Proposal, ProposalKind, ProposalOutcome, VotingPowerConfig};
+use crate::voting_power::voting_power;
+use crate::snapshot::read_snapshot;
+use crate::deposit::{debit_positions, DepositError};
+
+// 가결된 지출 제안 기록 (key: 제안 키 pda_n). 전송은 온체인에서 recipient에게 실행
+pub const TREASURY_SPEND_TABLE: &str = "treasury_spend";
+
+pub type LifecycleError = StorageError;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreasurySpend {
+    pub pda: String,
+    pub proposal: String,               // pda_n
+    pub recipient: String,
+    pub amount: u64,
+    pub debits: Vec<(String, u64)>,     // 포지션 키(pda_n)별 차감액
+    pub approved_at: u64,
+}
+
+// 커뮤니티 거버넌스 규칙으로 거부된 제안 생성
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProposalRejection {
+    VotingPeriodTooShort { min_secs: u64, requested_secs: u64 },
+    TooManyActiveProposals { max: u64 },
+}
+
+impl fmt::Display for ProposalRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProposalRejection::VotingPeriodTooShort { min_secs, requested_secs } => write!(
+                f, "Voting period of {} seconds is shorter than the community minimum of {} seconds", requested_secs, min_secs
+            ),
+            ProposalRejection::TooManyActiveProposals { max } => write!(
+                f, "Community already has the maximum of {} active proposals", max
+            ),
+        }
+    }
+}
+
+impl StdError for ProposalRejection {}
+
+// 이번 실행에서 마감된 제안
+#[derive(Debug, PartialEq, Eq)]
+pub struct ClosedProposal {
+    pub key: String,                // pda_n
+    pub outcome: ProposalOutcome,
+}
+
+// 제안 키(pda_n)에서 커뮤니티 PDA 추출
+fn proposal_pda(key: &str) -> Option<&str> {
+    key.rsplit_once('_').map(|(pda, _)| pda)
+}
+
+// 가결된 제안을 커뮤니티 필드에 반영. 적용할 수 없는 제안이면 false
+pub fn apply_proposal(community: &mut Community, proposal: &Proposal) -> bool {
+    match &proposal.kind {
+        ProposalKind::TimeLimit(secs) => community.time_limit = *secs,
+        ProposalKind::BaseFee(fee) => community.base_fee = *fee,
+        ProposalKind::AiModeration(enabled) => community.ai_moderation = *enabled,
+        ProposalKind::DepositShare(share) if *share <= 100 => community.deposit_share = *share,
+        ProposalKind::AdminTransfer(admin) if !admin.is_empty() => community.admin = admin.clone(),
+        ProposalKind::Governance(rules) => community.governance = *rules,
+        // 예치금 범위 안인지만 확인. 포지션 차감과 기록은 close_proposal에서, 전송은 온체인에서 실행
+        ProposalKind::TreasurySpend { amount, .. } if *amount <= community.total_deposit => {},
+        _ => return false,
+    }
+    true
+}
+
+// 가결된 지출 금액을 포지션에서 차감하고 수령자와 함께 기록. 포지션 잔액이 모자라면 false
+fn spend_treasury(
+    txn: &DatabaseTransaction<'_, '_>,
+    key: &str,
+    pda: &str,
+    community: &mut Community,
+    recipient: &str,
+    amount: u64,
+    now: u64,
+) -> Result<bool, LifecycleError> {
+    let debits = match debit_positions(txn, pda, community, amount, now) {
+        Ok(debits) => debits,
+        Err(DepositError::InsufficientBalance { .. }) => return Ok(false),
+        Err(DepositError::Storage(e)) => return Err(e),
+        Err(e) => return Err(database_error(e)),
+    };
+
+    let spend = TreasurySpend {
+        pda: pda.to_string(),
+        proposal: key.to_string(),
+        recipient: recipient.to_string(),
+        amount,
+        debits,
+        approved_at: now,
+    };
+    txn.write(key, &encode(&spend)?, TREASURY_SPEND_TABLE).map_err(database_error)?;
+    Ok(true)
+}
+
+// 새 제안이 커뮤니티의 최소 투표 기간과 동시 활성 제안 수 제한을 지키는지 확인
+pub fn check_new_proposal(community: &Community, proposal: &Proposal, now: u64) -> Result<(), ProposalRejection> {
+    let rules = &community.governance;
+
+    let requested_secs = proposal.voting_end_time.saturating_sub(now);
+    if requested_secs < rules.min_voting_period_secs {
+        return Err(ProposalRejection::VotingPeriodTooShort {
+            min_secs: rules.min_voting_period_secs,
+            requested_secs,
+        });
+    }
+    if community.active_proposal_count >= rules.max_active_proposals {
+        return Err(ProposalRejection::TooManyActiveProposals {
+            max: rules.max_active_proposals,
+        });
+    }
+
+    Ok(())
+}
+
+// 정족수와 찬성 비율 규칙으로 결과 판정
+pub fn decide(proposal: &Proposal, total_power: u64, rules: &GovernanceRules) -> ProposalOutcome {
+    let cast = proposal.yes_votes as u128 + proposal.no_votes as u128;
+    let required = total_power as u128 * rules.quorum_percent as u128;
+
+    if cast == 0 || cast * 100 < required {
+        ProposalOutcome::QuorumNotMet
+    } else if proposal.yes_votes as u128 * 100 > cast * rules.approval_percent as u128 {
+        ProposalOutcome::Passed
+    } else {
+        ProposalOutcome::Rejected
+    }
+}
+
+// 커뮤니티 투표 파워 모델로 다시 계산한 전체 voting_power
+fn total_voting_power(
+    txn: &DatabaseTransaction<'_, '_>,
+    pda: &str,
+    config: &VotingPowerConfig,
+    now: u64,
+) -> Result<u64, LifecycleError> {
+    let prefix = format!("{}_", pda);
+    let mut total: u64 = 0;
+
+    for (key, value) in txn.read_all("depositor").map_err(database_error)? {
+        if key.starts_with(prefix.as_bytes()) {
+            let depositor: Depositor = decode(&value)?;
+            total = total.saturating_add(voting_power(config, &depositor, now));
+        }
+    }
+
+    Ok(total)
+}
+
+// 투표 기간이 끝난 제안 하나를 마감. 제안 결과, 커뮤니티 변경, 카운터 감소를 하나의 트랜잭션으로
+// 기록하므로 중간에 중단되어도 반쯤 적용된 상태가 남지 않고, 이미 마감된 제안은 건너뜀
+pub fn close_proposal<T: SafeDatabase>(
+    database: &T,
+    key: &str,
+    now: u64,
+) -> Result<Option<ProposalOutcome>, LifecycleError> {
+    let Some(pda) = proposal_pda(key) else {
+        return Ok(None);
+    };
+
+    database.transaction(|txn| {
+        let Some(proposal_data) = txn.read(key, "proposal").map_err(database_error)? else {
+            return Ok(None);
+        };
+        let mut proposal: Proposal = decode(&proposal_data)?;
+
+        if proposal.outcome.is_some() || proposal.is_executed || now <= proposal.voting_end_time {
+            return Ok(None);
+        }
+
+        let community_data = txn.read(pda, "community").map_err(database_error)?;
+        let mut community: Option<Community> = community_data.map(|data| decode(&data)).transpose()?;
+
+        // 제안 생성 시점 스냅샷 기준. 스냅샷 도입 전 제안은 현재 예치 기준으로 계산
+        let total_power = match read_snapshot(txn, key).map_err(database_error)? {
+            Some(snapshot) => snapshot.total_power,
+            None => {
+                let config = community.as_ref().map(|community| community.voting_power.clone()).unwrap_or_default();
+                total_voting_power(txn, pda, &config, now)?
+            },
+        };
+        // 생성 시점 규칙 기준. 규칙 스냅샷 도입 전 제안은 현재 커뮤니티 규칙
+        let rules = proposal.rules
+            .or_else(|| community.as_ref().map(|community| community.governance))
+            .unwrap_or_default();
+        let mut outcome = decide(&proposal, total_power, &rules);
+        if outcome == ProposalOutcome::Passed {
+            let executed = match (community.as_mut(), &proposal.kind) {
+                (Some(community), ProposalKind::TreasurySpend { recipient, amount }) => {
+                    apply_proposal(community, &proposal) && spend_treasury(txn, key, pda, community, recipient, *amount, now)?
+                },
+                (Some(community), _) => apply_proposal(community, &proposal),
+                (None, _) => false,
+            };
+            if executed {
+                proposal.is_executed = true;
+            } else {
+                outcome = ProposalOutcome::Invalid;
+            }
+        }
+        proposal.outcome = Some(outcome);
+
+        if let Some(community) = community.as_mut() {
+            community.active_proposal_count = community.active_proposal_count.saturating_sub(1);
+            txn.write(pda, &encode(community)?, "community").map_err(database_error)?;
+        }
+        txn.write(key, &encode(&proposal)?, "proposal").map_err(database_error)?;
+
+        Ok(Some(outcome))
+    }).map_err(database_error)?
+}
+
+// 모든 만료 제안을 마감. 반복 실행해도 결과가 같음
+pub fn close_expired_proposals<T: SafeDatabase>(
+    database: &T,
+    now: u64,
+) -> Result<Vec<ClosedProposal>, LifecycleError> {
+    let mut keys = Vec::new();
+    for (key, value) in database.read_all("proposal").map_err(database_error)? {
+        let Ok(key) = String::from_utf8(key) else {
+            continue;
+        };
+        let proposal: Proposal = decode(&value)?;
+        if proposal.outcome.is_none() && !proposal.is_executed && now > proposal.voting_end_time {
+            keys.push(key);
+        }
+    }
+    keys.sort();
+
+    let mut closed = Vec::new();
+    for key in keys {
+        if let Some(outcome) = close_proposal(database, &key, now)? {
+            closed.push(ClosedProposal { key, outcome });
+        }
+    }
+
+    Ok(closed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{community, depositor, proposal, temp_db};
+    use serde::de::DeserializeOwned;
+    use turtle_database::basic_db::InnerDatabase;
+
+    // 활성 제안 2개와 예치자 "voter"(100) 한 명이 있는 커뮤니티
+    fn setup() -> Result<(tempfile::TempDir, InnerDatabase), Box<dyn std::error::Error>> {
+        let (temp_dir, db) = temp_db()?;
+
+        db.write("pda1", &encode(&Community { active_proposal_count: 2, depositor_count: 1, ..community() })?, "community")?;
+        db.write("pda1_1", &encode(&depositor("voter", 100))?, "depositor")?;
+
+        Ok((temp_dir, db))
+    }
+
+    fn load<V: DeserializeOwned>(db: &InnerDatabase, key: &str, table: &str) -> V {
+        decode(&db.read(key, table).unwrap().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_decide_applies_quorum_and_majority() {
+        let rules = GovernanceRules::default();
+
+        assert_eq!(decide(&Proposal { yes_votes: 6, no_votes: 4, ..proposal(ProposalKind::TimeLimit(0)) }, 100, &rules), ProposalOutcome::Passed);
+        assert_eq!(decide(&Proposal { yes_votes: 5, no_votes: 5, ..proposal(ProposalKind::TimeLimit(0)) }, 100, &rules), ProposalOutcome::Rejected);
+        assert_eq!(decide(&Proposal { yes_votes: 9, ..proposal(ProposalKind::TimeLimit(0)) }, 100, &rules), ProposalOutcome::QuorumNotMet);
+        assert_eq!(decide(&proposal(ProposalKind::TimeLimit(0)), 0, &rules), ProposalOutcome::QuorumNotMet);
+
+        // 2/3 찬성이 필요한 커뮤니티
+        let rules = GovernanceRules { quorum_percent: 50, approval_percent: 66, ..rules };
+        assert_eq!(decide(&Proposal { yes_votes: 30, no_votes: 20, ..proposal(ProposalKind::TimeLimit(0)) }, 100, &rules), ProposalOutcome::Rejected);
+        assert_eq!(decide(&Proposal { yes_votes: 34, no_votes: 16, ..proposal(ProposalKind::TimeLimit(0)) }, 100, &rules), ProposalOutcome::Passed);
+        assert_eq!(decide(&Proposal { yes_votes: 40, ..proposal(ProposalKind::TimeLimit(0)) }, 100, &rules), ProposalOutcome::QuorumNotMet);
+    }
+
+    #[test]
+    fn test_new_proposals_follow_governance_rules() {
+        let rules = GovernanceRules { min_voting_period_secs: 100, max_active_proposals: 2, ..GovernanceRules::default() };
+        let mut community = Community { active_proposal_count: 2, governance: rules, ..community() };
+
+        let short = Proposal { voting_end_time: 1_050, ..proposal(ProposalKind::BaseFee(1)) };
+        assert_eq!(
+            check_new_proposal(&community, &short, 1_000),
+            Err(ProposalRejection::VotingPeriodTooShort { min_secs: 100, requested_secs: 50 }),
+        );
+
+        // 이미 2개의 활성 제안이 있음
+        let valid = Proposal { voting_end_time: 1_100, ..proposal(ProposalKind::BaseFee(1)) };
+        assert_eq!(check_new_proposal(&community, &valid, 1_000), Err(ProposalRejection::TooManyActiveProposals { max: 2 }));
+        community.active_proposal_count = 1;
+        assert_eq!(check_new_proposal(&community, &valid, 1_000), Ok(()));
+    }
+
+    #[test]
+    fn test_close_expired_proposals_is_idempotent() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        db.write("pda1_1", &encode(&Proposal { voting_end_time: 100, yes_votes: 30, no_votes: 10, ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+        db.write("pda1_2", &encode(&Proposal { voting_end_time: 100, yes_votes: 1, ..proposal(ProposalKind::TimeLimit(60)) })?, "proposal")?;
+
+        // 투표 기간 중에는 아무것도 마감하지 않음
+        assert!(close_expired_proposals(&db, 100)?.is_empty());
+
+        let closed = close_expired_proposals(&db, 101)?;
+        assert_eq!(closed, vec![
+            ClosedProposal { key: "pda1_1".to_string(), outcome: ProposalOutcome::Passed },
+            ClosedProposal { key: "pda1_2".to_string(), outcome: ProposalOutcome::QuorumNotMet },
+        ]);
+
+        let community: Community = load(&db, "pda1", "community");
+        assert_eq!(community.base_fee, 500);
+        assert_eq!(community.time_limit, 3600);
+        assert_eq!(community.active_proposal_count, 0);
+
+        let passed: Proposal = load(&db, "pda1_1", "proposal");
+        assert!(passed.is_executed);
+        let failed: Proposal = load(&db, "pda1_2", "proposal");
+        assert!(!failed.is_executed);
+        assert_eq!(failed.outcome, Some(ProposalOutcome::QuorumNotMet));
+
+        // 다시 실행해도 카운터나 값이 바뀌지 않음
+        assert!(close_expired_proposals(&db, 200)?.is_empty());
+        let community: Community = load(&db, "pda1", "community");
+        assert_eq!(community.active_proposal_count, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_quorum_uses_snapshot_taken_at_creation() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        db.write("pda1_1", &encode(&Proposal { yes_votes: 30, no_votes: 10, ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+        db.transaction(|txn| crate::snapshot::record_snapshot(txn, "pda1", "pda1_1", 0))??;
+
+        // 제안 이후의 대규모 예치는 정족수 기준을 바꾸지 않음
+        db.write("pda1_2", &encode(&depositor("whale", 10_000))?, "depositor")?;
+
+        assert_eq!(close_proposal(&db, "pda1_1", 101)?, Some(ProposalOutcome::Passed));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_proposals_close_under_rules_from_creation() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        // 생성 당시 과반 규칙이었던 제안은 이후 2/3 규칙으로 바뀌어도 과반으로 가결
+        let strict = GovernanceRules { approval_percent: 66, ..GovernanceRules::default() };
+        let snapshot = Proposal { yes_votes: 60, no_votes: 40, rules: Some(GovernanceRules::default()), ..proposal(ProposalKind::Governance(strict)) };
+        db.write("pda1_1", &encode(&snapshot)?, "proposal")?;
+        db.write("pda1_2", &encode(&Proposal { yes_votes: 60, no_votes: 40, rules: Some(GovernanceRules::default()), ..proposal(ProposalKind::BaseFee(500)) })?, "proposal")?;
+
+        assert_eq!(close_proposal(&db, "pda1_1", 101)?, Some(ProposalOutcome::Passed));
+        let community: Community = load(&db, "pda1", "community");
+        assert_eq!(community.governance, strict);
+
+        assert_eq!(close_proposal(&db, "pda1_2", 101)?, Some(ProposalOutcome::Passed));
+        let community: Community = load(&db, "pda1", "community");
+        assert_eq!(community.base_fee, 500);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_proposal_type_is_not_executed() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        db.write("pda1_1", &encode(&Proposal { yes_votes: 50, ..proposal(ProposalKind::Unknown { proposal_type: 9, new_value: 1 }) })?, "proposal")?;
+
+        assert_eq!(close_proposal(&db, "pda1_1", 101)?, Some(ProposalOutcome::Invalid));
+        let proposal: Proposal = load(&db, "pda1_1", "proposal");
+        assert!(!proposal.is_executed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_passed_treasury_spend_debits_positions() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup()?;
+        db.write("pda1", &encode(&Community { total_deposit: 300, active_proposal_count: 2, depositor_count: 2, ..community() })?, "community")?;
+        db.write("pda1_2", &encode(&depositor("whale", 200))?, "depositor")?;
+        let spend = |amount| ProposalKind::TreasurySpend { recipient: "recipient".to_string(), amount };
+        db.write("pda1_1", &encode(&Proposal { yes_votes: 300, ..proposal(spend(150)) })?, "proposal")?;
+        db.write("pda1_3", &encode(&Proposal { yes_votes: 300, ..proposal(spend(1_000)) })?, "proposal")?;
+
+        assert_eq!(close_proposal(&db, "pda1_1", 101)?, Some(ProposalOutcome::Passed));
+        assert_eq!(close_proposal(&db, "pda1_3", 101)?, Some(ProposalOutcome::Invalid));
+
+        // 지출 금액은 예치 비례로 포지션에서 빠지고 수령자와 함께 기록됨
+        let voter: Depositor = load(&db, "pda1_1", "depositor");
+        let whale: Depositor = load(&db, "pda1_2", "depositor");
+        assert_eq!((voter.amount, whale.amount), (50, 100));
+        let community: Community = load(&db, "pda1", "community");
+        assert_eq!(community.total_deposit, 150);
+
+        let record: TreasurySpend = load(&db, "pda1_1", TREASURY_SPEND_TABLE);
+        assert_eq!((record.recipient.as_str(), record.amount, record.approved_at), ("recipient", 150, 101));
+        assert_eq!(record.debits, vec![("pda1_1".to_string(), 50), ("pda1_2".to_string(), 100)]);
+        assert!(db.read("pda1_3", TREASURY_SPEND_TABLE)?.is_none());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use sol::pubkey::parse_pubkey;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Community {
     pub admin: String,                  // DAO 관리자 공개키
     pub time_limit: u64,                // 시간 제한(초)
@@ -12,6 +13,68 @@ pub struct Community {
     pub active_proposal_count: u64,     // 활성 제안 수
     pub content_count: u64,             // 콘텐츠 수
     pub depositor_count: u64,           // 예치자 수
+    #[serde(default)]
+    pub weighted_content_votes: bool,   // 콘텐츠 투표를 예치자 voting_power로 가중할지 여부
+    #[serde(default)]
+    pub round_winner: RoundWinnerRule,  // 라운드 종료 시 우승 콘텐츠 선정 기준
+    #[serde(default)]
+    pub voting_power: VotingPowerConfig,    // 예치자 voting_power 계산 모델
+    #[serde(default)]
+    pub governance: GovernanceRules,    // 제안 생성/마감 규칙
+}
+
+// 전체 voting_power 대비 최소 투표 비율(%)
+pub const DEFAULT_QUORUM_PERCENT: u64 = 10;
+// 찬반 합계 대비 찬성 비율(%)이 이 값을 넘어야 가결 (50이면 과반)
+pub const DEFAULT_APPROVAL_PERCENT: u64 = 50;
+pub const DEFAULT_MIN_VOTING_PERIOD_SECS: u64 = 60 * 60;
+pub const DEFAULT_MAX_ACTIVE_PROPOSALS: u64 = 10;
+
+// 커뮤니티별 거버넌스 규칙 (turtle_service::lifecycle에서 적용)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GovernanceRules {
+    pub quorum_percent: u64,            // 찬반 합계가 전체 voting_power의 이 비율 이상이어야 유효
+    pub approval_percent: u64,          // 찬성이 찬반 합계의 이 비율을 넘어야 가결
+    pub min_voting_period_secs: u64,    // 생성 시점부터 voting_end_time까지 최소 기간
+    pub max_active_proposals: u64,      // 동시에 투표 중일 수 있는 제안 수
+}
+
+impl Default for GovernanceRules {
+    fn default() -> Self {
+        Self {
+            quorum_percent: DEFAULT_QUORUM_PERCENT,
+            approval_percent: DEFAULT_APPROVAL_PERCENT,
+            min_voting_period_secs: DEFAULT_MIN_VOTING_PERIOD_SECS,
+            max_active_proposals: DEFAULT_MAX_ACTIVE_PROPOSALS,
+        }
+    }
+}
+
+// 커뮤니티별 투표 파워 모델 설정 (turtle_service::voting_power에서 계산)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum VotingPowerConfig {
+    #[default]
+    Linear,                             // 예치 금액 그대로
+    Quadratic,                          // 예치 금액의 제곱근
+    LockWeighted {                      // 남은 잠금 기간에 비례해 최대 max_bonus_percent만큼 가산
+        max_lock_secs: u64,
+        max_bonus_percent: u64,
+    },
+    Capped {                            // base 모델 결과를 cap 이하로 제한
+        cap: u64,
+        #[serde(default)]
+        base: Box<VotingPowerConfig>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundWinnerRule {
+    #[default]
+    Votes,                              // 득표 수가 가장 많은 콘텐츠 (동점이면 최신 콘텐츠)
+    Recency,                            // 마지막으로 올라온 콘텐츠
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -30,21 +93,221 @@ pub struct Depositor {
     pub pubkey: String,                 // 예치자 공개키
     pub amount: u64,                    // 예치 금액
     pub locked_until: u64,              // 잠금 해제 시간
-    pub voting_power: u64,              // 투표 파워(예치금액 기반)
+    pub voting_power: u64,              // 투표 파워(커뮤니티 voting_power 모델로 서버에서 계산)
+    #[serde(default)]
+    pub unlocked_at: Option<u64>,       // 스케줄러가 잠금 해제를 처리한 시간 (None이면 잠금 중)
 }
 
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "ProposalRecord", into = "ProposalRecord")]
 pub struct Proposal {
     pub id: u64,                        // 제안 ID
-    pub proposal_type: u8,    // 제안 유형(TimeLimit - 0, BaseFee - 1, AiModeration - 2)
-    pub new_value: u64,                 // 새 값
+    pub kind: ProposalKind,             // 제안 유형과 새 값
     pub voting_end_time: u64,           // 투표 종료 시간
     pub yes_votes: u64,                 // 찬성표
     pub no_votes: u64,                  // 반대표
     pub is_executed: bool,              // 실행 여부
+    pub outcome: Option<ProposalOutcome>,   // 마감 결과 (None이면 투표 진행 중)
+    pub rules: Option<GovernanceRules>, // 생성 시점의 커뮤니티 거버넌스 규칙 (도입 전 제안은 None)
+}
+
+// 저장/API 표현. 예전 행과 클라이언트를 위해 proposal_type/new_value도 읽고 쓰며, kind가 있으면 kind 우선
+#[derive(Serialize, Deserialize)]
+struct ProposalRecord {
+    id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kind: Option<ProposalKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proposal_type: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    new_value: Option<u64>,
+    voting_end_time: u64,
+    yes_votes: u64,
+    no_votes: u64,
+    is_executed: bool,
+    #[serde(default)]
+    outcome: Option<ProposalOutcome>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rules: Option<GovernanceRules>,
+}
+
+impl TryFrom<ProposalRecord> for Proposal {
+    type Error = String;
+
+    fn try_from(record: ProposalRecord) -> Result<Self, Self::Error> {
+        let kind = match (record.kind, record.proposal_type) {
+            (Some(kind), _) => kind,
+            (None, Some(proposal_type)) => ProposalKind::from_legacy(proposal_type, record.new_value.unwrap_or(0)),
+            (None, None) => return Err("missing field `kind`".to_string()),
+        };
+
+        Ok(Proposal {
+            id: record.id,
+            kind,
+            voting_end_time: record.voting_end_time,
+            yes_votes: record.yes_votes,
+            no_votes: record.no_votes,
+            is_executed: record.is_executed,
+            outcome: record.outcome,
+            rules: record.rules,
+        })
+    }
+}
+
+impl From<Proposal> for ProposalRecord {
+    fn from(proposal: Proposal) -> Self {
+        let legacy = proposal.kind.legacy();
+        ProposalRecord {
+            id: proposal.id,
+            kind: Some(proposal.kind),
+            proposal_type: legacy.map(|(proposal_type, _)| proposal_type),
+            new_value: legacy.map(|(_, new_value)| new_value),
+            voting_end_time: proposal.voting_end_time,
+            yes_votes: proposal.yes_votes,
+            no_votes: proposal.no_votes,
+            is_executed: proposal.is_executed,
+            outcome: proposal.outcome,
+            rules: proposal.rules,
+        }
+    }
+}
+
+// 레거시 제안 유형 코드 (proposal_type)
+pub const PROPOSAL_TYPE_TIME_LIMIT: u8 = 0;
+pub const PROPOSAL_TYPE_BASE_FEE: u8 = 1;
+pub const PROPOSAL_TYPE_AI_MODERATION: u8 = 2;
+
+// 제안으로 바꿀 수 있는 값의 허용 범위
+pub const MIN_TIME_LIMIT_SECS: u64 = 60;
+pub const MAX_TIME_LIMIT_SECS: u64 = 365 * 24 * 60 * 60;
+pub const MAX_BASE_FEE_LAMPORTS: u64 = 10_000_000_000;    // 10 SOL
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ProposalKind {
+    TimeLimit(u64),                     // 시간 제한(초)
+    BaseFee(u64),                       // 기본 수수료(lamports)
+    AiModeration(bool),
+    DepositShare(u8),                   // 고품질 콘텐츠 분배 비율(0-100%)
+    AdminTransfer(String),              // 새 관리자 공개키
+    TreasurySpend {                     // 예치금에서 recipient에게 지출 (전송은 온체인에서 실행)
+        recipient: String,
+        amount: u64,
+    },
+    Governance(GovernanceRules),        // 거버넌스 규칙 변경 (커뮤니티 수정으로는 바꿀 수 없음)
+    Unknown {                           // 알 수 없는 레거시 유형. 저장된 행을 읽기 위해서만 사용
+        proposal_type: u8,
+        new_value: u64,
+    },
+}
+
+impl ProposalKind {
+    pub fn from_legacy(proposal_type: u8, new_value: u64) -> Self {
+        match proposal_type {
+            PROPOSAL_TYPE_TIME_LIMIT => ProposalKind::TimeLimit(new_value),
+            PROPOSAL_TYPE_BASE_FEE => ProposalKind::BaseFee(new_value),
+            PROPOSAL_TYPE_AI_MODERATION => ProposalKind::AiModeration(new_value != 0),
+            _ => ProposalKind::Unknown { proposal_type, new_value },
+        }
+    }
+
+    // (proposal_type, new_value)로 표현할 수 있는 유형이면 그 값
+    pub fn legacy(&self) -> Option<(u8, u64)> {
+        match self {
+            ProposalKind::TimeLimit(secs) => Some((PROPOSAL_TYPE_TIME_LIMIT, *secs)),
+            ProposalKind::BaseFee(fee) => Some((PROPOSAL_TYPE_BASE_FEE, *fee)),
+            ProposalKind::AiModeration(enabled) => Some((PROPOSAL_TYPE_AI_MODERATION, *enabled as u64)),
+            ProposalKind::Unknown { proposal_type, new_value } => Some((*proposal_type, *new_value)),
+            _ => None,
+        }
+    }
+
+    // API로 받은 제안의 값 범위 확인
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ProposalKind::TimeLimit(secs) if !(MIN_TIME_LIMIT_SECS..=MAX_TIME_LIMIT_SECS).contains(secs) => Err(format!(
+                "time_limit must be between {} and {} seconds", MIN_TIME_LIMIT_SECS, MAX_TIME_LIMIT_SECS
+            )),
+            ProposalKind::BaseFee(fee) if *fee > MAX_BASE_FEE_LAMPORTS => Err(format!(
+                "base_fee must be at most {} lamports", MAX_BASE_FEE_LAMPORTS
+            )),
+            ProposalKind::DepositShare(share) if *share > 100 => Err("deposit_share must be between 0 and 100".to_string()),
+            ProposalKind::AdminTransfer(admin) if parse_pubkey(admin).is_err() => Err(format!("Invalid admin pubkey {}", admin)),
+            ProposalKind::TreasurySpend { recipient, .. } if parse_pubkey(recipient).is_err() => Err(format!("Invalid recipient pubkey {}", recipient)),
+            ProposalKind::TreasurySpend { amount: 0, .. } => Err("Treasury spend amount must be greater than zero".to_string()),
+            ProposalKind::Unknown { proposal_type, .. } => Err(format!("Unknown proposal type {}", proposal_type)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalOutcome {
+    Passed,                             // 가결되어 커뮤니티에 반영됨
+    Rejected,                           // 찬성이 과반이 아님
+    QuorumNotMet,                       // 정족수 미달
+    Invalid,                            // 적용할 수 없는 제안 (알 수 없는 유형, 예치금을 넘는 지출 등)
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Daopda{
-    pub address: String            // 실행 여부
+    pub address: String,                // 커뮤니티 계정 PDA
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin: Option<String>,          // PDA 유도에 쓰인 관리자 지갑 (프로그램 ID가 설정되면 필수)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::Validate;
+
+    #[test]
+    fn test_legacy_proposal_rows_deserialize() -> Result<(), serde_json::Error> {
+        let legacy = r#"{"id":1,"proposal_type":2,"new_value":1,"voting_end_time":10,"yes_votes":0,"no_votes":0,"is_executed":false}"#;
+        let proposal: Proposal = serde_json::from_str(legacy)?;
+        assert_eq!(proposal.kind, ProposalKind::AiModeration(true));
+        assert_eq!(proposal.outcome, None);
+
+        let unknown: Proposal = serde_json::from_str(&legacy.replace(r#""proposal_type":2"#, r#""proposal_type":7"#))?;
+        assert_eq!(unknown.kind, ProposalKind::Unknown { proposal_type: 7, new_value: 1 });
+
+        // 저장할 때는 kind와 함께 표현 가능한 레거시 필드도 기록
+        let value = serde_json::to_value(&proposal)?;
+        assert_eq!(value["kind"], serde_json::json!({ "type": "ai_moderation", "value": true }));
+        assert_eq!((value["proposal_type"].as_u64(), value["new_value"].as_u64()), (Some(2), Some(1)));
+
+        let spend = Proposal { kind: ProposalKind::TreasurySpend { recipient: "r".to_string(), amount: 5 }, ..proposal };
+        let value = serde_json::to_value(&spend)?;
+        assert!(value.get("proposal_type").is_none());
+        assert_eq!(serde_json::from_value::<Proposal>(value)?.kind, spend.kind);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_governance_rules_default_for_existing_rows() -> Result<(), serde_json::Error> {
+        let rules: GovernanceRules = serde_json::from_str(r#"{"quorum_percent":25}"#)?;
+        assert_eq!(rules, GovernanceRules { quorum_percent: 25, ..GovernanceRules::default() });
+        assert!(rules.validate_fields(0).is_ok());
+        assert!(GovernanceRules { approval_percent: 100, ..rules }.validate_fields(0).is_err());
+        assert!(GovernanceRules { max_active_proposals: 0, ..rules }.validate_fields(0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_proposal_kind_ranges() {
+        let pubkey = crate::test_util::wallet(7);
+
+        assert!(ProposalKind::TimeLimit(3600).validate().is_ok());
+        assert!(ProposalKind::TimeLimit(0).validate().is_err());
+        assert!(ProposalKind::BaseFee(MAX_BASE_FEE_LAMPORTS + 1).validate().is_err());
+        assert!(ProposalKind::DepositShare(100).validate().is_ok());
+        assert!(ProposalKind::DepositShare(101).validate().is_err());
+        assert!(ProposalKind::AdminTransfer(pubkey.clone()).validate().is_ok());
+        assert!(ProposalKind::AdminTransfer("not-a-key".to_string()).validate().is_err());
+        assert!(ProposalKind::TreasurySpend { recipient: pubkey, amount: 0 }.validate().is_err());
+        assert!(ProposalKind::Unknown { proposal_type: 7, new_value: 0 }.validate().is_err());
+    }
 }
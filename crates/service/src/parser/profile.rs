@@ -10,6 +10,7 @@ pub struct UserProfile {
     pub x_account: String,
     pub tg_account: String,
     pub user_bio: String,
-    pub user_avatar: Option<Vec<u8>>,  // 바이너리 이미지 데이터
-    pub avatar_content_type: Option<String>,  // 이미지 MIME 타입 (예: "image/jpeg")
+    #[serde(default)]
+    pub avatar_hash: Option<String>,  // 아바타 이미지 SHA-256 (바이트는 user_avatars 테이블에 저장)
+    pub avatar_content_type: Option<String>,  // 이미지 MIME 타입 (예: "image/png")
 }
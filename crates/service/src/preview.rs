@@ -0,0 +1,160 @@
+use serde::Serialize;
+use turtle_database::basic_db::SafeDatabase;
+use turtle_database::error::{database_error, decode, not_found, StorageError};
+use crate::lifecycle::apply_proposal;
+use crate::parser::community::{Community, GovernanceRules, Proposal, ProposalKind};
+use crate::round::{round_deadline, round_status};
+
+// 제안이 가결되었을 때의 커뮤니티 변화 (저장하지 않음)
+#[derive(Serialize)]
+pub struct ProposalPreview {
+    pub applicable: bool,               // false면 가결되어도 Invalid로 마감됨
+    pub before: Community,
+    pub after: Community,
+    pub effects: Vec<ProposalEffect>,
+}
+
+// 필드 변경으로 생기는 효과
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "effect", rename_all = "snake_case")]
+pub enum ProposalEffect {
+    NextPostFee {                       // 다음 게시물부터 적용되는 base_fee
+        before: u64,
+        after: u64,
+    },
+    RoundDeadline {                     // 진행 중인 라운드 종료 시점 (콘텐츠가 없으면 None)
+        before: Option<u64>,
+        after: Option<u64>,
+    },
+    AiModeration {
+        enabled: bool,
+    },
+    PayoutShare {                       // 라운드 종료 시 고품질 콘텐츠에 분배되는 비율
+        before: u8,
+        after: u8,
+    },
+    AdminTransfer {
+        from: String,
+        to: String,
+    },
+    TreasurySpend {
+        recipient: String,
+        amount: u64,
+        remaining_deposit: u64,
+    },
+    Governance {                        // 이후 생성되는 제안부터 적용되는 규칙
+        before: GovernanceRules,
+        after: GovernanceRules,
+    },
+}
+
+pub type PreviewError = StorageError;
+
+// 바뀐 필드로부터 효과 목록 계산
+pub fn proposal_effects(before: &Community, after: &Community, proposal: &Proposal, round_ends_at: Option<u64>) -> Vec<ProposalEffect> {
+    let mut effects = Vec::new();
+
+    if before.base_fee != after.base_fee {
+        effects.push(ProposalEffect::NextPostFee {
+            before: before.base_fee,
+            after: after.base_fee,
+        });
+    }
+    if before.time_limit != after.time_limit {
+        effects.push(ProposalEffect::RoundDeadline {
+            before: round_ends_at,
+            after: round_ends_at.and_then(|_| round_deadline(after)),
+        });
+    }
+    if before.ai_moderation != after.ai_moderation {
+        effects.push(ProposalEffect::AiModeration {
+            enabled: after.ai_moderation,
+        });
+    }
+    if before.deposit_share != after.deposit_share {
+        effects.push(ProposalEffect::PayoutShare {
+            before: before.deposit_share,
+            after: after.deposit_share,
+        });
+    }
+    if before.admin != after.admin {
+        effects.push(ProposalEffect::AdminTransfer {
+            from: before.admin.clone(),
+            to: after.admin.clone(),
+        });
+    }
+    if before.governance != after.governance {
+        effects.push(ProposalEffect::Governance {
+            before: before.governance,
+            after: after.governance,
+        });
+    }
+    if let ProposalKind::TreasurySpend { recipient, amount } = &proposal.kind {
+        effects.push(ProposalEffect::TreasurySpend {
+            recipient: recipient.clone(),
+            amount: *amount,
+            remaining_deposit: before.total_deposit.saturating_sub(*amount),
+        });
+    }
+
+    effects
+}
+
+// 현재 커뮤니티에 제안을 적용한 결과
+pub fn preview_proposal<T: SafeDatabase>(database: &T, pda: &str, proposal: &Proposal, now: u64) -> Result<ProposalPreview, PreviewError> {
+    let status = round_status(database, pda, now)?;
+
+    let before: Community = decode(&database.read(pda, "community")
+        .map_err(database_error)?
+        .ok_or_else(|| not_found(format!("Community with PDA {} not found", pda)))?)?;
+
+    let mut after = before.clone();
+    let applicable = apply_proposal(&mut after, proposal);
+    let effects = proposal_effects(&before, &after, proposal, status.ends_at);
+
+    Ok(ProposalPreview {
+        applicable,
+        before,
+        after,
+        effects,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::community::Content;
+    use crate::test_util::{community, content, proposal, temp_db};
+
+    #[test]
+    fn test_preview_shows_changes_without_writing() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = temp_db()?;
+
+        let community = Community {
+            last_activity_timestamp: 1_000,
+            total_deposit: 500,
+            content_count: 1,
+            ..community()
+        };
+        let content = Content { timestamp: 1_000, ..content("author") };
+        db.write("pda1", &serde_json::to_string(&community)?, "community")?;
+        db.write("pda1_1", &serde_json::to_string(&content)?, "content")?;
+
+        let preview = preview_proposal(&db, "pda1", &proposal(ProposalKind::TimeLimit(600)), 1_100)?;
+        assert!(preview.applicable);
+        assert_eq!((preview.before.time_limit, preview.after.time_limit), (3600, 600));
+        assert_eq!(preview.effects, vec![ProposalEffect::RoundDeadline { before: Some(4_600), after: Some(1_600) }]);
+
+        let preview = preview_proposal(&db, "pda1", &proposal(ProposalKind::BaseFee(250)), 1_100)?;
+        assert_eq!(preview.effects, vec![ProposalEffect::NextPostFee { before: 100, after: 250 }]);
+
+        // 예치금을 넘는 지출은 적용할 수 없음
+        let spend = ProposalKind::TreasurySpend { recipient: "r".to_string(), amount: 900 };
+        assert!(!preview_proposal(&db, "pda1", &proposal(spend), 1_100)?.applicable);
+
+        let stored: Community = serde_json::from_slice(&db.read("pda1", "community")?.unwrap())?;
+        assert_eq!((stored.time_limit, stored.base_fee), (3600, 100));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use serde::{Deserialize, Serialize};
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error::{database_error, decode, encode, not_found, StorageError};
+use crate::parser::community::{Community, Content, RoundWinnerRule};
+use crate::settlement::{record_payouts, SettlementError};
+use crate::claim::{record_claims, ClaimError};
+
+// 커뮤니티별 진행 중인 라운드 (key: pda). 커뮤니티 수정으로 덮어쓰이지 않도록 별도 테이블에 보관
+pub const ROUND_TABLE: &str = "round";
+
+// 종료된 라운드와 그 라운드의 콘텐츠 보관 (key: pda:round_id)
+pub const ROUND_ARCHIVE_TABLE: &str = "round_archive";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundState {
+    pub round_id: u64,
+    pub started_at: u64,
+}
+
+impl Default for RoundState {
+    fn default() -> Self {
+        Self {
+            round_id: 1,
+            started_at: 0,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RoundEntry {
+    pub key: String,                // pda_n
+    pub content: Content,
+}
+
+// 종료된 라운드 기록
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SettledRound {
+    pub pda: String,
+    pub round_id: u64,
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub rule: RoundWinnerRule,
+    pub winner: RoundEntry,
+    pub contents: Vec<RoundEntry>,
+}
+
+// 진행 중인 라운드 상태 (/api/dao/community/status)
+#[derive(Serialize)]
+pub struct RoundStatus {
+    pub round_id: u64,
+    pub started_at: u64,
+    pub ends_at: Option<u64>,       // 콘텐츠가 없으면 라운드가 끝나지 않으므로 None
+    pub time_remaining: Option<u64>,
+    pub content_count: usize,
+    pub leader: Option<RoundEntry>,
+}
+
+pub type RoundError = StorageError;
+
+// 규칙에 따라 우승 콘텐츠 선정. 완전히 같으면 키가 큰(나중에 등록된) 콘텐츠
+pub fn pick_winner(entries: &[RoundEntry], rule: RoundWinnerRule) -> Option<&RoundEntry> {
+    entries.iter().max_by(|a, b| {
+        let by_rule = match rule {
+            RoundWinnerRule::Votes => a.content.votes.cmp(&b.content.votes)
+                .then(a.content.timestamp.cmp(&b.content.timestamp)),
+            RoundWinnerRule::Recency => a.content.timestamp.cmp(&b.content.timestamp),
+        };
+        by_rule.then_with(|| content_id(&a.key).cmp(&content_id(&b.key)))
+    })
+}
+
+// 콘텐츠 키(pda_n)의 ID
+fn content_id(key: &str) -> u64 {
+    key.rsplit_once('_')
+        .and_then(|(_, id)| id.parse().ok())
+        .unwrap_or(0)
+}
+
+// 마지막 활동 후 time_limit이 지나 라운드가 끝나는 시점. time_limit이 0이면 끝나지 않음
+pub fn round_deadline(community: &Community) -> Option<u64> {
+    if community.time_limit == 0 {
+        return None;
+    }
+    Some(community.last_activity_timestamp.saturating_add(community.time_limit))
+}
+
+fn round_contents(txn: &DatabaseTransaction<'_, '_>, pda: &str) -> Result<Vec<RoundEntry>, RoundError> {
+    let prefix = format!("{}_", pda);
+    let mut entries = Vec::new();
+
+    for (key, value) in txn.read_all("content").map_err(database_error)? {
+        let Ok(key) = String::from_utf8(key) else {
+            continue;
+        };
+        if key.starts_with(&prefix) {
+            entries.push(RoundEntry {
+                content: decode(&value)?,
+                key,
+            });
+        }
+    }
+
+    entries.sort_by_key(|entry| content_id(&entry.key));
+    Ok(entries)
+}
+
+fn read_round(txn: &DatabaseTransaction<'_, '_>, pda: &str) -> Result<RoundState, RoundError> {
+    txn.read(pda, ROUND_TABLE).map_err(database_error)?
+        .map(|data| decode(&data))
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+// 진행 중인 라운드의 남은 시간과 현재 1위 콘텐츠
+pub fn round_status<T: SafeDatabase>(database: &T, pda: &str, now: u64) -> Result<RoundStatus, RoundError> {
+    database.transaction(|txn| {
+        let community: Community = decode(&txn.read(pda, "community").map_err(database_error)?
+            .ok_or_else(|| not_found(format!("Community with PDA {} not found", pda)))?)?;
+        let round = read_round(txn, pda)?;
+        let contents = round_contents(txn, pda)?;
+
+        let ends_at = if contents.is_empty() { None } else { round_deadline(&community) };
+
+        Ok(RoundStatus {
+            round_id: round.round_id,
+            started_at: round.started_at,
+            ends_at,
+            time_remaining: ends_at.map(|ends_at| ends_at.saturating_sub(now)),
+            content_count: contents.len(),
+            leader: pick_winner(&contents, community.round_winner).cloned(),
+        })
+    }).map_err(database_error)?
+}
+
+// time_limit 동안 활동이 없었던 라운드 하나를 종료. 콘텐츠를 라운드 기록으로 옮기고 우승 콘텐츠를 정한 뒤
+// 새 라운드를 시작하는 과정을 하나의 트랜잭션으로 처리하므로 반복 실행해도 같은 라운드를 두 번 종료하지 않음
+pub fn settle_round<T: SafeDatabase>(database: &T, pda: &str, now: u64) -> Result<Option<SettledRound>, RoundError> {
+    database.transaction(|txn| {
+        let Some(community_data) = txn.read(pda, "community").map_err(database_error)? else {
+            return Ok(None);
+        };
+        let mut community: Community = decode(&community_data)?;
+
+        if round_deadline(&community).is_none_or(|deadline| now < deadline) {
+            return Ok(None);
+        }
+
+        // 콘텐츠가 없는 라운드는 첫 콘텐츠가 올라올 때까지 계속 진행
+        let contents = round_contents(txn, pda)?;
+        let Some(winner) = pick_winner(&contents, community.round_winner).cloned() else {
+            return Ok(None);
+        };
+
+        let round = read_round(txn, pda)?;
+        let settled = SettledRound {
+            pda: pda.to_string(),
+            round_id: round.round_id,
+            started_at: round.started_at,
+            ended_at: now,
+            rule: community.round_winner,
+            winner,
+            contents,
+        };
+
+        txn.write(&format!("{}:{}", pda, round.round_id), &encode(&settled)?, ROUND_ARCHIVE_TABLE)
+            .map_err(database_error)?;
+
+        // 라운드 콘텐츠 득표 기준 예치금 분배표 저장, 분배한 예치금은 포지션에서 차감
+        let round_contents: Vec<Content> = settled.contents.iter().map(|entry| entry.content.clone()).collect();
+        let payouts = record_payouts(txn, pda, round.round_id, &mut community, &round_contents, now).map_err(|e| match e {
+            SettlementError::Storage(e) => e,
+            e => database_error(e),
+        })?;
+
+        // 지갑별 청구를 위한 머클 루트와 증명 저장
+        record_claims(txn, pda, round.round_id, &payouts).map_err(|e| match e {
+            ClaimError::Storage(e) => e,
+            e => database_error(e),
+        })?;
+        for entry in &settled.contents {
+            txn.delete(&entry.key, "content").map_err(database_error)?;
+        }
+
+        let next_round = RoundState {
+            round_id: round.round_id + 1,
+            started_at: now,
+        };
+        txn.write(pda, &encode(&next_round)?, ROUND_TABLE).map_err(database_error)?;
+
+        community.content_count = 0;
+        community.last_activity_timestamp = now;
+        txn.write(pda, &encode(&community)?, "community").map_err(database_error)?;
+
+        Ok(Some(settled))
+    }).map_err(database_error)?
+}
+
+// 종료 시점이 지난 모든 커뮤니티의 라운드를 종료
+pub fn settle_expired_rounds<T: SafeDatabase>(database: &T, now: u64) -> Result<Vec<SettledRound>, RoundError> {
+    let mut pdas = Vec::new();
+    for (key, value) in database.read_all("community").map_err(database_error)? {
+        let Ok(pda) = String::from_utf8(key) else {
+            continue;
+        };
+        let community: Community = decode(&value)?;
+        if round_deadline(&community).is_some_and(|deadline| now >= deadline) {
+            pdas.push(pda);
+        }
+    }
+    pdas.sort();
+
+    let mut settled = Vec::new();
+    for pda in pdas {
+        if let Some(round) = settle_round(database, &pda, now)? {
+            settled.push(round);
+        }
+    }
+
+    Ok(settled)
+}
+
+// 종료된 라운드 기록 조회
+pub fn settled_round<T: SafeDatabase>(database: &T, pda: &str, round_id: u64) -> Result<Option<SettledRound>, RoundError> {
+    database.read(&format!("{}:{}", pda, round_id), ROUND_ARCHIVE_TABLE)
+        .map_err(database_error)?
+        .map(|data| decode(&data))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{community, content, temp_db, wallet};
+    use turtle_database::basic_db::InnerDatabase;
+
+    // 라운드 시작 1000, time_limit 100인 커뮤니티만 저장한 DB
+    fn setup(rule: RoundWinnerRule) -> Result<(tempfile::TempDir, InnerDatabase), Box<dyn std::error::Error>> {
+        let (temp_dir, db) = temp_db()?;
+        let community = Community {
+            time_limit: 100,
+            last_activity_timestamp: 1000,
+            content_count: 2,
+            round_winner: rule,
+            ..community()
+        };
+        db.write("pda1", &encode(&community)?, "community")?;
+
+        Ok((temp_dir, db))
+    }
+
+    #[test]
+    fn test_settle_round_archives_contents_and_starts_next_round() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(RoundWinnerRule::Votes)?;
+        db.write("pda1_1", &encode(&Content { timestamp: 900, votes: 5, ..content("popular") })?, "content")?;
+        db.write("pda1_2", &encode(&Content { timestamp: 1000, votes: 1, ..content("latest") })?, "content")?;
+
+        let status = round_status(&db, "pda1", 1040)?;
+        assert_eq!(status.round_id, 1);
+        assert_eq!(status.time_remaining, Some(60));
+        assert_eq!(status.leader.map(|entry| entry.content.author), Some("popular".to_string()));
+
+        // time_limit 전에는 종료하지 않음
+        assert!(settle_expired_rounds(&db, 1099)?.is_empty());
+
+        let settled = settle_expired_rounds(&db, 1100)?;
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].winner.key, "pda1_1");
+        assert_eq!(settled[0].contents.len(), 2);
+
+        // 콘텐츠는 라운드 기록으로 옮겨지고 새 라운드는 콘텐츠가 올라올 때까지 끝나지 않음
+        assert!(db.read("pda1_1", "content")?.is_none());
+        assert_eq!(settled_round(&db, "pda1", 1)?.map(|round| round.ended_at), Some(1100));
+        assert!(crate::settlement::payout_table(&db, "pda1", 1)?.is_some());
+        let status = round_status(&db, "pda1", 5000)?;
+        assert_eq!((status.round_id, status.started_at, status.ends_at), (2, 1100, None));
+        assert!(settle_expired_rounds(&db, 5000)?.is_empty());
+
+        let community: Community = decode(&db.read("pda1", "community")?.unwrap())?;
+        assert_eq!(community.content_count, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_settled_deposits_cannot_be_withdrawn_or_paid_again() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::deposit::{deposit, deposit_history, withdraw, DepositAction, DepositError};
+        use crate::settlement::payout_table;
+
+        let (_temp_dir, db) = setup(RoundWinnerRule::Votes)?;
+        db.write("pda1_1", &encode(&Content { timestamp: 900, votes: 3, ..content(&wallet(1)) })?, "content")?;
+
+        deposit(&db, "pda1", &wallet(2), 100, 0, 1000)?;
+        deposit(&db, "pda1", &wallet(3), 300, 0, 1000)?;
+        settle_round(&db, "pda1", 1100)?.unwrap();
+        assert_eq!(payout_table(&db, "pda1", 1)?.map(|table| table.total_deposit), Some(400));
+
+        // 분배한 예치금은 포지션과 커뮤니티 합계에서 빠짐
+        let community: Community = decode(&db.read("pda1", "community")?.unwrap())?;
+        assert_eq!((community.total_deposit, community.depositor_count), (0, 0));
+        assert!(matches!(withdraw(&db, "pda1", &wallet(2), 100, 1200), Err(DepositError::Storage(StorageError::NotFound(_)))));
+        let (_, history) = deposit_history(&db, "pda1", &wallet(2))?;
+        assert_eq!(history.last().map(|event| (event.action, event.amount, event.balance)), Some((DepositAction::Settle, 100, 0)));
+
+        // 다음 라운드는 새로 예치한 금액만 분배
+        deposit(&db, "pda1", &wallet(2), 50, 0, 1200)?;
+        db.write("pda1_2", &encode(&Content { timestamp: 1200, votes: 1, ..content(&wallet(1)) })?, "content")?;
+        settle_round(&db, "pda1", 1300)?.unwrap();
+        assert_eq!(payout_table(&db, "pda1", 2)?.map(|table| table.total_deposit), Some(50));
+        assert!(matches!(withdraw(&db, "pda1", &wallet(2), 1, 1400), Err(DepositError::Storage(StorageError::NotFound(_)))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_legacy_author_does_not_block_settlement() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::claim::CLAIM_ROOT_TABLE;
+        use crate::deposit::deposit;
+        use crate::settlement::payout_table;
+
+        let (_temp_dir, db) = setup(RoundWinnerRule::Votes)?;
+        // 지갑 주소 검사 도입 전에 저장된 작성자
+        db.write("pda1_1", &encode(&Content { timestamp: 900, votes: 1, ..content("legacy-author") })?, "content")?;
+        db.write("pda1_2", &encode(&Content { timestamp: 1000, votes: 1, ..content(&wallet(1)) })?, "content")?;
+        deposit(&db, "pda1", &wallet(2), 100, 0, 1000)?;
+        deposit(&db, "pda1", &wallet(3), 100, 0, 1000)?;
+
+        assert!(settle_round(&db, "pda1", 1100)?.is_some());
+
+        // 보상 100을 반씩 나누고 청구할 수 없는 작성자의 몫은 dust로 남김
+        let table = payout_table(&db, "pda1", 1)?.unwrap();
+        assert_eq!(table.author_payouts.iter().map(|payout| payout.pubkey.clone()).collect::<Vec<_>>(), vec![wallet(1)]);
+        assert_eq!(table.skipped.iter().map(|payout| (payout.pubkey.as_str(), payout.lamports)).collect::<Vec<_>>(), vec![("legacy-author", 50)]);
+        assert_eq!(table.dust, 50);
+
+        let claim_root: crate::claim::ClaimRoot = decode(&db.read("pda1:1", CLAIM_ROOT_TABLE)?.unwrap())?;
+        assert_eq!((claim_root.leaf_count, claim_root.total_amount), (3, 150));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recency_rule_picks_last_content() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = setup(RoundWinnerRule::Recency)?;
+        db.write("pda1_1", &encode(&Content { timestamp: 900, votes: 5, ..content("popular") })?, "content")?;
+        db.write("pda1_2", &encode(&Content { timestamp: 1000, votes: 1, ..content("latest") })?, "content")?;
+
+        let settled = settle_round(&db, "pda1", 1200)?.unwrap();
+        assert_eq!(settled.winner.content.author, "latest");
+        assert!(settle_round(&db, "pda1", 1200)?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use turtle_database::basic_db::DatabaseTransaction;
+use turtle_database::error::{database_error, StorageError};
+
+// 하위 리소스 ID 시퀀스 테이블 (key: table:pda, value: 마지막 발급 ID)
+pub const SEQUENCE_TABLE: &str = "sequence";
+
+// 하위 리소스(content, depositor, proposal)의 다음 ID. 카운터는 삭제 시 줄어들므로 키 발급은 별도 시퀀스로 관리
+pub fn next_id(txn: &DatabaseTransaction<'_, '_>, table: &str, pda: &str) -> Result<u64, StorageError> {
+    let sequence_key = format!("{}:{}", table, pda);
+    let last_id = match txn.read(&sequence_key, SEQUENCE_TABLE).map_err(database_error)? {
+        Some(data) => String::from_utf8(data)
+            .ok()
+            .and_then(|id| id.parse::<u64>().ok())
+            .ok_or_else(|| StorageError::Serialization(format!("Invalid sequence for {}", sequence_key)))?,
+        None => {
+            // 시퀀스 도입 전 데이터는 가장 큰 기존 ID 다음부터 발급
+            let prefix = format!("{}_", pda);
+            txn.read_all(table).map_err(database_error)?
+                .keys()
+                .filter_map(|key| std::str::from_utf8(key).ok())
+                .filter_map(|key| key.strip_prefix(&prefix))
+                .filter_map(|id| id.parse::<u64>().ok())
+                .max()
+                .unwrap_or(0)
+        },
+    };
+
+    let next_id = last_id + 1;
+    txn.write(&sequence_key, &next_id.to_string(), SEQUENCE_TABLE).map_err(database_error)?;
+    Ok(next_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_db;
+    use turtle_database::basic_db::SafeDatabase;
+
+    #[test]
+    fn test_next_id_continues_after_legacy_rows() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = temp_db()?;
+        db.write("pda1_3", "{}", "content")?;
+        db.write("pda2_9", "{}", "content")?;
+
+        let ids = db.transaction(|txn| {
+            Ok::<_, StorageError>((next_id(txn, "content", "pda1")?, next_id(txn, "content", "pda1")?, next_id(txn, "proposal", "pda1")?))
+        })??;
+        assert_eq!(ids, (4, 5, 1));
+
+        // 삭제된 ID는 재사용하지 않음
+        db.delete("pda1_3", "content")?;
+        assert_eq!(db.transaction(|txn| next_id(txn, "content", "pda1"))??, 6);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error as StdError;
+use std::fmt;
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error::{database_error, decode, encode, StorageError};
+use crate::parser::community::{Community, Content, Depositor};
+use crate::deposit::{settle_positions, DepositError};
+use sol::pubkey::parse_pubkey;
+
+// 라운드별 분배 결과 (key: pda:round_id)
+pub const PAYOUT_TABLE: &str = "payout";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Payout {
+    pub pubkey: String,
+    pub lamports: u64,
+}
+
+// 라운드 종료 시 분배표. author_payouts + depositor_payouts + dust == total_deposit
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutTable {
+    pub total_deposit: u64,             // 예치자 amount 합계
+    pub reward_pool: u64,               // total_deposit * deposit_share / 100 (내림)
+    pub author_payouts: Vec<Payout>,    // 득표 수 비례, pubkey 순
+    pub depositor_payouts: Vec<Payout>, // 예치 금액 비례, pubkey 순
+    pub dust: u64,                      // 받을 대상이 없거나 청구할 수 없어 분배하지 못한 금액
+    #[serde(default)]
+    pub skipped: Vec<Payout>,           // 지갑 주소가 아닌 수령자(예전 데이터)의 몫. dust에 포함
+}
+
+impl PayoutTable {
+    // 청구 리프를 만들 수 없는 수령자의 몫을 skipped로 옮기고 dust에 더함
+    fn skip_unclaimable(&mut self) {
+        for payouts in [&mut self.author_payouts, &mut self.depositor_payouts] {
+            let (claimable, unclaimable): (Vec<Payout>, Vec<Payout>) = std::mem::take(payouts).into_iter()
+                .partition(|payout| parse_pubkey(&payout.pubkey).is_ok());
+            *payouts = claimable;
+            self.dust += unclaimable.iter().map(|payout| payout.lamports).sum::<u64>();
+            self.skipped.extend(unclaimable);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SettlementError {
+    Storage(StorageError),
+    Overflow,
+}
+
+impl fmt::Display for SettlementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettlementError::Storage(e) => write!(f, "{}", e),
+            SettlementError::Overflow => write!(f, "Total deposit exceeds u64"),
+        }
+    }
+}
+
+impl StdError for SettlementError {}
+
+impl From<StorageError> for SettlementError {
+    fn from(e: StorageError) -> Self {
+        SettlementError::Storage(e)
+    }
+}
+
+// amount를 가중치 비례로 나눔. 각 몫은 내림하고 남는 lamport는 나머지가 큰 순(같으면 pubkey 순)으로
+// 1씩 더해 합계가 항상 amount와 같게 함. 가중치 합이 0이면 (빈 목록, amount)
+pub(crate) fn split_by_weight(amount: u64, weights: BTreeMap<String, u128>) -> (Vec<Payout>, u64) {
+    let total_weight: u128 = weights.values().sum();
+    if total_weight == 0 {
+        return (Vec::new(), amount);
+    }
+
+    let mut shares: Vec<(String, u64, u128)> = weights.into_iter()
+        .map(|(pubkey, weight)| {
+            let scaled = amount as u128 * weight;
+            (pubkey, (scaled / total_weight) as u64, scaled % total_weight)
+        })
+        .collect();
+
+    let distributed: u64 = shares.iter().map(|(_, share, _)| share).sum();
+    let mut leftover = amount - distributed;
+
+    let mut by_remainder: Vec<usize> = (0..shares.len()).collect();
+    by_remainder.sort_by(|&a, &b| shares[b].2.cmp(&shares[a].2).then_with(|| shares[a].0.cmp(&shares[b].0)));
+    for index in by_remainder {
+        if leftover == 0 {
+            break;
+        }
+        shares[index].1 += 1;
+        leftover -= 1;
+    }
+
+    let payouts = shares.into_iter()
+        .filter(|(_, lamports, _)| *lamports > 0)
+        .map(|(pubkey, lamports, _)| Payout { pubkey, lamports })
+        .collect();
+
+    (payouts, 0)
+}
+
+// 예치금의 deposit_share%를 득표 수 비례로 작성자에게, 나머지는 예치 금액 비례로 예치자에게 분배.
+// 득표한 콘텐츠가 없으면 보상 몫도 예치자에게 돌아감. 입력 순서와 무관하게 같은 결과
+pub fn calculate_payouts(
+    community: &Community,
+    depositors: &[Depositor],
+    contents: &[Content],
+) -> Result<PayoutTable, SettlementError> {
+    let total_deposit = depositors.iter()
+        .try_fold(0u64, |total, depositor| total.checked_add(depositor.amount))
+        .ok_or(SettlementError::Overflow)?;
+    let reward_pool = (total_deposit as u128 * community.deposit_share.min(100) as u128 / 100) as u64;
+
+    let mut votes_by_author = BTreeMap::new();
+    for content in contents {
+        *votes_by_author.entry(content.author.clone()).or_insert(0u128) += content.votes as u128;
+    }
+    let (author_payouts, unrewarded) = split_by_weight(reward_pool, votes_by_author);
+
+    let mut amount_by_depositor = BTreeMap::new();
+    for depositor in depositors {
+        *amount_by_depositor.entry(depositor.pubkey.clone()).or_insert(0u128) += depositor.amount as u128;
+    }
+    let depositor_pool = total_deposit - reward_pool + unrewarded;
+    let (depositor_payouts, dust) = split_by_weight(depositor_pool, amount_by_depositor);
+
+    Ok(PayoutTable {
+        total_deposit,
+        reward_pool,
+        author_payouts,
+        depositor_payouts,
+        dust,
+        skipped: Vec::new(),
+    })
+}
+
+fn payout_key(pda: &str, round_id: u64) -> String {
+    format!("{}:{}", pda, round_id)
+}
+
+// 커뮤니티 예치자 기준으로 라운드 분배표를 계산해 저장 (라운드 종료 트랜잭션 안에서 호출).
+// 분배한 예치금은 같은 트랜잭션에서 포지션과 커뮤니티 예치 합계에서 차감해 다시 인출하거나 분배할 수 없게 함
+pub fn record_payouts(
+    txn: &DatabaseTransaction<'_, '_>,
+    pda: &str,
+    round_id: u64,
+    community: &mut Community,
+    contents: &[Content],
+    now: u64,
+) -> Result<PayoutTable, SettlementError> {
+    let depositors = settle_positions(txn, pda, community, now).map_err(|e| match e {
+        DepositError::Storage(e) => SettlementError::Storage(e),
+        e => SettlementError::Storage(database_error(e)),
+    })?;
+
+    // 청구할 수 없는 수령자가 있어도 라운드 종료가 멈추지 않도록 그 몫은 dust로 남김
+    let mut table = calculate_payouts(community, &depositors, contents)?;
+    table.skip_unclaimable();
+    txn.write(&payout_key(pda, round_id), &encode(&table)?, PAYOUT_TABLE).map_err(database_error)?;
+
+    Ok(table)
+}
+
+// 저장된 라운드 분배표 조회
+pub fn payout_table<T: SafeDatabase>(database: &T, pda: &str, round_id: u64) -> Result<Option<PayoutTable>, SettlementError> {
+    let table = database.read(&payout_key(pda, round_id), PAYOUT_TABLE)
+        .map_err(database_error)?
+        .map(|data| decode(&data))
+        .transpose()?;
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{community, content, depositor};
+    use proptest::prelude::*;
+
+    fn payout(pubkey: &str, lamports: u64) -> Payout {
+        Payout {
+            pubkey: pubkey.to_string(),
+            lamports,
+        }
+    }
+
+    fn distributed(table: &PayoutTable) -> u128 {
+        table.author_payouts.iter().chain(&table.depositor_payouts)
+            .map(|payout| payout.lamports as u128)
+            .sum::<u128>() + table.dust as u128
+    }
+
+    #[test]
+    fn test_remainders_go_to_largest_fraction() -> Result<(), SettlementError> {
+        let depositors = [depositor("d1", 50), depositor("d2", 50)];
+        let contents = [
+            Content { votes: 1, ..content("a") },
+            Content { votes: 1, ..content("b") },
+            Content { votes: 1, ..content("c") },
+            content("a"),
+        ];
+
+        // 보상 50을 3명이 나누면 16씩, 남는 2는 pubkey 순으로
+        let table = calculate_payouts(&Community { deposit_share: 50, ..community() }, &depositors, &contents)?;
+        assert_eq!(table.reward_pool, 50);
+        assert_eq!(table.author_payouts, vec![payout("a", 17), payout("b", 17), payout("c", 16)]);
+        assert_eq!(table.depositor_payouts, vec![payout("d1", 25), payout("d2", 25)]);
+        assert_eq!(table.dust, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unvoted_round_returns_rewards_to_depositors() -> Result<(), SettlementError> {
+        let depositors = [depositor("d1", 3), depositor("d2", 1), depositor("d1", 3)];
+
+        let table = calculate_payouts(&Community { deposit_share: 100, ..community() }, &depositors, &[content("a")])?;
+        assert!(table.author_payouts.is_empty());
+        assert_eq!(table.depositor_payouts, vec![payout("d1", 6), payout("d2", 1)]);
+
+        assert_eq!(
+            calculate_payouts(&Community { deposit_share: 10, ..community() }, &[depositor("d1", u64::MAX), depositor("d2", 1)], &[]),
+            Err(SettlementError::Overflow),
+        );
+
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_payouts_add_up_to_total_deposit(
+            deposit_share in 0u8..=120,
+            amounts in prop::collection::vec((0u8..8, 0u64..1_000_000_000_000_000), 0..16),
+            votes in prop::collection::vec((0u8..8, 0u64..1_000_000), 0..16),
+        ) {
+            let depositors: Vec<Depositor> = amounts.iter()
+                .map(|(id, amount)| depositor(&format!("d{}", id), *amount))
+                .collect();
+            let contents: Vec<Content> = votes.iter()
+                .map(|(id, votes)| Content { votes: *votes, ..content(&format!("a{}", id)) })
+                .collect();
+
+            let community = Community { deposit_share, ..community() };
+            let table = calculate_payouts(&community, &depositors, &contents).unwrap();
+            prop_assert_eq!(distributed(&table), table.total_deposit as u128);
+            prop_assert!(table.author_payouts.iter().map(|payout| payout.lamports).sum::<u64>() <= table.reward_pool);
+
+            // 입력 순서와 무관하게 같은 분배표
+            let mut reversed_depositors = depositors.clone();
+            reversed_depositors.reverse();
+            let mut reversed_contents = contents.clone();
+            reversed_contents.reverse();
+            let reversed = calculate_payouts(&community, &reversed_depositors, &reversed_contents).unwrap();
+            prop_assert_eq!(reversed, table);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use turtle_database::basic_db::{DatabaseTransaction, SafeDatabase};
+use turtle_database::error::{database_error, decode, encode, not_found, StorageError};
+use crate::delegation::load_delegations;
+use crate::parser::community::{Community, Depositor};
+use crate::voting_power::voting_power;
+
+// 제안 생성 시점의 지갑별 투표 파워 (key: 제안 키 pda_n)
+pub const VOTING_POWER_SNAPSHOT_TABLE: &str = "voting_power_snapshot";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub wallet: String,
+    pub voting_power: u64,
+}
+
+// 제안 집계는 생성 이후의 예치/인출과 무관하게 이 스냅샷 기준으로 계산
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VotingPowerSnapshot {
+    pub proposal: String,               // 제안 키 (pda_n)
+    pub taken_at: u64,
+    pub total_power: u64,
+    pub entries: Vec<SnapshotEntry>,    // 투표 파워가 있는 지갑 (지갑 순)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegations: Option<BTreeMap<String, String>>,  // 생성 시점의 위임 (delegator -> delegate). 도입 전 스냅샷은 None
+}
+
+impl VotingPowerSnapshot {
+    pub fn powers(&self) -> BTreeMap<String, u64> {
+        self.entries.iter()
+            .map(|entry| (entry.wallet.clone(), entry.voting_power))
+            .collect()
+    }
+}
+
+pub type SnapshotError = StorageError;
+
+// 커뮤니티 투표 파워 모델로 계산한 지갑별 합계와 위임 관계를 제안 스냅샷으로 기록
+pub fn record_snapshot(
+    txn: &DatabaseTransaction<'_, '_>,
+    pda: &str,
+    proposal_key: &str,
+    now: u64,
+) -> Result<VotingPowerSnapshot, SnapshotError> {
+    let community: Community = decode(&txn.read(pda, "community").map_err(database_error)?
+        .ok_or_else(|| not_found(format!("Community with PDA {} not found", pda)))?)?;
+
+    let prefix = format!("{}_", pda);
+    let mut powers: BTreeMap<String, u64> = BTreeMap::new();
+    for (key, value) in txn.read_all("depositor").map_err(database_error)? {
+        if key.starts_with(prefix.as_bytes()) {
+            let depositor: Depositor = decode(&value)?;
+            let power = powers.entry(depositor.pubkey.clone()).or_insert(0);
+            *power = power.saturating_add(voting_power(&community.voting_power, &depositor, now));
+        }
+    }
+
+    let entries: Vec<SnapshotEntry> = powers.into_iter()
+        .filter(|(_, voting_power)| *voting_power > 0)
+        .map(|(wallet, voting_power)| SnapshotEntry { wallet, voting_power })
+        .collect();
+    let snapshot = VotingPowerSnapshot {
+        proposal: proposal_key.to_string(),
+        taken_at: now,
+        total_power: entries.iter().fold(0u64, |total, entry| total.saturating_add(entry.voting_power)),
+        entries,
+        delegations: Some(load_delegations(txn, pda)?),
+    };
+
+    txn.write(proposal_key, &encode(&snapshot)?, VOTING_POWER_SNAPSHOT_TABLE).map_err(database_error)?;
+
+    Ok(snapshot)
+}
+
+// 스냅샷 도입 전에 만들어진 제안이면 None
+pub fn read_snapshot(txn: &DatabaseTransaction<'_, '_>, proposal_key: &str) -> Result<Option<VotingPowerSnapshot>, SnapshotError> {
+    txn.read(proposal_key, VOTING_POWER_SNAPSHOT_TABLE).map_err(database_error)?
+        .map(|data| decode(&data))
+        .transpose()
+}
+
+// 제안을 삭제할 때 함께 제거
+pub fn delete_snapshot(txn: &DatabaseTransaction<'_, '_>, proposal_key: &str) -> Result<bool, SnapshotError> {
+    txn.delete(proposal_key, VOTING_POWER_SNAPSHOT_TABLE).map_err(database_error)
+}
+
+pub fn load_snapshot<T: SafeDatabase>(database: &T, proposal_key: &str) -> Result<Option<VotingPowerSnapshot>, SnapshotError> {
+    database.read(proposal_key, VOTING_POWER_SNAPSHOT_TABLE).map_err(database_error)?
+        .map(|data| decode(&data))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deposit::deposit;
+    use crate::parser::community::VotingPowerConfig;
+    use crate::test_util::{community, temp_db};
+
+    #[test]
+    fn test_snapshot_ignores_later_deposits() -> Result<(), Box<dyn std::error::Error>> {
+        let (_temp_dir, db) = temp_db()?;
+
+        let community = Community {
+            voting_power: VotingPowerConfig::Quadratic,
+            ..community()
+        };
+        db.write("pda1", &serde_json::to_string(&community)?, "community")?;
+        deposit(&db, "pda1", "alice", 100, 0, 1)?;
+        deposit(&db, "pda1", "bob", 400, 0, 1)?;
+
+        let snapshot = db.transaction(|txn| record_snapshot(txn, "pda1", "pda1_1", 10))??;
+        assert_eq!(snapshot.total_power, 30);
+        assert_eq!(snapshot.entries, vec![
+            SnapshotEntry { wallet: "alice".to_string(), voting_power: 10 },
+            SnapshotEntry { wallet: "bob".to_string(), voting_power: 20 },
+        ]);
+
+        // 제안 생성 이후의 예치는 저장된 스냅샷에 반영되지 않음
+        deposit(&db, "pda1", "alice", 9_900, 0, 20)?;
+        deposit(&db, "pda1", "carol", 900, 0, 20)?;
+        assert_eq!(load_snapshot(&db, "pda1_1")?, Some(snapshot));
+        assert_eq!(load_snapshot(&db, "pda1_2")?, None);
+
+        Ok(())
+    }
+}
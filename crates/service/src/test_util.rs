@@ -0,0 +1,66 @@
+use std::error::Error;
+use tempfile::TempDir;
+use turtle_database::basic_db::InnerDatabase;
+use crate::parser::community::{Community, Content, Depositor, Proposal, ProposalKind};
+
+// 서비스와 net 테스트가 함께 쓰는 픽스처. 다른 크레이트에서는 test-util 기능으로 사용하고,
+// 테스트마다 필요한 값만 ..community() 같은 구조체 갱신 문법으로 덮어씀
+
+// 관리자 "admin", 1시간 라운드, 수수료 100, 분배 50%인 커뮤니티
+pub fn community() -> Community {
+    Community {
+        admin: "admin".to_string(),
+        time_limit: 3600,
+        base_fee: 100,
+        deposit_share: 50,
+        ..Community::default()
+    }
+}
+
+// 득표 0, 타임스탬프 0인 콘텐츠
+pub fn content(author: &str) -> Content {
+    Content {
+        author: author.to_string(),
+        content_hash: "hash".to_string(),
+        content_uri: "ipfs://uri".to_string(),
+        timestamp: 0,
+        votes: 0,
+    }
+}
+
+// 잠금 없이 예치 금액만큼 투표 파워를 가진 포지션
+pub fn depositor(pubkey: &str, amount: u64) -> Depositor {
+    Depositor {
+        pubkey: pubkey.to_string(),
+        amount,
+        locked_until: 0,
+        voting_power: amount,
+        unlocked_at: None,
+    }
+}
+
+// 집계와 결과가 비어 있는 제안 (ID 1, 투표 종료 시간 0)
+pub fn proposal(kind: ProposalKind) -> Proposal {
+    Proposal {
+        id: 1,
+        kind,
+        voting_end_time: 0,
+        yes_votes: 0,
+        no_votes: 0,
+        is_executed: false,
+        outcome: None,
+        rules: None,
+    }
+}
+
+// 같은 바이트로 채운 32바이트 base58 지갑 주소
+pub fn wallet(seed: u8) -> String {
+    bs58::encode([seed; 32]).into_string()
+}
+
+// 임시 디렉터리의 빈 DB. 디렉터리는 반환한 TempDir이 살아 있는 동안 유지
+pub fn temp_db() -> Result<(TempDir, InnerDatabase), Box<dyn Error>> {
+    let temp_dir = tempfile::tempdir()?;
+    let db = InnerDatabase::new(temp_dir.path().join("test_db"))?;
+    Ok((temp_dir, db))
+}
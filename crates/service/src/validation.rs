@@ -0,0 +1,345 @@
+use serde::Serialize;
+use std::error::Error as StdError;
+use std::fmt;
+use crate::parser::community::{
+    Community, Content, Depositor, GovernanceRules, Proposal, ProposalKind, VotingPowerConfig,
+    MAX_BASE_FEE_LAMPORTS, MAX_TIME_LIMIT_SECS, MIN_TIME_LIMIT_SECS,
+};
+use crate::parser::profile::UserProfile;
+use sol::pubkey::parse_pubkey;
+
+// 클라이언트 시계 오차로 허용하는 미래 타임스탬프 범위(초)
+pub const MAX_FUTURE_SKEW_SECS: u64 = 5 * 60;
+// 예치 잠금과 제안 투표 기간의 최대 길이
+pub const MAX_LOCK_DURATION_SECS: u64 = 4 * 365 * 24 * 60 * 60;
+pub const MAX_VOTING_PERIOD_SECS: u64 = 90 * 24 * 60 * 60;
+
+pub const MAX_CONTENT_HASH_LEN: usize = 128;
+pub const MAX_CONTENT_URI_LEN: usize = 512;
+pub const CONTENT_URI_SCHEMES: &[&str] = &["ipfs", "ar", "https"];
+
+pub const MAX_USER_NAME_LEN: usize = 64;
+pub const MAX_USER_BIO_LEN: usize = 500;
+
+// 필드 하나의 규칙 위반
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub field: String,
+    pub message: String,
+}
+
+// 한 요청에서 발견된 모든 위반
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationErrors(pub Vec<Violation>);
+
+impl ValidationErrors {
+    pub fn fields(&self) -> Vec<&str> {
+        self.0.iter().map(|violation| violation.field.as_str()).collect()
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.0.iter()
+            .map(|violation| format!("{}: {}", violation.field, violation.message))
+            .collect();
+        write!(f, "Validation failed: {}", messages.join("; "))
+    }
+}
+
+impl StdError for ValidationErrors {}
+
+// 규칙을 차례로 적용하며 위반을 모음
+#[derive(Default)]
+pub struct Validator {
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn violation(&mut self, field: &str, message: impl fmt::Display) -> &mut Self {
+        self.violations.push(Violation {
+            field: field.to_string(),
+            message: message.to_string(),
+        });
+        self
+    }
+
+    pub fn check(&mut self, field: &str, valid: bool, message: impl fmt::Display) -> &mut Self {
+        if !valid {
+            self.violation(field, message);
+        }
+        self
+    }
+
+    pub fn range(&mut self, field: &str, value: u64, min: u64, max: u64) -> &mut Self {
+        self.check(field, (min..=max).contains(&value), format_args!("must be between {} and {}", min, max))
+    }
+
+    pub fn at_most(&mut self, field: &str, value: u64, max: u64) -> &mut Self {
+        self.check(field, value <= max, format_args!("must be at most {}", max))
+    }
+
+    pub fn required(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(field, !value.trim().is_empty(), "is required")
+    }
+
+    pub fn max_len(&mut self, field: &str, value: &str, max: usize) -> &mut Self {
+        self.check(field, value.chars().count() <= max, format_args!("must be at most {} characters", max))
+    }
+
+    pub fn pubkey(&mut self, field: &str, value: &str) -> &mut Self {
+        self.check(field, parse_pubkey(value).is_ok(), "must be a base58-encoded 32-byte public key")
+    }
+
+    pub fn uri(&mut self, field: &str, value: &str, schemes: &[&str]) -> &mut Self {
+        let valid = value.split_once("://")
+            .is_some_and(|(scheme, rest)| schemes.contains(&scheme) && !rest.is_empty() && !rest.chars().any(char::is_whitespace));
+        self.check(field, valid, format_args!("must be a URI with one of the schemes {}", schemes.join(", ")))
+    }
+
+    // 서버 시각보다 허용 오차 이상 미래인 타임스탬프 거부
+    pub fn not_in_future(&mut self, field: &str, timestamp: u64, now: u64) -> &mut Self {
+        self.check(field, timestamp <= now.saturating_add(MAX_FUTURE_SKEW_SECS), "must not be in the future")
+    }
+
+    pub fn within(&mut self, field: &str, timestamp: u64, now: u64, max_secs: u64) -> &mut Self {
+        self.check(
+            field,
+            timestamp <= now.saturating_add(max_secs),
+            format_args!("must be at most {} seconds from now", max_secs),
+        )
+    }
+
+    // 비어 있으면 통과 (선택 필드)
+    pub fn optional_handle(&mut self, field: &str, value: &str, handle: Handle) -> &mut Self {
+        if value.is_empty() {
+            return self;
+        }
+        self.check(field, handle.matches(value), handle.description())
+    }
+
+    pub fn finish(self) -> Result<(), ValidationErrors> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors(self.violations))
+        }
+    }
+}
+
+// 프로필 계정 이름 형식
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Handle {
+    UserId,                             // 영문 소문자, 숫자, _ 3-32자
+    GitHub,                             // 영숫자와 -, 1-39자, -로 시작/끝나지 않음
+    X,                                  // @ 선택, 영숫자와 _, 1-15자
+    Telegram,                           // @ 선택, 영숫자와 _, 5-32자
+}
+
+impl Handle {
+    fn matches(&self, value: &str) -> bool {
+        let word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        match self {
+            Handle::UserId => (3..=32).contains(&value.len())
+                && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'),
+            Handle::GitHub => (1..=39).contains(&value.len())
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                && !value.starts_with('-')
+                && !value.ends_with('-'),
+            Handle::X => {
+                let name = value.strip_prefix('@').unwrap_or(value);
+                (1..=15).contains(&name.len()) && name.chars().all(word)
+            },
+            Handle::Telegram => {
+                let name = value.strip_prefix('@').unwrap_or(value);
+                (5..=32).contains(&name.len()) && name.chars().all(word)
+            },
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Handle::UserId => "must be 3-32 lowercase letters, digits or underscores",
+            Handle::GitHub => "must be a GitHub username (1-39 letters, digits or hyphens)",
+            Handle::X => "must be an X handle (1-15 letters, digits or underscores)",
+            Handle::Telegram => "must be a Telegram username (5-32 letters, digits or underscores)",
+        }
+    }
+}
+
+// API로 받은 페이로드의 필드 규칙. now는 서버 시각(초)
+pub trait Validate {
+    fn rules(&self, validator: &mut Validator, now: u64);
+
+    fn validate_fields(&self, now: u64) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::new();
+        self.rules(&mut validator, now);
+        validator.finish()
+    }
+}
+
+impl Validate for GovernanceRules {
+    fn rules(&self, validator: &mut Validator, _now: u64) {
+        validator
+            .at_most("governance.quorum_percent", self.quorum_percent, 100)
+            // 100%면 어떤 제안도 가결될 수 없음
+            .at_most("governance.approval_percent", self.approval_percent, 99)
+            .check("governance.max_active_proposals", self.max_active_proposals > 0, "must be at least 1")
+            .at_most("governance.min_voting_period_secs", self.min_voting_period_secs, MAX_VOTING_PERIOD_SECS);
+    }
+}
+
+impl Validate for VotingPowerConfig {
+    fn rules(&self, validator: &mut Validator, now: u64) {
+        match self {
+            VotingPowerConfig::Linear | VotingPowerConfig::Quadratic => {},
+            VotingPowerConfig::LockWeighted { max_lock_secs, .. } => {
+                validator.range("voting_power.max_lock_secs", *max_lock_secs, 1, MAX_LOCK_DURATION_SECS);
+            },
+            VotingPowerConfig::Capped { cap, base } => {
+                validator.check("voting_power.cap", *cap > 0, "must be at least 1");
+                base.rules(validator, now);
+            },
+        }
+    }
+}
+
+impl Validate for Community {
+    fn rules(&self, validator: &mut Validator, now: u64) {
+        validator
+            .pubkey("admin", &self.admin)
+            .range("time_limit", self.time_limit, MIN_TIME_LIMIT_SECS, MAX_TIME_LIMIT_SECS)
+            .at_most("base_fee", self.base_fee, MAX_BASE_FEE_LAMPORTS)
+            .at_most("deposit_share", self.deposit_share as u64, 100)
+            .not_in_future("last_activity_timestamp", self.last_activity_timestamp, now);
+        self.voting_power.rules(validator, now);
+        self.governance.rules(validator, now);
+    }
+}
+
+impl Validate for Content {
+    fn rules(&self, validator: &mut Validator, now: u64) {
+        validator
+            .pubkey("author", &self.author)
+            .required("content_hash", &self.content_hash)
+            .max_len("content_hash", &self.content_hash, MAX_CONTENT_HASH_LEN)
+            .max_len("content_uri", &self.content_uri, MAX_CONTENT_URI_LEN)
+            .uri("content_uri", &self.content_uri, CONTENT_URI_SCHEMES)
+            .not_in_future("timestamp", self.timestamp, now);
+    }
+}
+
+impl Validate for Depositor {
+    fn rules(&self, validator: &mut Validator, now: u64) {
+        validator
+            .pubkey("pubkey", &self.pubkey)
+            .check("amount", self.amount > 0, "must be greater than zero")
+            .within("locked_until", self.locked_until, now, MAX_LOCK_DURATION_SECS);
+    }
+}
+
+impl Validate for Proposal {
+    fn rules(&self, validator: &mut Validator, now: u64) {
+        if let Err(message) = self.kind.validate() {
+            validator.violation("kind", message);
+        }
+        if let ProposalKind::Governance(rules) = &self.kind {
+            rules.rules(validator, now);
+        }
+        validator.within("voting_end_time", self.voting_end_time, now, MAX_VOTING_PERIOD_SECS);
+    }
+}
+
+impl Validate for UserProfile {
+    fn rules(&self, validator: &mut Validator, _now: u64) {
+        validator
+            .pubkey("user_address", &self.user_address)
+            .optional_handle("user_id", &self.user_id, Handle::UserId)
+            .max_len("user_name", &self.user_name, MAX_USER_NAME_LEN)
+            .optional_handle("github_account", &self.github_account, Handle::GitHub)
+            .optional_handle("x_account", &self.x_account, Handle::X)
+            .optional_handle("tg_account", &self.tg_account, Handle::Telegram)
+            .max_len("user_bio", &self.user_bio, MAX_USER_BIO_LEN);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::community::{ProposalKind, RoundWinnerRule};
+    use crate::test_util::{community, content, depositor, proposal, wallet};
+
+    #[test]
+    fn test_community_reports_every_violation() {
+        let community = Community { admin: wallet(1), last_activity_timestamp: 1_000, ..community() };
+        assert_eq!(community.validate_fields(1_000), Ok(()));
+
+        let invalid = Community {
+            admin: "admin".to_string(),
+            deposit_share: 150,
+            last_activity_timestamp: 1_000 + MAX_FUTURE_SKEW_SECS + 1,
+            governance: GovernanceRules { approval_percent: 100, ..GovernanceRules::default() },
+            ..community
+        };
+        let errors = invalid.validate_fields(1_000).unwrap_err();
+        assert_eq!(errors.fields(), vec!["admin", "deposit_share", "last_activity_timestamp", "governance.approval_percent"]);
+    }
+
+    #[test]
+    fn test_content_uri_scheme_and_lengths() {
+        let content = content(&wallet(2));
+        assert_eq!(content.validate_fields(0), Ok(()));
+
+        let invalid = Content {
+            content_hash: String::new(),
+            content_uri: "javascript://alert(1)".to_string(),
+            ..content.clone()
+        };
+        assert_eq!(invalid.validate_fields(0).unwrap_err().fields(), vec!["content_hash", "content_uri"]);
+
+        let too_long = Content { content_uri: format!("https://{}", "a".repeat(MAX_CONTENT_URI_LEN)), ..content };
+        assert_eq!(too_long.validate_fields(0).unwrap_err().fields(), vec!["content_uri"]);
+    }
+
+    #[test]
+    fn test_depositor_and_proposal_time_bounds() {
+        let depositor = Depositor { locked_until: 10 + MAX_LOCK_DURATION_SECS + 1, ..depositor(&wallet(3), 0) };
+        assert_eq!(depositor.validate_fields(10).unwrap_err().fields(), vec!["amount", "locked_until"]);
+
+        let proposal = Proposal { voting_end_time: u64::MAX, ..proposal(ProposalKind::DepositShare(101)) };
+        assert_eq!(proposal.validate_fields(10).unwrap_err().fields(), vec!["kind", "voting_end_time"]);
+    }
+
+    #[test]
+    fn test_profile_handles() {
+        let profile = UserProfile {
+            user_id: "turtle_01".to_string(),
+            user_name: "Turtle".to_string(),
+            user_address: wallet(4),
+            github_account: "turtle-dao".to_string(),
+            x_account: "@turtle".to_string(),
+            tg_account: "@turtle_dao".to_string(),
+            user_bio: String::new(),
+            avatar_hash: None,
+            avatar_content_type: None,
+        };
+        assert_eq!(profile.validate_fields(0), Ok(()));
+
+        let invalid = UserProfile {
+            user_id: "Turtle!".to_string(),
+            user_address: "0xabcdef".to_string(),
+            github_account: "-turtle".to_string(),
+            x_account: "@this_handle_is_too_long".to_string(),
+            tg_account: "tg".to_string(),
+            ..profile
+        };
+        assert_eq!(
+            invalid.validate_fields(0).unwrap_err().fields(),
+            vec!["user_address", "user_id", "github_account", "x_account", "tg_account"],
+        );
+    }
+}
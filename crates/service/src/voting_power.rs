@@ -0,0 +1,112 @@
+use crate::parser::community::{Depositor, VotingPowerConfig};
+
+// 예치 포지션의 투표 파워 계산. 잠금 기간 모델처럼 시간에 따라 달라질 수 있어 현재 시각을 받음
+pub trait VotingPowerModel {
+    fn power(&self, depositor: &Depositor, now: u64) -> u64;
+}
+
+pub struct Linear;
+
+impl VotingPowerModel for Linear {
+    fn power(&self, depositor: &Depositor, _now: u64) -> u64 {
+        depositor.amount
+    }
+}
+
+// 고래 예치자의 영향력을 줄이기 위해 예치 금액의 제곱근(내림)
+pub struct Quadratic;
+
+impl VotingPowerModel for Quadratic {
+    fn power(&self, depositor: &Depositor, _now: u64) -> u64 {
+        depositor.amount.isqrt()
+    }
+}
+
+// amount * (100 + max_bonus_percent * min(남은 잠금 기간, max_lock_secs) / max_lock_secs) / 100
+pub struct LockWeighted {
+    pub max_lock_secs: u64,
+    pub max_bonus_percent: u64,
+}
+
+impl VotingPowerModel for LockWeighted {
+    fn power(&self, depositor: &Depositor, now: u64) -> u64 {
+        if self.max_lock_secs == 0 {
+            return depositor.amount;
+        }
+
+        let remaining = depositor.locked_until.saturating_sub(now).min(self.max_lock_secs) as u128;
+        let bonus = self.max_bonus_percent as u128 * remaining / self.max_lock_secs as u128;
+        let power = depositor.amount as u128 * (100 + bonus) / 100;
+        power.min(u64::MAX as u128) as u64
+    }
+}
+
+pub struct Capped<M> {
+    pub base: M,
+    pub cap: u64,
+}
+
+impl<M: VotingPowerModel> VotingPowerModel for Capped<M> {
+    fn power(&self, depositor: &Depositor, now: u64) -> u64 {
+        self.base.power(depositor, now).min(self.cap)
+    }
+}
+
+impl VotingPowerModel for Box<dyn VotingPowerModel> {
+    fn power(&self, depositor: &Depositor, now: u64) -> u64 {
+        self.as_ref().power(depositor, now)
+    }
+}
+
+// 커뮤니티 설정에 해당하는 모델
+pub fn model_for(config: &VotingPowerConfig) -> Box<dyn VotingPowerModel> {
+    match config {
+        VotingPowerConfig::Linear => Box::new(Linear),
+        VotingPowerConfig::Quadratic => Box::new(Quadratic),
+        VotingPowerConfig::LockWeighted { max_lock_secs, max_bonus_percent } => Box::new(LockWeighted {
+            max_lock_secs: *max_lock_secs,
+            max_bonus_percent: *max_bonus_percent,
+        }),
+        VotingPowerConfig::Capped { cap, base } => Box::new(Capped {
+            base: model_for(base),
+            cap: *cap,
+        }),
+    }
+}
+
+// 커뮤니티 설정으로 예치 포지션의 투표 파워 계산
+pub fn voting_power(config: &VotingPowerConfig, depositor: &Depositor, now: u64) -> u64 {
+    model_for(config).power(depositor, now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::depositor;
+
+    #[test]
+    fn test_models_compute_power_from_position() {
+        let depositor = |amount, locked_until| Depositor { locked_until, ..depositor("wallet", amount) };
+        assert_eq!(voting_power(&VotingPowerConfig::Linear, &depositor(1000, 0), 0), 1000);
+        assert_eq!(voting_power(&VotingPowerConfig::Quadratic, &depositor(1000, 0), 0), 31);
+
+        // 남은 잠금 기간이 길수록 가산, 최대 기간을 넘으면 max_bonus_percent에서 멈춤
+        let lock = VotingPowerConfig::LockWeighted { max_lock_secs: 100, max_bonus_percent: 50 };
+        assert_eq!(voting_power(&lock, &depositor(1000, 0), 10), 1000);
+        assert_eq!(voting_power(&lock, &depositor(1000, 60), 10), 1250);
+        assert_eq!(voting_power(&lock, &depositor(1000, 10_000), 10), 1500);
+
+        let capped = VotingPowerConfig::Capped { cap: 20, base: Box::new(VotingPowerConfig::Quadratic) };
+        assert_eq!(voting_power(&capped, &depositor(100, 0), 0), 10);
+        assert_eq!(voting_power(&capped, &depositor(1_000_000, 0), 0), 20);
+    }
+
+    #[test]
+    fn test_config_defaults_to_linear() -> Result<(), serde_json::Error> {
+        let config: VotingPowerConfig = serde_json::from_str(r#"{"model":"capped","cap":5}"#)?;
+        assert_eq!(config, VotingPowerConfig::Capped { cap: 5, base: Box::new(VotingPowerConfig::Linear) });
+        assert_eq!(voting_power(&config, &depositor("wallet", 9), 0), 5);
+
+        Ok(())
+    }
+}
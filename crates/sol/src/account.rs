@@ -0,0 +1,610 @@
+use crate::pubkey::{Pubkey, PUBKEY_BYTES};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use solana_sdk::hash::hash;
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::path::Path;
+
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+// 자기 자신을 참조하는 타입 정의로 인한 무한 재귀 방지
+const MAX_TYPE_DEPTH: usize = 32;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AccountError {
+    Io(String),
+    InvalidIdl(String),
+    TooShort(usize),                    // 판별자(8바이트)보다 짧은 계정 데이터
+    UnknownDiscriminator([u8; DISCRIMINATOR_LEN]),
+    UnknownAccount(String),
+    UnknownType(String),
+    UnexpectedEnd(usize),               // Borsh 값을 읽는 중 데이터가 끝난 위치
+    InvalidBool(u8),
+    InvalidOption(u8),
+    InvalidVariant { ty: String, index: u8 },
+    InvalidUtf8(String),
+    TooDeep,
+    Mismatch(String),                   // 디코딩한 값이 요청한 구조체와 맞지 않음
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountError::Io(msg) => write!(f, "Failed to read IDL: {}", msg),
+            AccountError::InvalidIdl(msg) => write!(f, "Invalid IDL: {}", msg),
+            AccountError::TooShort(len) => write!(f, "Account data is {} bytes, shorter than the discriminator", len),
+            AccountError::UnknownDiscriminator(discriminator) => write!(f, "No account in IDL has discriminator {:?}", discriminator),
+            AccountError::UnknownAccount(name) => write!(f, "Unknown account {}", name),
+            AccountError::UnknownType(name) => write!(f, "IDL does not define type {}", name),
+            AccountError::UnexpectedEnd(offset) => write!(f, "Account data ended at byte {}", offset),
+            AccountError::InvalidBool(value) => write!(f, "Invalid bool byte {}", value),
+            AccountError::InvalidOption(value) => write!(f, "Invalid option tag {}", value),
+            AccountError::InvalidVariant { ty, index } => write!(f, "Enum {} has no variant {}", ty, index),
+            AccountError::InvalidUtf8(msg) => write!(f, "Invalid UTF-8 string: {}", msg),
+            AccountError::TooDeep => write!(f, "Type nesting exceeds {} levels", MAX_TYPE_DEPTH),
+            AccountError::Mismatch(msg) => write!(f, "Decoded account does not match: {}", msg),
+        }
+    }
+}
+
+impl StdError for AccountError {}
+
+// IDL 필드 타입 (Borsh 인코딩 단위)
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    U128,
+    I128,
+    Pubkey,
+    String,
+    Bytes,
+    Option(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    Defined(String),
+}
+
+#[derive(Clone, Debug)]
+enum Fields {
+    Named(Vec<(String, IdlType)>),
+    Tuple(Vec<IdlType>),                // 빈 목록이면 값 없는 enum variant
+}
+
+#[derive(Clone, Debug)]
+enum TypeDef {
+    Struct(Fields),
+    Enum(Vec<(String, Fields)>),
+}
+
+#[derive(Clone, Debug)]
+struct AccountLayout {
+    name: String,
+    discriminator: [u8; DISCRIMINATOR_LEN],
+}
+
+// Anchor IDL의 accounts/types로 계정 데이터를 디코딩
+#[derive(Clone, Debug)]
+pub struct AccountDecoder {
+    accounts: Vec<AccountLayout>,
+    types: HashMap<String, TypeDef>,
+}
+
+// Anchor 0.30 이전 IDL은 판별자가 없으므로 sha256("account:<Name>")의 앞 8바이트로 계산
+pub fn account_discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    let digest = hash(format!("account:{}", name).as_bytes()).to_bytes();
+    let mut discriminator = [0u8; DISCRIMINATOR_LEN];
+    discriminator.copy_from_slice(&digest[..DISCRIMINATOR_LEN]);
+    discriminator
+}
+
+// 예전 IDL의 camelCase 필드명을 서비스 구조체의 snake_case로 변환
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+fn idl_error(msg: impl Into<String>) -> AccountError {
+    AccountError::InvalidIdl(msg.into())
+}
+
+fn parse_type(value: &Value) -> Result<IdlType, AccountError> {
+    if let Some(name) = value.as_str() {
+        return Ok(match name {
+            "bool" => IdlType::Bool,
+            "u8" => IdlType::U8,
+            "i8" => IdlType::I8,
+            "u16" => IdlType::U16,
+            "i16" => IdlType::I16,
+            "u32" => IdlType::U32,
+            "i32" => IdlType::I32,
+            "u64" => IdlType::U64,
+            "i64" => IdlType::I64,
+            "u128" => IdlType::U128,
+            "i128" => IdlType::I128,
+            "pubkey" | "publicKey" => IdlType::Pubkey,
+            "string" => IdlType::String,
+            "bytes" => IdlType::Bytes,
+            other => return Err(idl_error(format!("unsupported type {}", other))),
+        });
+    }
+
+    let object = value.as_object().ok_or_else(|| idl_error(format!("unsupported type {}", value)))?;
+    if let Some(inner) = object.get("option") {
+        return Ok(IdlType::Option(Box::new(parse_type(inner)?)));
+    }
+    if let Some(inner) = object.get("vec") {
+        return Ok(IdlType::Vec(Box::new(parse_type(inner)?)));
+    }
+    if let Some(array) = object.get("array") {
+        let (inner, len) = match array.as_array().map(Vec::as_slice) {
+            Some([inner, len]) => (inner, len.as_u64()),
+            _ => return Err(idl_error(format!("invalid array type {}", array))),
+        };
+        let len = len.ok_or_else(|| idl_error(format!("array length must be a number: {}", array)))?;
+        return Ok(IdlType::Array(Box::new(parse_type(inner)?), len as usize));
+    }
+    if let Some(defined) = object.get("defined") {
+        // 0.30 이후는 {"defined": {"name": ..}}, 이전은 {"defined": ".."}
+        let name = defined.as_str()
+            .or_else(|| defined.get("name").and_then(Value::as_str))
+            .ok_or_else(|| idl_error(format!("invalid defined type {}", defined)))?;
+        return Ok(IdlType::Defined(name.to_string()));
+    }
+
+    Err(idl_error(format!("unsupported type {}", value)))
+}
+
+fn parse_fields(value: Option<&Value>) -> Result<Fields, AccountError> {
+    let Some(fields) = value else {
+        return Ok(Fields::Tuple(Vec::new()));
+    };
+    let fields = fields.as_array().ok_or_else(|| idl_error("fields must be an array"))?;
+
+    // 이름 있는 필드는 {"name", "type"}, 튜플 필드는 타입만 나열
+    if !fields.is_empty() && fields.iter().all(|field| field.get("name").is_some() && field.get("type").is_some()) {
+        let named = fields.iter()
+            .map(|field| {
+                let name = field["name"].as_str().ok_or_else(|| idl_error("field name must be a string"))?;
+                Ok((snake_case(name), parse_type(&field["type"])?))
+            })
+            .collect::<Result<_, AccountError>>()?;
+        Ok(Fields::Named(named))
+    } else {
+        Ok(Fields::Tuple(fields.iter().map(parse_type).collect::<Result<_, _>>()?))
+    }
+}
+
+fn parse_type_def(value: &Value) -> Result<TypeDef, AccountError> {
+    match value.get("kind").and_then(Value::as_str) {
+        Some("struct") => Ok(TypeDef::Struct(parse_fields(value.get("fields"))?)),
+        Some("enum") => {
+            let variants = value.get("variants")
+                .and_then(Value::as_array)
+                .ok_or_else(|| idl_error("enum must have variants"))?
+                .iter()
+                .map(|variant| {
+                    let name = variant.get("name")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| idl_error("variant name must be a string"))?;
+                    Ok((snake_case(name), parse_fields(variant.get("fields"))?))
+                })
+                .collect::<Result<_, AccountError>>()?;
+            Ok(TypeDef::Enum(variants))
+        },
+        other => Err(idl_error(format!("unsupported type kind {:?}", other))),
+    }
+}
+
+// Borsh 바이트를 앞에서부터 읽는 커서
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], AccountError> {
+        let end = self.offset.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(AccountError::UnexpectedEnd(self.data.len()))?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], AccountError> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(self.take(N)?);
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, AccountError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_len(&mut self) -> Result<usize, AccountError> {
+        Ok(u32::from_le_bytes(self.array()?) as usize)
+    }
+}
+
+// JSON 숫자로 표현할 수 없는 128비트 값은 문자열로
+fn u128_value(value: u128) -> Value {
+    u64::try_from(value).map(Value::from).unwrap_or_else(|_| Value::String(value.to_string()))
+}
+
+fn i128_value(value: i128) -> Value {
+    i64::try_from(value).map(Value::from).unwrap_or_else(|_| Value::String(value.to_string()))
+}
+
+impl AccountDecoder {
+    pub fn from_idl(idl: &str) -> Result<Self, AccountError> {
+        let idl: Value = serde_json::from_str(idl).map_err(|e| idl_error(e.to_string()))?;
+
+        let mut types = HashMap::new();
+        for def in idl.get("types").and_then(Value::as_array).into_iter().flatten() {
+            let name = def.get("name").and_then(Value::as_str).ok_or_else(|| idl_error("type name must be a string"))?;
+            let ty = def.get("type").ok_or_else(|| idl_error(format!("type {} has no definition", name)))?;
+            types.insert(name.to_string(), parse_type_def(ty)?);
+        }
+
+        let mut accounts = Vec::new();
+        for account in idl.get("accounts").and_then(Value::as_array).into_iter().flatten() {
+            let name = account.get("name").and_then(Value::as_str).ok_or_else(|| idl_error("account name must be a string"))?;
+
+            // 예전 IDL은 계정 레이아웃을 accounts 안에 직접 정의
+            if let Some(ty) = account.get("type") {
+                types.insert(name.to_string(), parse_type_def(ty)?);
+            }
+            if !types.contains_key(name) {
+                return Err(AccountError::UnknownType(name.to_string()));
+            }
+
+            let discriminator = match account.get("discriminator") {
+                Some(value) => serde_json::from_value::<[u8; DISCRIMINATOR_LEN]>(value.clone())
+                    .map_err(|e| idl_error(format!("invalid discriminator for {}: {}", name, e)))?,
+                None => account_discriminator(name),
+            };
+            accounts.push(AccountLayout { name: name.to_string(), discriminator });
+        }
+
+        Ok(AccountDecoder { accounts, types })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AccountError> {
+        let idl = std::fs::read_to_string(path).map_err(|e| AccountError::Io(e.to_string()))?;
+        Self::from_idl(&idl)
+    }
+
+    pub fn account_names(&self) -> impl Iterator<Item = &str> {
+        self.accounts.iter().map(|account| account.name.as_str())
+    }
+
+    // 판별자로 계정 종류를 찾아 JSON 값으로 디코딩 (할당 공간의 남는 뒷부분은 무시)
+    pub fn decode_value(&self, data: &[u8]) -> Result<(&str, Value), AccountError> {
+        if data.len() < DISCRIMINATOR_LEN {
+            return Err(AccountError::TooShort(data.len()));
+        }
+        let mut discriminator = [0u8; DISCRIMINATOR_LEN];
+        discriminator.copy_from_slice(&data[..DISCRIMINATOR_LEN]);
+
+        let account = self.accounts.iter()
+            .find(|account| account.discriminator == discriminator)
+            .ok_or(AccountError::UnknownDiscriminator(discriminator))?;
+
+        let mut reader = Reader { data, offset: DISCRIMINATOR_LEN };
+        let value = self.read_defined(&mut reader, &account.name, 0)?;
+        Ok((&account.name, value))
+    }
+
+    // 지정한 계정 종류로 디코딩해 구조체로 변환
+    pub fn decode<T: DeserializeOwned>(&self, name: &str, data: &[u8]) -> Result<T, AccountError> {
+        let (decoded, value) = self.decode_value(data)?;
+        if decoded != name {
+            return Err(AccountError::Mismatch(format!("expected {} account, got {}", name, decoded)));
+        }
+        serde_json::from_value(value).map_err(|e| AccountError::Mismatch(e.to_string()))
+    }
+
+    fn read_defined(&self, reader: &mut Reader, name: &str, depth: usize) -> Result<Value, AccountError> {
+        if depth > MAX_TYPE_DEPTH {
+            return Err(AccountError::TooDeep);
+        }
+        let def = self.types.get(name).ok_or_else(|| AccountError::UnknownType(name.to_string()))?;
+
+        match def {
+            TypeDef::Struct(fields) => self.read_fields(reader, fields, depth),
+            TypeDef::Enum(variants) => {
+                let index = reader.u8()?;
+                let (variant, fields) = variants.get(index as usize)
+                    .ok_or_else(|| AccountError::InvalidVariant { ty: name.to_string(), index })?;
+
+                // 값 없는 variant는 이름만, 있으면 {"이름": 값} (serde 외부 태그 형식)
+                match fields {
+                    Fields::Tuple(types) if types.is_empty() => Ok(Value::String(variant.clone())),
+                    fields => {
+                        let mut object = Map::new();
+                        object.insert(variant.clone(), self.read_fields(reader, fields, depth)?);
+                        Ok(Value::Object(object))
+                    },
+                }
+            },
+        }
+    }
+
+    fn read_fields(&self, reader: &mut Reader, fields: &Fields, depth: usize) -> Result<Value, AccountError> {
+        match fields {
+            Fields::Named(named) => {
+                let mut object = Map::new();
+                for (name, ty) in named {
+                    object.insert(name.clone(), self.read(reader, ty, depth + 1)?);
+                }
+                Ok(Value::Object(object))
+            },
+            Fields::Tuple(types) if types.len() == 1 => self.read(reader, &types[0], depth + 1),
+            Fields::Tuple(types) => types.iter()
+                .map(|ty| self.read(reader, ty, depth + 1))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+        }
+    }
+
+    fn read(&self, reader: &mut Reader, ty: &IdlType, depth: usize) -> Result<Value, AccountError> {
+        Ok(match ty {
+            IdlType::Bool => match reader.u8()? {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                other => return Err(AccountError::InvalidBool(other)),
+            },
+            IdlType::U8 => Value::from(reader.u8()?),
+            IdlType::I8 => Value::from(i8::from_le_bytes(reader.array()?)),
+            IdlType::U16 => Value::from(u16::from_le_bytes(reader.array()?)),
+            IdlType::I16 => Value::from(i16::from_le_bytes(reader.array()?)),
+            IdlType::U32 => Value::from(u32::from_le_bytes(reader.array()?)),
+            IdlType::I32 => Value::from(i32::from_le_bytes(reader.array()?)),
+            IdlType::U64 => Value::from(u64::from_le_bytes(reader.array()?)),
+            IdlType::I64 => Value::from(i64::from_le_bytes(reader.array()?)),
+            IdlType::U128 => u128_value(u128::from_le_bytes(reader.array()?)),
+            IdlType::I128 => i128_value(i128::from_le_bytes(reader.array()?)),
+            IdlType::Pubkey => Value::String(Pubkey::new_from_array(reader.array::<PUBKEY_BYTES>()?).to_string()),
+            IdlType::String => {
+                let len = reader.read_len()?;
+                let bytes = reader.take(len)?;
+                Value::String(String::from_utf8(bytes.to_vec()).map_err(|e| AccountError::InvalidUtf8(e.to_string()))?)
+            },
+            IdlType::Bytes => {
+                let len = reader.read_len()?;
+                Value::from(reader.take(len)?.to_vec())
+            },
+            IdlType::Option(inner) => match reader.u8()? {
+                0 => Value::Null,
+                1 => self.read(reader, inner, depth + 1)?,
+                other => return Err(AccountError::InvalidOption(other)),
+            },
+            IdlType::Vec(inner) => {
+                let len = reader.read_len()?;
+                // 길이 필드만 크고 데이터가 없는 경우를 미리 거름
+                if len > reader.data.len() - reader.offset {
+                    return Err(AccountError::UnexpectedEnd(reader.data.len()));
+                }
+                (0..len)
+                    .map(|_| self.read(reader, inner, depth + 1))
+                    .collect::<Result<_, _>>()
+                    .map(Value::Array)?
+            },
+            IdlType::Array(inner, len) => (0..*len)
+                .map(|_| self.read(reader, inner, depth + 1))
+                .collect::<Result<_, _>>()
+                .map(Value::Array)?,
+            IdlType::Defined(name) => self.read_defined(reader, name, depth + 1)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pubkey::parse_pubkey;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    // fixtures/*.bin은 아래 *_fixture 함수가 만드는 바이트 (판별자 8바이트 + Borsh, 정수는 little-endian).
+    // IDL이나 값이 바뀌면 함수를 고치고 같은 바이트로 파일을 다시 씀
+    const TURTLE_IDL: &str = include_str!("../fixtures/turtle_idl.json");
+    const COMMUNITY_ACCOUNT: &[u8] = include_bytes!("../fixtures/community.bin");
+    const CONTENT_ACCOUNT: &[u8] = include_bytes!("../fixtures/content.bin");
+    const DEPOSITOR_ACCOUNT: &[u8] = include_bytes!("../fixtures/depositor.bin");
+    const PROPOSAL_ACCOUNT: &[u8] = include_bytes!("../fixtures/proposal.bin");
+
+    // 시드 [1; 32], [2; 32] ed25519 키의 공개키
+    const ADMIN: &str = "AKnL4NNf3DGWZJS6cPknBuEGnVsV4A4m5tgebLHaRSZ9";
+    const MEMBER: &str = "9hSR6S7WPtxmTojgo6GG3k4yDPecgJY292j7xrsUGWBu";
+
+    // 테스트 계정 데이터 작성기
+    struct AccountWriter(Vec<u8>);
+
+    impl AccountWriter {
+        fn new(name: &str) -> Self {
+            Self(account_discriminator(name).to_vec())
+        }
+
+        fn bytes(mut self, bytes: &[u8]) -> Self {
+            self.0.extend_from_slice(bytes);
+            self
+        }
+
+        fn pubkey(self, value: &str) -> Self {
+            self.bytes(&parse_pubkey(value).unwrap().to_bytes())
+        }
+
+        fn u8(self, value: u8) -> Self {
+            self.bytes(&[value])
+        }
+
+        fn u64(self, value: u64) -> Self {
+            self.bytes(&value.to_le_bytes())
+        }
+
+        fn string(self, value: &str) -> Self {
+            self.bytes(&(value.len() as u32).to_le_bytes()).bytes(value.as_bytes())
+        }
+    }
+
+    fn community_fixture() -> Vec<u8> {
+        AccountWriter::new("Community")
+            .pubkey(ADMIN)              // admin
+            .u64(3600)                  // time_limit
+            .u64(100)                   // base_fee
+            .u8(1)                      // ai_moderation
+            .u8(50)                     // deposit_share
+            .u64(1_700_000_000)         // last_activity_timestamp
+            .u64(5000)                  // total_deposit
+            .u64(1)                     // active_proposal_count
+            .u64(2)                     // content_count
+            .u64(3)                     // depositor_count
+            .u8(0xfe)                   // bump
+            .0
+    }
+
+    fn content_fixture() -> Vec<u8> {
+        AccountWriter::new("Content")
+            .pubkey(MEMBER)             // author
+            .string("QmHash")           // content_hash
+            .string("ipfs://QmHash")    // content_uri
+            .u64(1_700_000_000)         // timestamp
+            .u64(7)                     // votes
+            .0
+    }
+
+    fn depositor_fixture() -> Vec<u8> {
+        AccountWriter::new("Depositor")
+            .pubkey(MEMBER)             // pubkey
+            .u64(5000)                  // amount
+            .u64(1_800_000_000)         // locked_until
+            .u64(5000)                  // voting_power
+            .0
+    }
+
+    fn proposal_fixture() -> Vec<u8> {
+        AccountWriter::new("Proposal")
+            .u64(4)                     // id
+            .u8(1)                      // proposal_type
+            .u64(250)                   // new_value
+            .u64(1_800_000_000)         // voting_end_time
+            .u64(30)                    // yes_votes
+            .u64(10)                    // no_votes
+            .u8(0)                      // is_executed
+            .0
+    }
+
+    #[derive(Deserialize)]
+    struct DepositorAccount {
+        pubkey: String,
+        amount: u64,
+        locked_until: u64,
+    }
+
+    #[test]
+    fn test_fixtures_match_layout() {
+        assert_eq!(COMMUNITY_ACCOUNT, community_fixture().as_slice());
+        assert_eq!(CONTENT_ACCOUNT, content_fixture().as_slice());
+        assert_eq!(DEPOSITOR_ACCOUNT, depositor_fixture().as_slice());
+        assert_eq!(PROPOSAL_ACCOUNT, proposal_fixture().as_slice());
+    }
+
+    #[test]
+    fn test_decode_turtle_accounts() -> Result<(), Box<dyn std::error::Error>> {
+        let decoder = AccountDecoder::from_idl(TURTLE_IDL)?;
+        assert_eq!(decoder.account_names().collect::<Vec<_>>(), vec!["Community", "Content", "Depositor", "Proposal"]);
+
+        let (name, community) = decoder.decode_value(COMMUNITY_ACCOUNT)?;
+        assert_eq!(name, "Community");
+        assert_eq!(community, json!({
+            "admin": ADMIN, "time_limit": 3600, "base_fee": 100, "ai_moderation": true, "deposit_share": 50,
+            "last_activity_timestamp": 1_700_000_000u64, "total_deposit": 5000, "active_proposal_count": 1,
+            "content_count": 2, "depositor_count": 3, "bump": 0xfe,
+        }));
+
+        let (_, content) = decoder.decode_value(CONTENT_ACCOUNT)?;
+        assert_eq!(content["author"], MEMBER);
+        assert_eq!((content["content_hash"].as_str(), content["content_uri"].as_str()), (Some("QmHash"), Some("ipfs://QmHash")));
+
+        // 필요한 필드만 가진 구조체로도 변환
+        let depositor: DepositorAccount = decoder.decode("Depositor", DEPOSITOR_ACCOUNT)?;
+        assert_eq!((depositor.pubkey.as_str(), depositor.amount, depositor.locked_until), (MEMBER, 5000, 1_800_000_000));
+
+        let (_, proposal) = decoder.decode_value(PROPOSAL_ACCOUNT)?;
+        assert_eq!((proposal["id"].as_u64(), proposal["proposal_type"].as_u64(), proposal["new_value"].as_u64()), (Some(4), Some(1), Some(250)));
+        assert_eq!(proposal["is_executed"], false);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_malformed_account_data() -> Result<(), Box<dyn std::error::Error>> {
+        let decoder = AccountDecoder::from_idl(TURTLE_IDL)?;
+
+        assert_eq!(decoder.decode_value(&COMMUNITY_ACCOUNT[..4]).err(), Some(AccountError::TooShort(4)));
+        assert!(matches!(decoder.decode_value(&[0u8; 64]), Err(AccountError::UnknownDiscriminator(_))));
+        assert_eq!(
+            decoder.decode_value(&CONTENT_ACCOUNT[..CONTENT_ACCOUNT.len() - 1]).err(),
+            Some(AccountError::UnexpectedEnd(CONTENT_ACCOUNT.len() - 1))
+        );
+        assert!(matches!(decoder.decode::<DepositorAccount>("Depositor", PROPOSAL_ACCOUNT), Err(AccountError::Mismatch(_))));
+
+        // 계정에 할당된 여분 공간은 무시
+        let mut padded = DEPOSITOR_ACCOUNT.to_vec();
+        padded.extend_from_slice(&[0u8; 16]);
+        assert_eq!(decoder.decode::<DepositorAccount>("Depositor", &padded)?.amount, 5000);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_legacy_idl_with_enums_and_options() -> Result<(), Box<dyn std::error::Error>> {
+        // 판별자가 없고 camelCase 필드명을 쓰는 예전 IDL
+        let idl = r#"{
+            "accounts": [{ "name": "Settings", "type": { "kind": "struct", "fields": [
+                { "name": "roundWinner", "type": { "defined": "RoundWinnerRule" } },
+                { "name": "maxStake", "type": { "option": "u64" } },
+                { "name": "tags", "type": { "vec": "string" } }
+            ] } }],
+            "types": [{ "name": "RoundWinnerRule", "type": { "kind": "enum", "variants": [
+                { "name": "Votes" }, { "name": "StakeWeighted" }
+            ] } }]
+        }"#;
+        let decoder = AccountDecoder::from_idl(idl)?;
+
+        let mut data = account_discriminator("Settings").to_vec();
+        data.push(1);
+        data.extend_from_slice(&[1, 10, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, b'o', b'k']);
+
+        let (name, value) = decoder.decode_value(&data)?;
+        assert_eq!(name, "Settings");
+        assert_eq!(value, serde_json::json!({ "round_winner": "stake_weighted", "max_stake": 10, "tags": ["ok"] }));
+
+        data[DISCRIMINATOR_LEN] = 2;
+        assert!(matches!(decoder.decode_value(&data), Err(AccountError::InvalidVariant { index: 2, .. })));
+
+        Ok(())
+    }
+}
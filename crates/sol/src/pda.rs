@@ -0,0 +1,117 @@
+use crate::pubkey::{validate_pubkey, Curve, PubkeyError};
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use std::error::Error as StdError;
+use std::fmt;
+
+// 커뮤니티 계정 시드: [COMMUNITY_SEED, admin]
+pub const COMMUNITY_SEED: &[u8] = b"community";
+
+// 예치 계정 시드: [DEPOSITOR_SEED, community, wallet]
+pub const DEPOSITOR_SEED: &[u8] = b"depositor";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PdaError {
+    InvalidAddress(PubkeyError),
+    TooManySeeds(usize),
+    SeedTooLong(usize),
+    NoViableBump,                       // 모든 bump가 곡선 위로 떨어짐
+    Mismatch { expected: String },      // 시드에서 유도한 주소와 다름
+}
+
+impl fmt::Display for PdaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PdaError::InvalidAddress(e) => write!(f, "{}", e),
+            PdaError::TooManySeeds(count) => write!(f, "At most {} seeds are allowed, got {}", MAX_SEEDS - 1, count),
+            PdaError::SeedTooLong(len) => write!(f, "Seed must be at most {} bytes, got {}", MAX_SEED_LEN, len),
+            PdaError::NoViableBump => write!(f, "No bump seed produces an off-curve address"),
+            PdaError::Mismatch { expected } => write!(f, "Address does not match derived PDA {}", expected),
+        }
+    }
+}
+
+impl StdError for PdaError {}
+
+impl From<PubkeyError> for PdaError {
+    fn from(error: PubkeyError) -> Self {
+        PdaError::InvalidAddress(error)
+    }
+}
+
+// bump 자리를 남겨 두어야 하므로 시드는 MAX_SEEDS - 1 개까지
+fn check_seeds(seeds: &[&[u8]]) -> Result<(), PdaError> {
+    if seeds.len() > MAX_SEEDS - 1 {
+        return Err(PdaError::TooManySeeds(seeds.len()));
+    }
+    if let Some(seed) = seeds.iter().find(|seed| seed.len() > MAX_SEED_LEN) {
+        return Err(PdaError::SeedTooLong(seed.len()));
+    }
+    Ok(())
+}
+
+// 시드와 프로그램 ID로 canonical PDA와 bump를 찾음
+pub fn find_pda(seeds: &[&[u8]], program_id: &Pubkey) -> Result<(Pubkey, u8), PdaError> {
+    check_seeds(seeds)?;
+    Pubkey::try_find_program_address(seeds, program_id).ok_or(PdaError::NoViableBump)
+}
+
+// 주소가 곡선 밖에 있고 시드에서 유도한 PDA와 같은지 확인, bump 반환
+pub fn verify_pda(address: &str, seeds: &[&[u8]], program_id: &Pubkey) -> Result<u8, PdaError> {
+    let address = validate_pubkey(address, Curve::OffCurve)?;
+    let (expected, bump) = find_pda(seeds, program_id)?;
+
+    if address != expected {
+        return Err(PdaError::Mismatch { expected: expected.to_string() });
+    }
+    Ok(bump)
+}
+
+pub fn community_pda(admin: &Pubkey, program_id: &Pubkey) -> Result<(Pubkey, u8), PdaError> {
+    find_pda(&[COMMUNITY_SEED, admin.as_ref()], program_id)
+}
+
+pub fn depositor_pda(community: &Pubkey, wallet: &Pubkey, program_id: &Pubkey) -> Result<(Pubkey, u8), PdaError> {
+    find_pda(&[DEPOSITOR_SEED, community.as_ref(), wallet.as_ref()], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_verify_community_pda() -> Result<(), Box<dyn std::error::Error>> {
+        let program_id = Pubkey::new_unique();
+        let admin = Keypair::new().pubkey();
+        let (pda, bump) = community_pda(&admin, &program_id)?;
+
+        let seeds: &[&[u8]] = &[COMMUNITY_SEED, admin.as_ref()];
+        assert_eq!(verify_pda(&pda.to_string(), seeds, &program_id)?, bump);
+
+        // 다른 관리자의 PDA는 불일치
+        let other = Keypair::new().pubkey();
+        let (other_pda, _) = community_pda(&other, &program_id)?;
+        assert_eq!(
+            verify_pda(&other_pda.to_string(), seeds, &program_id),
+            Err(PdaError::Mismatch { expected: pda.to_string() })
+        );
+
+        // 지갑 주소는 PDA가 될 수 없음
+        assert_eq!(
+            verify_pda(&admin.to_string(), seeds, &program_id),
+            Err(PdaError::InvalidAddress(PubkeyError::OnCurve))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seed_limits() {
+        let program_id = Pubkey::new_unique();
+        let long_seed = [0u8; MAX_SEED_LEN + 1];
+        assert_eq!(find_pda(&[long_seed.as_ref()], &program_id), Err(PdaError::SeedTooLong(MAX_SEED_LEN + 1)));
+
+        let seeds: Vec<&[u8]> = vec![b"s".as_ref(); MAX_SEEDS];
+        assert_eq!(find_pda(&seeds, &program_id), Err(PdaError::TooManySeeds(MAX_SEEDS)));
+    }
+}
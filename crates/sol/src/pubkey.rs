@@ -0,0 +1,94 @@
+pub use solana_sdk::pubkey::Pubkey;
+use std::error::Error as StdError;
+use std::fmt;
+
+pub const PUBKEY_BYTES: usize = 32;
+
+// 주소가 ed25519 곡선 위에 있어야 하는지
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    Any,
+    OnCurve,                            // 지갑 주소 (개인키가 존재)
+    OffCurve,                           // PDA (프로그램만 서명 가능)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PubkeyError {
+    Empty,
+    InvalidBase58(String),
+    InvalidLength(usize),
+    OnCurve,                            // PDA 자리에 지갑 주소
+    OffCurve,                           // 지갑 자리에 PDA 또는 곡선 밖의 바이트
+}
+
+impl fmt::Display for PubkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PubkeyError::Empty => write!(f, "Public key is empty"),
+            PubkeyError::InvalidBase58(msg) => write!(f, "Public key is not valid base58: {}", msg),
+            PubkeyError::InvalidLength(len) => write!(f, "Public key must be {} bytes, got {}", PUBKEY_BYTES, len),
+            PubkeyError::OnCurve => write!(f, "Address is on the ed25519 curve and cannot be a PDA"),
+            PubkeyError::OffCurve => write!(f, "Address is off the ed25519 curve and cannot be a wallet"),
+        }
+    }
+}
+
+impl StdError for PubkeyError {}
+
+// base58 문자열을 32바이트 공개키로 변환
+pub fn parse_pubkey(value: &str) -> Result<Pubkey, PubkeyError> {
+    if value.is_empty() {
+        return Err(PubkeyError::Empty);
+    }
+
+    let bytes = bs58::decode(value)
+        .into_vec()
+        .map_err(|e| PubkeyError::InvalidBase58(e.to_string()))?;
+    let bytes: [u8; PUBKEY_BYTES] = bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| PubkeyError::InvalidLength(bytes.len()))?;
+
+    Ok(Pubkey::new_from_array(bytes))
+}
+
+pub fn validate_pubkey(value: &str, curve: Curve) -> Result<Pubkey, PubkeyError> {
+    let pubkey = parse_pubkey(value)?;
+
+    match (curve, pubkey.is_on_curve()) {
+        (Curve::OnCurve, false) => Err(PubkeyError::OffCurve),
+        (Curve::OffCurve, true) => Err(PubkeyError::OnCurve),
+        _ => Ok(pubkey),
+    }
+}
+
+// 개인키로 서명할 수 있는 지갑 주소
+pub fn parse_wallet(value: &str) -> Result<Pubkey, PubkeyError> {
+    validate_pubkey(value, Curve::OnCurve)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    fn test_parse_pubkey_rejects_malformed_input() {
+        assert_eq!(parse_pubkey(""), Err(PubkeyError::Empty));
+        assert!(matches!(parse_pubkey("0OIl"), Err(PubkeyError::InvalidBase58(_))));
+        assert_eq!(parse_pubkey(&bs58::encode([1u8; 31]).into_string()), Err(PubkeyError::InvalidLength(31)));
+
+        let pubkey = Pubkey::new_unique();
+        assert_eq!(parse_pubkey(&pubkey.to_string()), Ok(pubkey));
+    }
+
+    #[test]
+    fn test_curve_requirement() {
+        let wallet = Keypair::new().pubkey();
+        let (pda, _) = Pubkey::find_program_address(&[b"turtle".as_ref()], &Pubkey::new_unique());
+
+        assert_eq!(parse_wallet(&wallet.to_string()), Ok(wallet));
+        assert_eq!(parse_wallet(&pda.to_string()), Err(PubkeyError::OffCurve));
+        assert_eq!(validate_pubkey(&wallet.to_string(), Curve::OffCurve), Err(PubkeyError::OnCurve));
+        assert_eq!(validate_pubkey(&pda.to_string(), Curve::Any), Ok(pda));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::pubkey::Pubkey;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::error::Error as StdError;
+use std::fmt;
+
+// 온체인 계정 조회 실패 (RPC 연결, 응답 오류)
+#[derive(Debug, PartialEq, Eq)]
+pub struct RpcError(pub String);
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RPC error: {}", self.0)
+    }
+}
+
+impl StdError for RpcError {}
+
+// 조회한 계정의 소유 프로그램과 원본 데이터
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainAccount {
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+// 계정 조회 경로. 서버는 RPC를, 테스트는 메모리 구현을 사용
+pub trait AccountSource: fmt::Debug + Send + Sync {
+    // 계정이 없으면 None
+    fn account(&self, address: &Pubkey) -> Result<Option<ChainAccount>, RpcError>;
+}
+
+// 확정(confirmed) 상태의 계정을 JSON-RPC로 조회. 블로킹 호출이므로 비동기 문맥에서는 spawn_blocking으로 호출
+pub struct RpcAccountSource {
+    client: RpcClient,
+}
+
+impl RpcAccountSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: RpcClient::new_with_commitment(url.into(), CommitmentConfig::confirmed()),
+        }
+    }
+}
+
+impl fmt::Debug for RpcAccountSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcAccountSource").field("url", &self.client.url()).finish()
+    }
+}
+
+impl AccountSource for RpcAccountSource {
+    fn account(&self, address: &Pubkey) -> Result<Option<ChainAccount>, RpcError> {
+        let response = self.client.get_account_with_commitment(address, self.client.commitment())
+            .map_err(|e| RpcError(e.to_string()))?;
+
+        Ok(response.value.map(|account| ChainAccount {
+            owner: account.owner,
+            data: account.data,
+        }))
+    }
+}